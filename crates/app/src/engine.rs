@@ -1,190 +1,381 @@
 use anyhow::Result;
 use screenhop_core::config::AppConfig;
-use screenhop_core::monitor;
-use screenhop_core::Point;
-use std::sync::atomic::{AtomicBool, Ordering};
+use screenhop_core::frame_memory::FrameMemory;
+use screenhop_core::hotkey::{HotkeyAction, KeyCombo};
+use screenhop_platform::engine::HopEngine;
+use screenhop_platform::worker::HopWorker;
+use screenhop_platform::{
+    HitTester, HookHealth, KeyboardHook, MonitorManager, MouseEvent, MouseHook, WindowManager,
+};
+use std::cell::RefCell;
+use std::sync::Arc;
 
-/// 全局标志：钩子是否处于启用状态（true = 处理事件，false = 放行所有事件）
-static HOOK_ENABLED: AtomicBool = AtomicBool::new(true);
+/// 擦除平台具体类型后的鼠标钩子，便于托盘菜单卸载 / 重新安装
+trait HookControl {
+    /// 安装系统钩子（已安装时会先卸载再安装）
+    fn install(&mut self) -> Result<()>;
 
-/// 动态启用或禁用鼠标中键移动功能
-/// 在托盘菜单切换时调用，无需重新安装钩子
-pub fn set_hook_enabled(enabled: bool) {
-    HOOK_ENABLED.store(enabled, Ordering::SeqCst);
-    log::info!(
-        "鼠标中键移动功能已{}",
-        if enabled { "启用" } else { "禁用" }
-    );
+    /// 卸载系统钩子
+    fn uninstall(&mut self) -> Result<()>;
+
+    /// 钩子的运行状况
+    fn health(&self) -> HookHealth;
+
+    /// 撤销最近一次移动（交给工作线程执行）
+    fn undo(&self) -> bool;
+
+    /// 重做最近一次撤销的移动（交给工作线程执行）
+    fn redo(&self) -> bool;
 }
 
-/// 安装鼠标中键钩子，注册事件处理逻辑
-pub fn install_hook(config: &AppConfig) -> Result<()> {
-    // 根据配置初始化全局启用状态
-    HOOK_ENABLED.store(!config.disable_hook, Ordering::SeqCst);
-    let title_bar_height = config.title_bar_height;
+/// 擦除平台具体类型后的全局快捷键
+trait HotkeyControl {
+    fn register(&mut self, hotkeys: &[KeyCombo], callback: Box<dyn Fn(usize) + Send>)
+        -> Result<()>;
 
-    #[cfg(target_os = "macos")]
-    {
-        use screenhop_platform::macos::hook::MacMouseHook;
-        let mut hook = MacMouseHook::new();
-        hook.install_event_tap(move |event| handle_middle_click(event.point, title_bar_height))?;
+    fn unregister(&mut self) -> Result<()>;
+}
+
+impl<B: KeyboardHook> HotkeyControl for B {
+    fn register(
+        &mut self,
+        hotkeys: &[KeyCombo],
+        callback: Box<dyn Fn(usize) + Send>,
+    ) -> Result<()> {
+        self.start(hotkeys, callback)
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        use screenhop_platform::windows::hook::WinMouseHook;
-        let mut hook = WinMouseHook::new();
-        hook.install_hook(move |event| handle_middle_click(event.point, title_bar_height))?;
+    fn unregister(&mut self) -> Result<()> {
+        self.stop()
     }
+}
 
-    log::info!("鼠标中键移动引擎已启动");
-    Ok(())
+/// 鼠标钩子、全局快捷键与它们转发事件的跳转工作线程
+///
+/// 每次安装都按当时的配置重新创建；卸载后引擎仍然保留，撤销记录在禁用期间可用。
+struct EngineHook<K, W, H, M> {
+    hook: K,
+    /// 当前平台不支持全局快捷键时为 None
+    keyboard: Option<Box<dyn HotkeyControl>>,
+    /// 配置中解析成功的快捷键
+    hotkeys: Vec<(KeyCombo, HotkeyAction)>,
+    worker: Arc<HopWorker<W, H, M>>,
 }
 
-/// 处理中键点击事件
-/// 返回 true 表示事件已消费（窗口已移动），返回 false 表示放行事件
-fn handle_middle_click(point: Point, title_bar_height: f64) -> bool {
-    // 如果功能已被用户禁用，直接放行事件
-    if !HOOK_ENABLED.load(Ordering::SeqCst) {
-        return false;
-    }
-    #[cfg(target_os = "windows")]
-    let _ = title_bar_height; // Windows uses WM_NCHITTEST instead of height
-    use screenhop_platform::{HitTester, MonitorManager, WindowManager};
+impl<K, W, H, M> HookControl for EngineHook<K, W, H, M>
+where
+    K: MouseHook,
+    W: WindowManager + Send + Sync + 'static,
+    H: HitTester + Send + Sync + 'static,
+    M: MonitorManager + Send + Sync + 'static,
+{
+    fn install(&mut self) -> Result<()> {
+        let worker = self.worker.clone();
+        self.hook
+            .start(move |event| handle_mouse_event(&worker, &event))?;
 
-    // 根据平台创建对应实现
-    #[cfg(target_os = "macos")]
-    let (wm, monitor_mgr, hit_tester) = {
-        use screenhop_platform::macos::{
-            hittest::MacHitTester, monitor::MacMonitorManager, window::MacWindowManager,
-        };
-        let mut ht = MacHitTester::new();
-        ht.set_title_bar_height(title_bar_height);
-        (MacWindowManager::new(), MacMonitorManager::new(), ht)
-    };
+        if let (Some(keyboard), false) = (self.keyboard.as_mut(), self.hotkeys.is_empty()) {
+            let combos: Vec<KeyCombo> = self.hotkeys.iter().map(|(combo, _)| *combo).collect();
+            let actions: Vec<HotkeyAction> =
+                self.hotkeys.iter().map(|(_, action)| *action).collect();
+            let worker = self.worker.clone();
+            let callback = move |index: usize| {
+                if let Some(&action) = actions.get(index) {
+                    worker.handle_hotkey(action);
+                }
+            };
 
-    #[cfg(target_os = "windows")]
-    let (wm, monitor_mgr, hit_tester) = {
-        use screenhop_platform::windows::{
-            hittest::WinHitTester, monitor::WinMonitorManager, window::WinWindowManager,
-        };
-        (
-            WinWindowManager::new(),
-            WinMonitorManager::new(),
-            WinHitTester::new(),
-        )
-    };
-
-    // 1. 获取点击位置的窗口
-    let handle = match wm.get_window_at(point) {
-        Some(h) => h,
-        None => {
-            log::debug!("点击位置没有窗口");
-            return false;
+            // 快捷键注册失败不影响鼠标移动功能
+            if let Err(e) = keyboard.register(&combos, Box::new(callback)) {
+                log::warn!("无法注册全局快捷键: {}", e);
+            }
         }
-    };
-
-    // 2. 检查是否点击在交互式标签页上（不移动）
-    if hit_tester.is_interactive_tab(&handle, point) {
-        log::debug!("点击在交互式标签页上，跳过");
-        return false;
+        Ok(())
     }
 
-    // 3. 获取窗口 frame
-    let frame = match wm.get_window_frame(&handle) {
-        Some(f) => f,
-        None => {
-            log::debug!("无法获取窗口 frame");
-            return false;
+    fn uninstall(&mut self) -> Result<()> {
+        self.hook.stop()?;
+        if let Some(keyboard) = self.keyboard.as_mut() {
+            keyboard.unregister()?;
         }
-    };
 
-    // 4. 检查是否在标题栏区域内
-    #[cfg(target_os = "macos")]
-    let in_title_bar = monitor::is_in_title_bar(point, &frame, title_bar_height);
+        let stats = self.worker.stats();
+        log::info!(
+            "点击判定 {} 次（平均 {:?}，最大 {:?}），移动窗口 {} 次（平均 {:?}，最大 {:?}）",
+            stats.decision.count,
+            stats.decision.average(),
+            stats.decision.max,
+            stats.hop.count,
+            stats.hop.average(),
+            stats.hop.max,
+        );
+        Ok(())
+    }
 
-    #[cfg(target_os = "windows")]
-    let in_title_bar = hit_tester.is_title_bar_hit(&handle, point);
+    fn health(&self) -> HookHealth {
+        self.hook.health()
+    }
 
-    if !in_title_bar {
-        log::debug!("点击不在标题栏内");
-        return false;
+    fn undo(&self) -> bool {
+        self.worker.undo()
     }
 
-    // 5. 获取所有显示器
-    let monitors = monitor_mgr.get_monitors();
-    if monitors.len() < 2 {
-        log::debug!("只有一个显示器，无法移动");
-        return false;
+    fn redo(&self) -> bool {
+        self.worker.redo()
     }
+}
 
-    // 6. 找到窗口当前所在的显示器
-    let window_center = Point {
-        x: frame.mid_x(),
-        y: frame.mid_y(),
-    };
-    let current_idx = match monitor::find_monitor_for_point(window_center, &monitors) {
-        Some(idx) => idx,
-        None => {
-            log::debug!("无法确定窗口所在显示器");
-            return false;
-        }
-    };
+thread_local! {
+    /// 已创建的钩子
+    ///
+    /// macOS 的 CGEventTap 和 Windows 的 WH_MOUSE_LL 都绑定在安装线程的事件循环上，
+    /// 安装和卸载都发生在主线程（启动时与托盘菜单回调中），因此按线程存放。
+    static HOOK: RefCell<Option<Box<dyn HookControl>>> = const { RefCell::new(None) };
+}
 
-    // 7. 计算目标显示器和新位置
-    let next_idx = monitor::next_monitor_index(current_idx, monitors.len());
-    let (new_pos, new_width, new_height) =
-        monitor::calculate_new_position(&frame, &monitors[current_idx], &monitors[next_idx]);
+/// 动态启用或禁用鼠标中键移动功能
+/// 在托盘菜单切换时调用：禁用时真正卸载系统钩子，启用时重新安装（无需重启）
+pub fn set_hook_enabled(enabled: bool, config: &AppConfig) -> Result<()> {
+    if enabled {
+        install_hook(config)?;
+    } else {
+        uninstall_hook()?;
+    }
 
     log::info!(
-        "移动窗口: 显示器 {} → {}, 位置 ({:.0},{:.0}) → ({:.0},{:.0})",
-        current_idx,
-        next_idx,
-        frame.x,
-        frame.y,
-        new_pos.x,
-        new_pos.y,
+        "鼠标中键移动功能已{}",
+        if enabled { "启用" } else { "禁用" }
     );
+    Ok(())
+}
 
-    // 8. Windows: 如果窗口是最大化的，先还原并记录状态
-    #[cfg(target_os = "windows")]
-    let was_maximized = {
-        let is_max = wm.is_maximized(&handle);
-        if is_max {
-            if let Err(e) = wm.restore_window(&handle) {
-                log::error!("还原窗口失败: {}", e);
-            }
-        }
-        is_max
-    };
+/// 卸载鼠标钩子（保留引擎，禁用期间仍可撤销）
+pub fn uninstall_hook() -> Result<()> {
+    HOOK.with(|slot| match slot.borrow_mut().as_mut() {
+        Some(hook) => hook.uninstall(),
+        None => Ok(()),
+    })
+}
 
-    // 9. 设置新位置和尺寸
-    if let Err(e) = wm.set_window_position(&handle, new_pos) {
-        log::error!("设置窗口位置失败: {}", e);
-        return false;
-    }
+/// 当前钩子的运行状况（尚未创建钩子时返回 None）
+pub fn hook_health() -> Option<HookHealth> {
+    HOOK.with(|slot| slot.borrow().as_ref().map(|hook| hook.health()))
+}
+
+/// 撤销最近一次窗口移动（托盘菜单调用）
+///
+/// 移动记录保存在引擎中，禁用钩子后仍然可以撤销；尚未创建引擎时返回 false。
+pub fn undo_hop() -> bool {
+    HOOK.with(|slot| slot.borrow().as_ref().is_some_and(|hook| hook.undo()))
+}
+
+/// 重做最近一次撤销的窗口移动（托盘菜单调用）
+pub fn redo_hop() -> bool {
+    HOOK.with(|slot| slot.borrow().as_ref().is_some_and(|hook| hook.redo()))
+}
 
-    // 如果窗口尺寸需要调整（目标显示器更小）
-    if (new_width - frame.width).abs() > 1.0 || (new_height - frame.height).abs() > 1.0 {
-        if let Err(e) = wm.set_window_size(&handle, new_width, new_height) {
-            log::error!("设置窗口尺寸失败: {}", e);
+/// 安装鼠标中键钩子，注册事件处理逻辑
+///
+/// 每次调用都按传入的配置重新创建平台钩子和跳转引擎，托盘切换时修改过的配置随之生效。
+/// 旧的钩子先卸载再替换；启用持久化时窗口记忆从磁盘重新加载。
+pub fn install_hook(config: &AppConfig) -> Result<()> {
+    HOOK.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if let Some(mut old) = slot.take() {
+            old.uninstall()?;
         }
+
+        let mut hook = create_hook(config)?;
+        hook.install()?;
+        *slot = Some(hook);
+
+        log::info!("鼠标中键移动引擎已启动");
+        Ok(())
+    })
+}
+
+/// 创建当前平台的鼠标钩子和跳转引擎（尚未安装）
+fn create_hook(config: &AppConfig) -> Result<Box<dyn HookControl>> {
+    #[cfg(target_os = "macos")]
+    {
+        use screenhop_platform::macos::{
+            hittest::MacHitTester, hook::MacMouseHook, hotkey::MacKeyboardHook,
+            monitor::MacMonitorManager, window::MacWindowManager,
+        };
+        let mut hit_tester = MacHitTester::new();
+        hit_tester.set_title_bar_height(config.title_bar_height);
+        let engine = build_engine(
+            MacWindowManager::new(),
+            hit_tester,
+            MacMonitorManager::new(),
+            config,
+        );
+
+        engine_hook(
+            MacMouseHook::new(),
+            Some(Box::new(MacKeyboardHook::new())),
+            engine,
+            config,
+        )
     }
 
-    // 新增：如果移动前是最大化状态，移动后恢复最大化
     #[cfg(target_os = "windows")]
     {
-        if was_maximized {
-            if let Err(e) = wm.maximize_window(&handle) {
-                log::error!("恢复窗口最大化失败: {}", e);
+        use screenhop_platform::windows::{
+            hittest::WinHitTester, hook::WinMouseHook, hotkey::WinKeyboardHook,
+            monitor::WinMonitorManager, window::WinWindowManager,
+        };
+        // Windows 使用 WM_NCHITTEST 判断标题栏，不需要 title_bar_height
+        let engine = build_engine(
+            WinWindowManager::new(),
+            WinHitTester::new(),
+            WinMonitorManager::new(),
+            config,
+        );
+
+        engine_hook(
+            WinMouseHook::new(),
+            Some(Box::new(WinKeyboardHook::new())),
+            engine,
+            config,
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // Wayland 会话优先使用 evdev + 合成器 IPC，不支持时退回 X11（XWayland，sway 除外）
+        let wayland_session = std::env::var_os("WAYLAND_DISPLAY").is_some();
+        if wayland_session {
+            match screenhop_platform::create_wayland_platform(config.wheel.enabled) {
+                Ok(platform) => {
+                    let mut hit_tester = platform.hit_tester;
+                    hit_tester.set_title_bar_height(config.title_bar_height);
+                    let engine = build_engine(
+                        platform.window_manager,
+                        hit_tester,
+                        platform.monitor_manager,
+                        config,
+                    );
+
+                    // evdev 只读取输入设备，无法独占按键，快捷键交给合成器绑定
+                    if !config.hotkeys.is_empty() {
+                        log::warn!("Wayland 下不支持全局快捷键，请在合成器配置中绑定");
+                    }
+                    return engine_hook(platform.hook, None, engine, config);
+                }
+                // sway 下的 X11（XWayland）钩子收不到原生窗口的输入，直接报告不支持
+                Err(e) if std::env::var_os("SWAYSOCK").is_some() => return Err(e),
+                Err(e) => log::warn!("无法使用 Wayland 后端，退回 X11: {}", e),
+            }
+        } else if std::env::var_os("I3SOCK").is_some() {
+            // i3 的平铺窗口不响应 EWMH 移动请求，窗口操作改走 i3 IPC（标题栏高度取自布局树）
+            match screenhop_platform::create_i3_platform() {
+                Ok(platform) => {
+                    let engine = build_engine(
+                        platform.window_manager,
+                        platform.hit_tester,
+                        platform.monitor_manager,
+                        config,
+                    );
+                    return engine_hook(
+                        platform.hook,
+                        Some(Box::new(platform.keyboard)),
+                        engine,
+                        config,
+                    );
+                }
+                Err(e) => log::warn!("无法使用 i3 IPC，退回 EWMH: {}", e),
             }
         }
+
+        let platform = screenhop_platform::create_platform()?;
+        let mut hit_tester = platform.hit_tester;
+        hit_tester.set_title_bar_height(config.title_bar_height);
+        let engine = build_engine(
+            platform.window_manager,
+            hit_tester,
+            platform.monitor_manager,
+            config,
+        );
+
+        engine_hook(
+            platform.hook,
+            Some(Box::new(platform.keyboard)),
+            engine,
+            config,
+        )
     }
+}
+
+/// 启动跳转工作线程，并与钩子和快捷键组合在一起
+fn engine_hook<K, W, H, M>(
+    hook: K,
+    keyboard: Option<Box<dyn HotkeyControl>>,
+    engine: HopEngine<W, H, M>,
+    config: &AppConfig,
+) -> Result<Box<dyn HookControl>>
+where
+    K: MouseHook + 'static,
+    W: WindowManager + Send + Sync + 'static,
+    H: HitTester + Send + Sync + 'static,
+    M: MonitorManager + Send + Sync + 'static,
+{
+    // 无法解析的快捷键只跳过该条，不影响其他快捷键
+    let hotkeys = config
+        .hotkeys
+        .iter()
+        .filter_map(|hotkey| match hotkey.combo() {
+            Ok(combo) => Some((combo, hotkey.action)),
+            Err(e) => {
+                log::warn!("忽略无效的快捷键配置: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let worker = HopWorker::spawn(Arc::new(engine))?;
+    Ok(Box::new(EngineHook {
+        hook,
+        keyboard,
+        hotkeys,
+        worker: Arc::new(worker),
+    }))
+}
 
-    // 10. 激活窗口
-    if let Err(e) = wm.activate_window(&handle) {
-        log::error!("激活窗口失败: {}", e);
+/// 创建跳转引擎（启用持久化时从磁盘加载窗口记忆）
+fn build_engine<W, H, M>(
+    window_manager: W,
+    hit_tester: H,
+    monitor_manager: M,
+    config: &AppConfig,
+) -> HopEngine<W, H, M>
+where
+    W: WindowManager,
+    H: HitTester,
+    M: MonitorManager,
+{
+    let engine = HopEngine::new(window_manager, hit_tester, monitor_manager, config.clone());
+
+    if !config.persist_frames {
+        return engine;
     }
 
-    true // 事件已消费
+    match FrameMemory::default_path() {
+        Ok(path) => engine.with_frame_memory_file(path),
+        Err(e) => {
+            log::warn!("无法获取窗口记忆文件路径: {}", e);
+            engine
+        }
+    }
+}
+
+/// 处理鼠标事件（在系统钩子回调中执行，按触发条件过滤后只做快速判定）
+/// 返回 true 表示事件已消费（窗口将在工作线程中移动），返回 false 表示放行事件
+fn handle_mouse_event<W, H, M>(worker: &HopWorker<W, H, M>, event: &MouseEvent) -> bool
+where
+    W: WindowManager + Send + Sync + 'static,
+    H: HitTester + Send + Sync + 'static,
+    M: MonitorManager + Send + Sync + 'static,
+{
+    worker.handle_event(event)
 }
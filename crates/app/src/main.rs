@@ -74,7 +74,9 @@ fn inner_main() -> Result<()> {
         } else {
             // 安装鼠标中键事件钩子
             if !config.disable_hook {
-                engine::install_hook(&config)?;
+                if let Err(e) = engine::install_hook(&config) {
+                    log::error!("无法安装鼠标钩子: {:?}", e);
+                }
             }
         }
 
@@ -87,13 +89,28 @@ fn inner_main() -> Result<()> {
     {
         // 安装鼠标中键事件钩子
         if !config.disable_hook {
-            engine::install_hook(&config)?;
+            if let Err(e) = engine::install_hook(&config) {
+                log::error!("无法安装鼠标钩子: {:?}", e);
+            }
         }
 
         // 启动系统托盘 + Windows 消息循环（阻塞）
         tray::run_app(config)?;
     }
 
+    // Linux (X11): 安装钩子 + 启动托盘
+    #[cfg(target_os = "linux")]
+    {
+        if !config.disable_hook {
+            if let Err(e) = engine::install_hook(&config) {
+                // 钩子安装失败（如 Wayland 下没有输入设备权限）时仍然启动托盘，可在菜单中重试
+                log::error!("无法安装鼠标钩子: {:?}", e);
+            }
+        }
+
+        tray::run_app(config)?;
+    }
+
     log::info!("ScreenHop 已退出");
     Ok(())
 }
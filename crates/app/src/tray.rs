@@ -3,10 +3,13 @@ use slint::ComponentHandle;
 use muda::{Menu, MenuItem, PredefinedMenuItem};
 use tray_icon::{menu::MenuEvent, Icon, TrayIconBuilder};
 use screenhop_core::config::AppConfig;
+use screenhop_platform::HookHealth;
 
 use std::sync::{Arc, Mutex};
 
 const MENU_ID_TOGGLE: &str = "toggle";
+const MENU_ID_UNDO: &str = "undo";
+const MENU_ID_REDO: &str = "redo";
 const MENU_ID_AUTOSTART: &str = "autostart";
 const MENU_ID_CHECK_UPDATE: &str = "check_update";
 const MENU_ID_PROXY_ENABLE: &str = "proxy_enable";
@@ -91,6 +94,13 @@ fn do_check_update(
                             }
                         };
 
+                        // Linux 暂无安装包格式，只提示新版本
+                        #[cfg(target_os = "linux")]
+                        let should_update = {
+                            log::info!("Linux 暂不支持自动更新，请手动下载新版本");
+                            false
+                        };
+
 
                         if !should_update {
                             log::info!("用户取消了更新");
@@ -235,6 +245,9 @@ fn do_check_update(
                                                     
                                                     #[cfg(target_os = "windows")]
                                                     let apply_res = screenhop_core::updater::apply_update_windows(&extract_dir);
+
+                                                    #[cfg(target_os = "linux")]
+                                                    let apply_res: anyhow::Result<()> = Err(anyhow::anyhow!("Linux 暂不支持自动更新"));
                                                     
                                                     if let Err(e) = apply_res {
                                                         log::error!("应用更新失败: {:?}", e);
@@ -318,12 +331,28 @@ fn do_check_update(
 
 
 struct MenuItems {
+    status_item: MenuItem,
     toggle_item: MenuItem,
     autostart_item: MenuItem,
     auto_check_update_item: MenuItem,
     proxy_enable_item: MenuItem,
 }
 
+/// 状态项的刷新间隔（菜单定时器每 50ms 触发一次）
+const STATUS_REFRESH_TICKS: u32 = 20;
+
+/// 托盘状态项的文字
+fn hook_status_text(health: Option<HookHealth>) -> String {
+    match health {
+        Some(h) if h.active && h.disabled => "⚠ 鼠标钩子被系统禁用，正在恢复…".to_string(),
+        Some(h) if h.active && h.disable_count > 0 => {
+            format!("Window Mover is Running（已自动恢复 {} 次）", h.disable_count)
+        }
+        Some(h) if h.active => "Window Mover is Running".to_string(),
+        _ => "鼠标钩子未运行".to_string(),
+    }
+}
+
 /// 运行托盘应用主循环
 pub fn run_app(config: AppConfig) -> Result<()> {
     // 我们现在使用 slint::run_event_loop_until_quit()，不再需要隐藏窗口来维持事件循环
@@ -344,6 +373,8 @@ pub fn run_app(config: AppConfig) -> Result<()> {
         true,
         None,
     );
+    let undo_item = MenuItem::with_id(MENU_ID_UNDO, "撤销上次移动", true, None);
+    let redo_item = MenuItem::with_id(MENU_ID_REDO, "重做移动", true, None);
     let autostart_item = MenuItem::with_id(
         MENU_ID_AUTOSTART,
         if config.lock().unwrap().auto_start {
@@ -395,6 +426,8 @@ pub fn run_app(config: AppConfig) -> Result<()> {
     menu.append(&status_item).ok();
     menu.append(&separator).ok();
     menu.append(&toggle_item).ok();
+    menu.append(&undo_item).ok();
+    menu.append(&redo_item).ok();
     menu.append(&autostart_item).ok();
     menu.append(&update_item).ok();
     menu.append(&auto_check_update_item).ok();
@@ -428,6 +461,7 @@ pub fn run_app(config: AppConfig) -> Result<()> {
 
     // 在主线程处理 Slint 和 MenuEvents
     let menu_items = MenuItems {
+        status_item,
         toggle_item,
         autostart_item,
         auto_check_update_item,
@@ -439,6 +473,10 @@ pub fn run_app(config: AppConfig) -> Result<()> {
     #[cfg(target_os = "macos")]
     let mut macos_policy_set = false;
 
+    // 每秒刷新一次状态项，显示钩子是否被系统禁用
+    let mut status_ticks = 0u32;
+    let mut status_text = String::new();
+
     // 定时检查 Menu事件
     let timer = slint::Timer::default();
     timer.start(slint::TimerMode::Repeated, std::time::Duration::from_millis(50), move || {
@@ -474,6 +512,15 @@ pub fn run_app(config: AppConfig) -> Result<()> {
             macos_policy_set = true;
         }
 
+        status_ticks = (status_ticks + 1) % STATUS_REFRESH_TICKS;
+        if status_ticks == 1 {
+            let text = hook_status_text(crate::engine::hook_health());
+            if text != status_text {
+                menu_items.status_item.set_text(&text);
+                status_text = text;
+            }
+        }
+
         while let Ok(event) = MenuEvent::receiver().try_recv() {
             if event.id.0 == MENU_ID_QUIT {
                 std::process::exit(0);
@@ -500,14 +547,26 @@ fn handle_menu_event(id: &str, items: &MenuItems, config: &Arc<Mutex<AppConfig>>
                 };
                 items.toggle_item.set_text(text);
 
-                // 实时启用/禁用钩子（无需重启）
-                crate::engine::set_hook_enabled(!cfg.disable_hook);
+                // 实时卸载/重新安装钩子（无需重启）
+                if let Err(e) = crate::engine::set_hook_enabled(!cfg.disable_hook, &cfg) {
+                    log::error!("切换鼠标钩子失败: {}", e);
+                }
 
                 if let Err(e) = cfg.save() {
                     log::error!("保存配置失败: {}", e);
                 }
             }
         }
+        MENU_ID_UNDO => {
+            if !crate::engine::undo_hop() {
+                log::warn!("窗口移动引擎未运行，无法撤销");
+            }
+        }
+        MENU_ID_REDO => {
+            if !crate::engine::redo_hop() {
+                log::warn!("窗口移动引擎未运行，无法重做");
+            }
+        }
         MENU_ID_AUTOSTART => {
             if let Ok(mut cfg) = config.lock() {
                 cfg.auto_start = !cfg.auto_start;
@@ -529,6 +588,16 @@ fn handle_menu_event(id: &str, items: &MenuItems, config: &Arc<Mutex<AppConfig>>
                     }
                 }
 
+                #[cfg(target_os = "linux")]
+                {
+                    use screenhop_platform::AutoStart;
+                    let auto = screenhop_platform::linux::autostart::LinuxAutoStart::new()
+                        .with_systemd(cfg.autostart_systemd);
+                    if let Err(e) = auto.set_enabled(cfg.auto_start) {
+                        log::error!("设置自启动失败: {}", e);
+                    }
+                }
+
                 if let Err(e) = cfg.save() {
                     log::error!("保存配置失败: {}", e);
                 }
@@ -3,6 +3,11 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::hotkey::Hotkey;
+use crate::monitor::{Direction, PlacementOptions, PlacementStrategy, DEFAULT_SNAP_TOLERANCE};
+use crate::rules::AppRule;
+use crate::trigger::{Trigger, WheelTrigger};
+
 /// 应用配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -14,6 +19,10 @@ pub struct AppConfig {
     #[serde(default)]
     pub auto_start: bool,
 
+    /// Linux：使用 systemd 用户服务代替 XDG autostart 自启动（异常退出时自动重启）
+    #[serde(default)]
+    pub autostart_systemd: bool,
+
     /// 是否总是最小化启动（隐藏至系统托盘）
     #[serde(default)]
     pub start_minimized: bool,
@@ -26,6 +35,40 @@ pub struct AppConfig {
     #[serde(default = "default_title_bar_height")]
     pub title_bar_height: f64,
 
+    /// 按固定方向跳转（未设置时按显示器顺序循环）
+    #[serde(default)]
+    pub hop_direction: Option<Direction>,
+
+    /// 窗口在目标显示器上的放置策略
+    #[serde(default)]
+    pub placement: PlacementStrategy,
+
+    /// 是否在目标显示器上保持半屏 / 三分屏 / 四分屏 / 最大化布局
+    #[serde(default = "default_true")]
+    pub preserve_snap_zones: bool,
+
+    /// 贴靠布局检测容差（逻辑像素）
+    #[serde(default = "default_snap_tolerance")]
+    pub snap_tolerance: f64,
+
+    /// 目标显示器放不下时是否等比缩小窗口
+    #[serde(default)]
+    pub keep_aspect_ratio: bool,
+
+    /// 等比缩小时与工作区边缘保留的间距（逻辑像素）
+    #[serde(default)]
+    pub fit_margin: f64,
+
+    /// 是否记忆窗口在各显示器上的 frame，跳回时原样恢复
+    #[serde(default = "default_true")]
+    pub remember_frames: bool,
+
+    /// 是否将窗口 frame 记忆保存到磁盘
+    ///
+    /// 保存的记忆按应用和窗口标题区分（窗口标识在重启后会被复用）。
+    #[serde(default)]
+    pub persist_frames: bool,
+
     /// 是否启用代理
     #[serde(default)]
     pub proxy_enabled: bool,
@@ -41,6 +84,22 @@ pub struct AppConfig {
     /// 代理密码（可选）
     #[serde(default)]
     pub proxy_password: Option<String>,
+
+    /// 触发移动的按键、修饰键、点击次数和位置（TOML 表，需放在最后）
+    #[serde(default)]
+    pub trigger: Trigger,
+
+    /// 标题栏上滚动滚轮切换显示器（TOML 表，默认关闭）
+    #[serde(default)]
+    pub wheel: WheelTrigger,
+
+    /// 全局快捷键（移动当前焦点窗口），默认不注册任何快捷键
+    #[serde(default)]
+    pub hotkeys: Vec<Hotkey>,
+
+    /// 按应用排除窗口或覆盖标题栏高度、放置策略和目标显示器，按顺序匹配
+    #[serde(default)]
+    pub rules: Vec<AppRule>,
 }
 
 fn default_true() -> bool {
@@ -51,26 +110,55 @@ fn default_title_bar_height() -> f64 {
     40.0
 }
 
+fn default_snap_tolerance() -> f64 {
+    DEFAULT_SNAP_TOLERANCE
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             disable_hook: false,
             auto_start: false,
+            autostart_systemd: false,
             start_minimized: false,
             auto_check_update: true,
             title_bar_height: default_title_bar_height(),
+            hop_direction: None,
+            placement: PlacementStrategy::default(),
+            preserve_snap_zones: true,
+            snap_tolerance: default_snap_tolerance(),
+            keep_aspect_ratio: false,
+            fit_margin: 0.0,
+            remember_frames: true,
+            persist_frames: false,
             proxy_enabled: false,
             proxy_url: String::new(),
             proxy_username: None,
             proxy_password: None,
+            trigger: Trigger::default(),
+            wheel: WheelTrigger::default(),
+            hotkeys: Vec::new(),
+            rules: Vec::new(),
         }
     }
 }
 
 impl AppConfig {
+    /// 根据配置生成窗口放置选项
+    pub fn placement_options(&self) -> PlacementOptions {
+        PlacementOptions {
+            strategy: self.placement,
+            preserve_snap: self.preserve_snap_zones,
+            snap_tolerance: self.snap_tolerance,
+            keep_aspect_ratio: self.keep_aspect_ratio,
+            fit_margin: self.fit_margin,
+        }
+    }
+
     /// 获取配置文件路径
     /// - macOS: ~/Library/Application Support/screenhop/config.toml
     /// - Windows: %APPDATA%/screenhop/config.toml
+    /// - Linux: ~/.config/screenhop/config.toml
     pub fn config_path() -> Result<PathBuf> {
         let config_dir = dirs::config_dir()
             .context("无法获取配置目录")?
@@ -125,13 +213,25 @@ mod tests {
         let config = AppConfig::default();
         assert!(!config.disable_hook);
         assert!(!config.auto_start);
+        assert!(!config.autostart_systemd);
         assert!(!config.start_minimized);
         assert!(config.auto_check_update);
         assert_eq!(config.title_bar_height, 40.0);
+        assert!(config.hop_direction.is_none());
+        assert!(config.preserve_snap_zones);
+        assert_eq!(config.snap_tolerance, DEFAULT_SNAP_TOLERANCE);
+        assert!(!config.keep_aspect_ratio);
+        assert_eq!(config.fit_margin, 0.0);
+        assert!(config.remember_frames);
+        assert!(!config.persist_frames);
         assert!(!config.proxy_enabled);
         assert!(config.proxy_url.is_empty());
         assert!(config.proxy_username.is_none());
         assert!(config.proxy_password.is_none());
+        assert_eq!(config.trigger, Trigger::default());
+        assert!(!config.wheel.enabled);
+        assert!(config.hotkeys.is_empty());
+        assert!(config.rules.is_empty());
     }
 
     #[test]
@@ -142,4 +242,124 @@ mod tests {
         assert_eq!(config.disable_hook, loaded.disable_hook);
         assert_eq!(config.auto_start, loaded.auto_start);
     }
+
+    #[test]
+    fn test_hop_direction_roundtrip() {
+        let config = AppConfig {
+            hop_direction: Some(Direction::Up),
+            ..AppConfig::default()
+        };
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        assert!(toml_str.contains("hop_direction = \"up\""));
+        let loaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(loaded.hop_direction, Some(Direction::Up));
+    }
+
+    #[test]
+    fn test_trigger_roundtrip() {
+        use crate::trigger::{ClickCount, MouseButton, TriggerRegion};
+
+        let toml_str = r#"
+            title_bar_height = 30.0
+
+            [trigger]
+            button = "x2"
+            click_count = "double"
+            region = "anywhere"
+
+            [trigger.modifiers]
+            alt = true
+        "#;
+        let loaded: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(loaded.trigger.button, MouseButton::X2);
+        assert_eq!(loaded.trigger.click_count, ClickCount::Double);
+        assert_eq!(loaded.trigger.region, TriggerRegion::Anywhere);
+        assert!(loaded.trigger.modifiers.alt && !loaded.trigger.modifiers.ctrl);
+        assert_eq!(loaded.trigger.double_click_ms, 400);
+
+        let toml_str = toml::to_string_pretty(&loaded).unwrap();
+        let reloaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(reloaded.trigger, loaded.trigger);
+    }
+
+    #[test]
+    fn test_wheel_roundtrip() {
+        let toml_str = r#"
+            [wheel]
+            enabled = true
+            invert = true
+
+            [wheel.modifiers]
+            shift = true
+        "#;
+        let loaded: AppConfig = toml::from_str(toml_str).unwrap();
+        assert!(loaded.wheel.enabled && loaded.wheel.invert);
+        assert!(loaded.wheel.modifiers.shift);
+        assert_eq!(loaded.wheel.debounce_ms, 300);
+
+        let toml_str = toml::to_string_pretty(&loaded).unwrap();
+        let reloaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(reloaded.wheel, loaded.wheel);
+    }
+
+    #[test]
+    fn test_hotkeys_roundtrip() {
+        use crate::hotkey::HotkeyAction;
+        use crate::monitor::Direction;
+
+        let config = AppConfig {
+            hotkeys: vec![
+                Hotkey {
+                    keys: "Ctrl+Alt+Right".to_string(),
+                    action: HotkeyAction::Direction(Direction::Right),
+                },
+                Hotkey {
+                    keys: "Ctrl+Alt+1".to_string(),
+                    action: HotkeyAction::Monitor(1),
+                },
+            ],
+            ..AppConfig::default()
+        };
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let loaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(loaded.hotkeys, config.hotkeys);
+    }
+
+    #[test]
+    fn test_rules_roundtrip() {
+        let toml_str = r#"
+            title_bar_height = 40.0
+
+            [[rules]]
+            process = "mstsc.exe"
+            hop = false
+
+            [[rules]]
+            class = "obsidian"
+            title_bar_height = 56.0
+            monitor = 2
+        "#;
+        let loaded: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(loaded.rules.len(), 2);
+        assert_eq!(loaded.rules[0].hop, Some(false));
+        assert_eq!(loaded.rules[1].monitor, Some(2));
+
+        let toml_str = toml::to_string_pretty(&loaded).unwrap();
+        let reloaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(reloaded.rules, loaded.rules);
+    }
+
+    #[test]
+    fn test_placement_roundtrip() {
+        let config = AppConfig {
+            placement: PlacementStrategy::Mirror,
+            ..AppConfig::default()
+        };
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        assert!(toml_str.contains("placement = \"mirror\""));
+        let loaded: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(loaded.placement, PlacementStrategy::Mirror);
+        assert_eq!(loaded.placement_options().strategy, PlacementStrategy::Mirror);
+    }
 }
@@ -0,0 +1,204 @@
+use anyhow::Result;
+
+/// EDID 基础块长度
+const EDID_BLOCK_LEN: usize = 128;
+
+/// EDID 固定头部
+const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+/// 显示描述符标签
+const DESCRIPTOR_SERIAL: u8 = 0xFF;
+const DESCRIPTOR_NAME: u8 = 0xFC;
+
+/// 从 EDID 基础块中解析出的显示器身份信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edid {
+    /// 制造商 PNP ID（三个大写字母，如 "DEL"）
+    pub manufacturer: String,
+    /// 产品编号
+    pub product_code: u16,
+    /// 数值序列号（0 表示未提供）
+    pub serial_number: u32,
+    /// 生产年份
+    pub manufacture_year: u16,
+    /// 显示器名称描述符
+    pub monitor_name: Option<String>,
+    /// 序列号字符串描述符
+    pub serial_string: Option<String>,
+}
+
+impl Edid {
+    /// 解析 EDID 数据（只读取 128 字节基础块，忽略扩展块）
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < EDID_BLOCK_LEN {
+            anyhow::bail!("EDID 数据长度不足: {} 字节", bytes.len());
+        }
+
+        let block = &bytes[..EDID_BLOCK_LEN];
+
+        if block[..8] != EDID_HEADER {
+            anyhow::bail!("EDID 头部无效");
+        }
+
+        let checksum = block.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        if checksum != 0 {
+            anyhow::bail!("EDID 校验和错误");
+        }
+
+        let manufacturer = decode_manufacturer_id(u16::from_be_bytes([block[8], block[9]]))
+            .ok_or_else(|| anyhow::anyhow!("EDID 制造商 ID 无效"))?;
+        let product_code = u16::from_le_bytes([block[10], block[11]]);
+        let serial_number = u32::from_le_bytes([block[12], block[13], block[14], block[15]]);
+        let manufacture_year = 1990 + block[17] as u16;
+
+        let mut monitor_name = None;
+        let mut serial_string = None;
+
+        for descriptor in block[54..126].chunks_exact(18) {
+            // 像素时钟为 0 的才是显示描述符，否则是详细时序
+            if descriptor[0] != 0 || descriptor[1] != 0 {
+                continue;
+            }
+
+            match descriptor[3] {
+                DESCRIPTOR_NAME => monitor_name = decode_descriptor_text(&descriptor[5..]),
+                DESCRIPTOR_SERIAL => serial_string = decode_descriptor_text(&descriptor[5..]),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            manufacturer,
+            product_code,
+            serial_number,
+            manufacture_year,
+            monitor_name,
+            serial_string,
+        })
+    }
+
+    /// 序列号：优先使用字符串描述符，其次使用非零的数值序列号
+    pub fn serial(&self) -> Option<String> {
+        self.serial_string.clone().or_else(|| {
+            (self.serial_number != 0).then(|| self.serial_number.to_string())
+        })
+    }
+}
+
+/// 将 EDID 中 16 位压缩的制造商 ID 解码为三个字母
+///
+/// 每个字母占 5 位，1 = 'A' … 26 = 'Z'。
+pub fn decode_manufacturer_id(raw: u16) -> Option<String> {
+    [(raw >> 10) & 0x1F, (raw >> 5) & 0x1F, raw & 0x1F]
+        .iter()
+        .map(|&c| (1..=26).contains(&c).then(|| (b'A' + c as u8 - 1) as char))
+        .collect()
+}
+
+/// 解码描述符中的 ASCII 文本（以 0x0A 结束，0x20 填充）
+fn decode_descriptor_text(bytes: &[u8]) -> Option<String> {
+    let end = bytes.iter().position(|&b| b == 0x0A).unwrap_or(bytes.len());
+    let text: String = bytes[..end]
+        .iter()
+        .filter(|b| b.is_ascii_graphic() || **b == b' ')
+        .map(|&b| b as char)
+        .collect();
+    let text = text.trim();
+
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造一个 128 字节的 EDID 基础块
+    fn make_edid(name: Option<&str>, serial: Option<&str>, serial_number: u32) -> Vec<u8> {
+        let mut edid = vec![0u8; EDID_BLOCK_LEN];
+        edid[..8].copy_from_slice(&EDID_HEADER);
+        // "DEL" = 4, 5, 12
+        let mfg: u16 = (4 << 10) | (5 << 5) | 12;
+        edid[8..10].copy_from_slice(&mfg.to_be_bytes());
+        edid[10..12].copy_from_slice(&0x4109u16.to_le_bytes());
+        edid[12..16].copy_from_slice(&serial_number.to_le_bytes());
+        edid[16] = 12;
+        edid[17] = 33; // 2023
+
+        // 第一个描述符保留为详细时序
+        edid[54] = 0x01;
+        edid[55] = 0x1D;
+
+        let mut write_text = |offset: usize, tag: u8, text: &str| {
+            edid[offset + 3] = tag;
+            let mut data = [0x20u8; 13];
+            let bytes = text.as_bytes();
+            data[..bytes.len()].copy_from_slice(bytes);
+            if bytes.len() < 13 {
+                data[bytes.len()] = 0x0A;
+            }
+            edid[offset + 5..offset + 18].copy_from_slice(&data);
+        };
+
+        if let Some(name) = name {
+            write_text(72, DESCRIPTOR_NAME, name);
+        }
+        if let Some(serial) = serial {
+            write_text(90, DESCRIPTOR_SERIAL, serial);
+        }
+
+        let sum = edid[..127].iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        edid[127] = 0u8.wrapping_sub(sum);
+        edid
+    }
+
+    #[test]
+    fn test_parse_full_edid() {
+        let bytes = make_edid(Some("DELL U2720Q"), Some("7X9KN13"), 0x12345678);
+        let edid = Edid::parse(&bytes).unwrap();
+
+        assert_eq!(edid.manufacturer, "DEL");
+        assert_eq!(edid.product_code, 0x4109);
+        assert_eq!(edid.serial_number, 0x12345678);
+        assert_eq!(edid.manufacture_year, 2023);
+        assert_eq!(edid.monitor_name.as_deref(), Some("DELL U2720Q"));
+        assert_eq!(edid.serial().as_deref(), Some("7X9KN13"));
+    }
+
+    #[test]
+    fn test_serial_falls_back_to_number() {
+        let edid = Edid::parse(&make_edid(None, None, 42)).unwrap();
+        assert!(edid.monitor_name.is_none());
+        assert_eq!(edid.serial().as_deref(), Some("42"));
+
+        let edid = Edid::parse(&make_edid(None, None, 0)).unwrap();
+        assert!(edid.serial().is_none());
+    }
+
+    #[test]
+    fn test_parse_ignores_extension_blocks() {
+        let mut bytes = make_edid(Some("LG HDR 4K"), None, 1);
+        bytes.extend_from_slice(&[0xAB; EDID_BLOCK_LEN]);
+        let edid = Edid::parse(&bytes).unwrap();
+        assert_eq!(edid.monitor_name.as_deref(), Some("LG HDR 4K"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_data() {
+        assert!(Edid::parse(&[0u8; 16]).is_err());
+
+        let mut bad_header = make_edid(None, None, 1);
+        bad_header[0] = 0x01;
+        assert!(Edid::parse(&bad_header).is_err());
+
+        let mut bad_checksum = make_edid(None, None, 1);
+        bad_checksum[127] = bad_checksum[127].wrapping_add(1);
+        assert!(Edid::parse(&bad_checksum).is_err());
+    }
+
+    #[test]
+    fn test_decode_manufacturer_id() {
+        assert_eq!(decode_manufacturer_id(0x10AC).as_deref(), Some("DEL"));
+        assert_eq!(decode_manufacturer_id(0x0610).as_deref(), Some("APP"));
+        assert_eq!(decode_manufacturer_id(0).as_deref(), None);
+    }
+}
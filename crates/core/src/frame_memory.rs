@@ -0,0 +1,283 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::monitor::fit_to_work_area;
+use crate::rules::WindowInfo;
+use crate::{MonitorInfo, Rect};
+
+/// 最多记忆的窗口数量，超出后淘汰最久未使用的窗口
+const MAX_WINDOWS: usize = 256;
+
+/// 单个窗口在各显示器上的 frame 记录
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct WindowFrames {
+    /// 最近一次使用的逻辑时钟，用于淘汰
+    last_used: u64,
+    /// 显示器 `stable_key` → 窗口在该显示器上的 frame
+    frames: HashMap<String, Rect>,
+}
+
+/// 窗口 frame 记忆
+///
+/// 窗口离开某个显示器时记录它在该显示器上的 frame，跳回来时原样恢复，
+/// 避免在大小屏之间来回移动时窗口被反复缩小。
+///
+/// 窗口由调用方给出的键区分：只在内存中记忆时可以使用平台的窗口标识（HWND、CGWindowID 等）；
+/// 这些标识只在窗口存活期间有效、之后会被复用，持久化的记忆应改用 [`app_key`]。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrameMemory {
+    windows: HashMap<String, WindowFrames>,
+    clock: u64,
+}
+
+/// 按应用标识和标题生成跨会话稳定的窗口键
+///
+/// 应用标识依次取 bundle id、进程名、窗口类（不区分大小写），都取不到时返回 `None`。
+/// 同一应用中标题相同的窗口共用一份记忆。
+pub fn app_key(info: &WindowInfo) -> Option<String> {
+    let app = info
+        .bundle_id
+        .as_deref()
+        .or(info.process_name.as_deref())
+        .or(info.class.as_deref())?;
+
+    Some(format!(
+        "{}\n{}",
+        app.to_lowercase(),
+        info.title.as_deref().unwrap_or_default()
+    ))
+}
+
+impl FrameMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取持久化文件路径（与配置文件同目录的 frames.json）
+    pub fn default_path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir()
+            .context("无法获取配置目录")?
+            .join("screenhop");
+
+        fs::create_dir_all(&config_dir).context("无法创建配置目录")?;
+
+        Ok(config_dir.join("frames.json"))
+    }
+
+    /// 从文件加载，文件不存在时返回空记忆
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("无法读取窗口记忆文件: {}", path.display()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("窗口记忆文件格式错误: {}", path.display()))
+    }
+
+    /// 保存到文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string(self).context("窗口记忆序列化失败")?;
+
+        fs::write(path, content)
+            .with_context(|| format!("无法写入窗口记忆文件: {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// 记录窗口在指定显示器上的 frame
+    pub fn remember(&mut self, window: &str, monitor: &MonitorInfo, frame: Rect) {
+        self.clock += 1;
+
+        if !self.windows.contains_key(window) && self.windows.len() >= MAX_WINDOWS {
+            self.evict_oldest();
+        }
+
+        let entry = self.windows.entry(window.to_string()).or_default();
+        entry.last_used = self.clock;
+        entry.frames.insert(monitor.stable_key(), frame);
+    }
+
+    /// 取出窗口在指定显示器上记忆的 frame
+    ///
+    /// 返回值已约束在显示器当前的工作区内（分辨率可能已变化）；
+    /// 记忆的 frame 与显示器完全不相交时（显示器位置已变化）视为失效，返回 `None`。
+    pub fn recall(&self, window: &str, monitor: &MonitorInfo) -> Option<Rect> {
+        let frame = self
+            .windows
+            .get(window)?
+            .frames
+            .get(&monitor.stable_key())?;
+
+        frame.intersection(&monitor.bounds)?;
+        Some(fit_to_work_area(frame, &monitor.work_area))
+    }
+
+    /// 清除窗口的所有记忆
+    pub fn forget(&mut self, window: &str) {
+        self.windows.remove(window);
+    }
+
+    /// 已记忆的窗口数量
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    fn evict_oldest(&mut self) {
+        let oldest = self
+            .windows
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(window, _)| window.clone());
+
+        if let Some(window) = oldest {
+            self.windows.remove(&window);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::{calculate_new_position, PlacementOptions};
+
+    fn make_monitor(name: &str, x: f64, w: f64, h: f64) -> MonitorInfo {
+        MonitorInfo {
+            name: name.to_string(),
+            bounds: Rect::new(x, 0.0, w, h),
+            work_area: Rect::new(x, 0.0, w, h),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ping_pong_is_lossless() {
+        let big = make_monitor("big", 0.0, 2560.0, 1440.0);
+        let small = make_monitor("small", 2560.0, 1280.0, 720.0);
+        let original = Rect::new(100.0, 100.0, 2000.0, 1200.0);
+        let mut memory = FrameMemory::new();
+
+        // 大屏 → 小屏：窗口被缩小
+        memory.remember("1", &big, original);
+        let (pos, w, h) =
+            calculate_new_position(&original, &big, &small, &PlacementOptions::default());
+        let shrunk = Rect::new(pos.x, pos.y, w, h);
+        assert!(shrunk.width < original.width);
+
+        // 小屏 → 大屏：恢复原始 frame
+        memory.remember("1", &small, shrunk);
+        assert_eq!(memory.recall("1", &big), Some(original));
+        assert_eq!(memory.recall("1", &small), Some(shrunk));
+    }
+
+    #[test]
+    fn test_recall_unknown() {
+        let monitor = make_monitor("a", 0.0, 1920.0, 1080.0);
+        let mut memory = FrameMemory::new();
+        assert_eq!(memory.recall("1", &monitor), None);
+
+        memory.remember("1", &monitor, Rect::new(0.0, 0.0, 800.0, 600.0));
+        assert_eq!(memory.recall("2", &monitor), None);
+        assert_eq!(
+            memory.recall("1", &make_monitor("b", 0.0, 1920.0, 1080.0)),
+            None
+        );
+
+        memory.forget("1");
+        assert!(memory.is_empty());
+    }
+
+    #[test]
+    fn test_recall_adapts_to_changed_monitor() {
+        let mut memory = FrameMemory::new();
+        memory.remember(
+            "1",
+            &make_monitor("a", 0.0, 2560.0, 1440.0),
+            Rect::new(1000.0, 500.0, 1500.0, 900.0),
+        );
+
+        // 分辨率降低：frame 被约束在新的工作区内
+        let lower = make_monitor("a", 0.0, 1920.0, 1080.0);
+        assert_eq!(
+            memory.recall("1", &lower),
+            Some(Rect::new(420.0, 180.0, 1500.0, 900.0))
+        );
+
+        // 显示器被移到别处：记忆失效
+        let moved = make_monitor("a", 5000.0, 2560.0, 1440.0);
+        assert_eq!(memory.recall("1", &moved), None);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let monitor = make_monitor("a", 0.0, 1920.0, 1080.0);
+        let frame = Rect::new(0.0, 0.0, 800.0, 600.0);
+        let mut memory = FrameMemory::new();
+
+        for window in 0..MAX_WINDOWS {
+            memory.remember(&window.to_string(), &monitor, frame);
+        }
+        // 刷新窗口 0，使窗口 1 成为最久未使用
+        memory.remember("0", &monitor, frame);
+        memory.remember("9999", &monitor, frame);
+
+        assert_eq!(memory.len(), MAX_WINDOWS);
+        assert!(memory.recall("0", &monitor).is_some());
+        assert!(memory.recall("1", &monitor).is_none());
+        assert!(memory.recall("9999", &monitor).is_some());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let monitor = make_monitor("a", 0.0, 1920.0, 1080.0);
+        let frame = Rect::new(10.0, 20.0, 800.0, 600.0);
+        let mut memory = FrameMemory::new();
+        memory.remember("42", &monitor, frame);
+
+        let path = std::env::temp_dir().join(format!(
+            "screenhop_frames_test_{}.json",
+            std::process::id()
+        ));
+        memory.save(&path).unwrap();
+        let loaded = FrameMemory::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.recall("42", &monitor), Some(frame));
+        assert!(FrameMemory::load(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_app_key() {
+        let info = WindowInfo {
+            process_name: Some("Code.exe".to_string()),
+            class: Some("Chrome_WidgetWin_1".to_string()),
+            title: Some("main.rs".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(app_key(&info).as_deref(), Some("code.exe\nmain.rs"));
+
+        // bundle id 优先于进程名
+        let mac = WindowInfo {
+            bundle_id: Some("com.apple.Safari".to_string()),
+            process_name: Some("Safari".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(app_key(&mac).as_deref(), Some("com.apple.safari\n"));
+
+        // 只有标题时无法区分应用
+        let untitled = WindowInfo {
+            title: Some("main.rs".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(app_key(&untitled), None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+
+use crate::Rect;
+
+/// 默认保留的移动记录条数
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// 一次窗口移动的记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct HopRecord<H> {
+    /// 被移动的窗口
+    pub handle: H,
+    /// 移动前的 frame
+    pub from: Rect,
+    /// 移动前是否最大化（移动后保持相同的状态）
+    pub maximized: bool,
+    /// 移动后的 frame
+    pub to: Rect,
+}
+
+/// 有上限的撤销 / 重做历史
+///
+/// 取出的记录由调用方执行撤销或重做，成功后再通过 `push_undone` / `push_redone`
+/// 放回另一侧；执行失败（例如窗口已关闭）的记录直接丢弃。
+#[derive(Debug)]
+pub struct HopHistory<H> {
+    undo: VecDeque<HopRecord<H>>,
+    redo: VecDeque<HopRecord<H>>,
+    limit: usize,
+}
+
+impl<H> Default for HopHistory<H> {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl<H> HopHistory<H> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: VecDeque::new(),
+            limit,
+        }
+    }
+
+    /// 记录一次新的移动，同时清空重做记录
+    pub fn push(&mut self, record: HopRecord<H>) {
+        self.redo.clear();
+        Self::push_bounded(&mut self.undo, record, self.limit);
+    }
+
+    /// 取出最近一次移动用于撤销
+    pub fn pop_undo(&mut self) -> Option<HopRecord<H>> {
+        self.undo.pop_back()
+    }
+
+    /// 取出最近一次撤销用于重做
+    pub fn pop_redo(&mut self) -> Option<HopRecord<H>> {
+        self.redo.pop_back()
+    }
+
+    /// 撤销成功后放入重做记录
+    pub fn push_undone(&mut self, record: HopRecord<H>) {
+        Self::push_bounded(&mut self.redo, record, self.limit);
+    }
+
+    /// 重做成功后放回撤销记录（不清空其余的重做记录）
+    pub fn push_redone(&mut self, record: HopRecord<H>) {
+        Self::push_bounded(&mut self.undo, record, self.limit);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn push_bounded(records: &mut VecDeque<HopRecord<H>>, record: HopRecord<H>, limit: usize) {
+        if limit == 0 {
+            return;
+        }
+        while records.len() >= limit {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(handle: u64) -> HopRecord<u64> {
+        HopRecord {
+            handle,
+            from: Rect::new(0.0, 0.0, 800.0, 600.0),
+            maximized: false,
+            to: Rect::new(1920.0, 0.0, 800.0, 600.0),
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_order() {
+        let mut history = HopHistory::new(10);
+        history.push(record(1));
+        history.push(record(2));
+
+        let undone = history.pop_undo().unwrap();
+        assert_eq!(undone.handle, 2);
+        history.push_undone(undone);
+        assert!(history.can_redo());
+
+        let redone = history.pop_redo().unwrap();
+        assert_eq!(redone.handle, 2);
+        history.push_redone(redone);
+        assert!(!history.can_redo());
+
+        assert_eq!(history.pop_undo().unwrap().handle, 2);
+        assert_eq!(history.pop_undo().unwrap().handle, 1);
+        assert!(history.pop_undo().is_none());
+    }
+
+    #[test]
+    fn test_new_hop_clears_redo() {
+        let mut history = HopHistory::new(10);
+        history.push(record(1));
+        let undone = history.pop_undo().unwrap();
+        history.push_undone(undone);
+
+        history.push(record(2));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = HopHistory::new(2);
+        for handle in 1..=3 {
+            history.push(record(handle));
+        }
+
+        assert_eq!(history.pop_undo().unwrap().handle, 3);
+        assert_eq!(history.pop_undo().unwrap().handle, 2);
+        assert!(!history.can_undo());
+    }
+}
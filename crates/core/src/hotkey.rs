@@ -0,0 +1,307 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::monitor::{self, Direction};
+use crate::trigger::Modifiers;
+use crate::MonitorInfo;
+
+/// 快捷键中的主键（各平台再映射为虚拟键码 / keysym）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// 字母 A-Z（大写）
+    Letter(char),
+    /// 数字 0-9
+    Digit(u8),
+    /// 功能键 F1-F12
+    F(u8),
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Space,
+    Tab,
+    Enter,
+    Escape,
+}
+
+impl Key {
+    fn parse(name: &str) -> Option<Self> {
+        let upper = name.to_ascii_uppercase();
+        let mut chars = upper.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return match c {
+                'A'..='Z' => Some(Key::Letter(c)),
+                '0'..='9' => Some(Key::Digit(c as u8 - b'0')),
+                _ => None,
+            };
+        }
+
+        let key = match upper.as_str() {
+            "LEFT" => Key::Left,
+            "RIGHT" => Key::Right,
+            "UP" => Key::Up,
+            "DOWN" => Key::Down,
+            "HOME" => Key::Home,
+            "END" => Key::End,
+            "PAGEUP" => Key::PageUp,
+            "PAGEDOWN" => Key::PageDown,
+            "SPACE" => Key::Space,
+            "TAB" => Key::Tab,
+            "ENTER" | "RETURN" => Key::Enter,
+            "ESC" | "ESCAPE" => Key::Escape,
+            _ => {
+                let number: u8 = upper.strip_prefix('F')?.parse().ok()?;
+                if !(1..=12).contains(&number) {
+                    return None;
+                }
+                Key::F(number)
+            }
+        };
+        Some(key)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Letter(c) => write!(f, "{}", c),
+            Key::Digit(d) => write!(f, "{}", d),
+            Key::F(n) => write!(f, "F{}", n),
+            Key::Left => f.write_str("Left"),
+            Key::Right => f.write_str("Right"),
+            Key::Up => f.write_str("Up"),
+            Key::Down => f.write_str("Down"),
+            Key::Home => f.write_str("Home"),
+            Key::End => f.write_str("End"),
+            Key::PageUp => f.write_str("PageUp"),
+            Key::PageDown => f.write_str("PageDown"),
+            Key::Space => f.write_str("Space"),
+            Key::Tab => f.write_str("Tab"),
+            Key::Enter => f.write_str("Enter"),
+            Key::Escape => f.write_str("Escape"),
+        }
+    }
+}
+
+/// 组合键，例如 `Ctrl+Alt+Right`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyCombo {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyCombo {
+    /// 解析 `Ctrl+Alt+Right` 形式的组合键（不区分大小写）
+    ///
+    /// 修饰键可写作 Ctrl / Control、Alt / Option、Shift、Cmd / Command / Super / Win / Meta。
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut key = None;
+
+        for part in text.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "alt" | "option" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                "cmd" | "command" | "super" | "win" | "meta" => modifiers.cmd = true,
+                _ => {
+                    if key.is_some() {
+                        bail!("快捷键 {} 包含多个主键", text);
+                    }
+                    let parsed = Key::parse(part)
+                        .with_context(|| format!("快捷键 {} 中的按键 {} 无法识别", text, part))?;
+                    key = Some(parsed);
+                }
+            }
+        }
+
+        let key = key.with_context(|| format!("快捷键 {} 缺少主键", text))?;
+        Ok(Self { modifiers, key })
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (self.modifiers.ctrl, "Ctrl"),
+            (self.modifiers.alt, "Alt"),
+            (self.modifiers.shift, "Shift"),
+            (self.modifiers.cmd, "Cmd"),
+        ];
+        for (_, name) in names.iter().filter(|(pressed, _)| *pressed) {
+            write!(f, "{}+", name)?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// 快捷键触发的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    /// 移到下一个显示器
+    Next,
+    /// 移到上一个显示器
+    Previous,
+    /// 移到第 N 个显示器（从 1 开始，按 `sort_monitors` 的顺序）
+    Monitor(usize),
+    /// 移到指定方向上的显示器
+    Direction(Direction),
+    /// 撤销最近一次移动（不限于当前焦点窗口）
+    Undo,
+    /// 重做最近一次撤销的移动
+    Redo,
+}
+
+impl HotkeyAction {
+    /// 计算目标显示器索引；目标不存在或就是当前显示器时返回 `None`
+    ///
+    /// `Undo` / `Redo` 不移到其他显示器，始终返回 `None`。
+    pub fn target_index(self, current: usize, monitors: &[MonitorInfo]) -> Option<usize> {
+        if monitors.len() < 2 || current >= monitors.len() {
+            return None;
+        }
+
+        let target = match self {
+            HotkeyAction::Next => monitor::next_monitor_index(current, monitors.len()),
+            HotkeyAction::Previous => monitor::previous_monitor_index(current, monitors.len()),
+            HotkeyAction::Monitor(number) => number.checked_sub(1)?,
+            HotkeyAction::Direction(direction) => {
+                monitor::next_monitor_in_direction(current, monitors, direction)?
+            }
+            HotkeyAction::Undo | HotkeyAction::Redo => return None,
+        };
+
+        (target < monitors.len() && target != current).then_some(target)
+    }
+}
+
+/// 一条快捷键配置
+///
+/// ```toml
+/// [[hotkeys]]
+/// keys = "Ctrl+Alt+Right"
+/// action = { direction = "right" }
+///
+/// [[hotkeys]]
+/// keys = "Ctrl+Alt+1"
+/// action = { monitor = 1 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hotkey {
+    /// 组合键，格式见 [`KeyCombo::parse`]
+    pub keys: String,
+    pub action: HotkeyAction,
+}
+
+impl Hotkey {
+    pub fn combo(&self) -> Result<KeyCombo> {
+        KeyCombo::parse(&self.keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rect;
+
+    fn monitors(count: usize) -> Vec<MonitorInfo> {
+        (0..count)
+            .map(|i| MonitorInfo {
+                id: i as u64,
+                bounds: Rect::new(i as f64 * 1920.0, 0.0, 1920.0, 1080.0),
+                work_area: Rect::new(i as f64 * 1920.0, 0.0, 1920.0, 1080.0),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_combo() {
+        let combo = KeyCombo::parse("ctrl + Alt+right").unwrap();
+        assert!(combo.modifiers.ctrl && combo.modifiers.alt);
+        assert!(!combo.modifiers.shift && !combo.modifiers.cmd);
+        assert_eq!(combo.key, Key::Right);
+        assert_eq!(combo.to_string(), "Ctrl+Alt+Right");
+
+        assert_eq!(KeyCombo::parse("Super+F12").unwrap().key, Key::F(12));
+        assert_eq!(
+            KeyCombo::parse("Cmd+Shift+h").unwrap().key,
+            Key::Letter('H')
+        );
+        assert_eq!(KeyCombo::parse("Win+3").unwrap().to_string(), "Cmd+3");
+    }
+
+    #[test]
+    fn test_parse_invalid_combo() {
+        assert!(KeyCombo::parse("Ctrl+Alt").is_err());
+        assert!(KeyCombo::parse("Ctrl+A+B").is_err());
+        assert!(KeyCombo::parse("Ctrl+F13").is_err());
+        assert!(KeyCombo::parse("Ctrl+Hyper").is_err());
+    }
+
+    #[test]
+    fn test_target_index() {
+        let three = monitors(3);
+        assert_eq!(HotkeyAction::Next.target_index(2, &three), Some(0));
+        assert_eq!(HotkeyAction::Previous.target_index(0, &three), Some(2));
+        assert_eq!(HotkeyAction::Monitor(2).target_index(0, &three), Some(1));
+        // 已经在目标显示器上，或显示器不存在
+        assert_eq!(HotkeyAction::Monitor(1).target_index(0, &three), None);
+        assert_eq!(HotkeyAction::Monitor(4).target_index(0, &three), None);
+        assert_eq!(HotkeyAction::Monitor(0).target_index(0, &three), None);
+        assert_eq!(
+            HotkeyAction::Direction(Direction::Left).target_index(1, &three),
+            Some(0)
+        );
+
+        assert_eq!(HotkeyAction::Next.target_index(0, &monitors(1)), None);
+        assert_eq!(HotkeyAction::Undo.target_index(0, &three), None);
+    }
+
+    #[test]
+    fn test_hotkey_toml() {
+        #[derive(Deserialize)]
+        struct Config {
+            hotkeys: Vec<Hotkey>,
+        }
+
+        let config: Config = toml::from_str(
+            r#"
+            [[hotkeys]]
+            keys = "Ctrl+Alt+Right"
+            action = "next"
+
+            [[hotkeys]]
+            keys = "Ctrl+Alt+2"
+            action = { monitor = 2 }
+
+            [[hotkeys]]
+            keys = "Ctrl+Alt+Up"
+            action = { direction = "up" }
+
+            [[hotkeys]]
+            keys = "Ctrl+Alt+Z"
+            action = "undo"
+            "#,
+        )
+        .unwrap();
+
+        let actions: Vec<HotkeyAction> = config.hotkeys.iter().map(|h| h.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                HotkeyAction::Next,
+                HotkeyAction::Monitor(2),
+                HotkeyAction::Direction(Direction::Up),
+                HotkeyAction::Undo,
+            ]
+        );
+        assert!(config.hotkeys.iter().all(|h| h.combo().is_ok()));
+    }
+}
@@ -1,16 +1,24 @@
+use serde::{Deserialize, Serialize};
+
 pub mod config;
+pub mod edid;
+pub mod frame_memory;
+pub mod history;
+pub mod hotkey;
 pub mod monitor;
+pub mod rules;
+pub mod trigger;
 pub mod updater;
 
 /// 二维坐标点
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
 }
 
 /// 矩形区域
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Rect {
     pub x: f64,
     pub y: f64,
@@ -53,15 +61,195 @@ impl Rect {
             && point.y >= self.min_y()
             && point.y <= self.max_y()
     }
+
+    pub fn center(&self) -> Point {
+        Point {
+            x: self.mid_x(),
+            y: self.mid_y(),
+        }
+    }
+
+    /// 面积（宽高为负时视为 0）
+    pub fn area(&self) -> f64 {
+        self.width.max(0.0) * self.height.max(0.0)
+    }
+
+    /// 与另一个矩形的交集，不相交（或仅边缘相接）时返回 `None`
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let x = self.min_x().max(other.min_x());
+        let y = self.min_y().max(other.min_y());
+        let max_x = self.max_x().min(other.max_x());
+        let max_y = self.max_y().min(other.max_y());
+
+        if max_x > x && max_y > y {
+            Some(Rect::new(x, y, max_x - x, max_y - y))
+        } else {
+            None
+        }
+    }
+
+    /// 与另一个矩形之间的最短距离（相交或相接时为 0）
+    pub fn distance_to(&self, other: &Rect) -> f64 {
+        let dx = (other.min_x() - self.max_x())
+            .max(self.min_x() - other.max_x())
+            .max(0.0);
+        let dy = (other.min_y() - self.max_y())
+            .max(self.min_y() - other.max_y())
+            .max(0.0);
+        dx.hypot(dy)
+    }
+
+    /// 点到矩形的最短距离（点在矩形内时为 0）
+    pub fn distance_to_point(&self, point: Point) -> f64 {
+        self.distance_to(&Rect::new(point.x, point.y, 0.0, 0.0))
+    }
 }
 
 /// 显示器信息
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MonitorInfo {
-    /// 显示器唯一标识
+    /// 显示器标识（仅在本次会话内唯一，跨重启请使用 `stable_key`）
     pub id: u64,
     /// 显示器完整区域（包含任务栏/Dock）
     pub bounds: Rect,
     /// 可用工作区域（排除任务栏/Dock）
     pub work_area: Rect,
+    /// 显示器名称（EDID 名称或系统提供的友好名称）
+    pub name: String,
+    /// 制造商 PNP ID（如 "DEL"）
+    pub manufacturer: Option<String>,
+    /// 产品编号
+    pub product_code: Option<u16>,
+    /// 序列号
+    pub serial: Option<String>,
+    /// 连接器名称（如 "DP-1"、`\\.\DISPLAY1`）
+    pub connector: Option<String>,
+    /// 是否为主显示器
+    pub is_primary: bool,
+    /// 坐标缩放比例：`bounds`/`work_area` 的坐标单位与逻辑像素之比
+    ///
+    /// Windows（Per-Monitor DPI 感知）下坐标为物理像素，值为 DPI / 96；
+    /// macOS 等直接使用逻辑坐标的平台为 1.0。
+    pub scale_factor: f64,
+}
+
+impl Default for MonitorInfo {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            bounds: Rect::default(),
+            work_area: Rect::default(),
+            name: String::new(),
+            manufacturer: None,
+            product_code: None,
+            serial: None,
+            connector: None,
+            is_primary: false,
+            scale_factor: 1.0,
+        }
+    }
+}
+
+impl MonitorInfo {
+    /// 跨重启、重新插拔保持不变的显示器标识
+    ///
+    /// 优先使用 EDID 的制造商 + 产品编号 + 序列号；没有序列号时（同型号多台会冲突）
+    /// 附加连接器名称；都没有时退化为名称 + 连接器。
+    pub fn stable_key(&self) -> String {
+        let product = self
+            .product_code
+            .map(|code| format!("{:04X}", code))
+            .unwrap_or_default();
+
+        match (&self.manufacturer, &self.serial, &self.connector) {
+            (Some(mfg), Some(serial), _) => format!("{}-{}-{}", mfg, product, serial),
+            (Some(mfg), None, Some(connector)) => format!("{}-{}@{}", mfg, product, connector),
+            (Some(mfg), None, None) => format!("{}-{}", mfg, product),
+            (None, _, Some(connector)) => format!("{}@{}", self.name, connector),
+            (None, _, None) if !self.name.is_empty() => self.name.clone(),
+            (None, _, None) => format!("#{}", self.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_area() {
+        assert_eq!(Rect::new(10.0, 10.0, 200.0, 100.0).area(), 20000.0);
+        assert_eq!(Rect::new(0.0, 0.0, -5.0, 100.0).area(), 0.0);
+    }
+
+    #[test]
+    fn test_rect_intersection() {
+        let a = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let b = Rect::new(50.0, 60.0, 100.0, 100.0);
+        assert_eq!(a.intersection(&b), Some(Rect::new(50.0, 60.0, 50.0, 40.0)));
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+
+        // 仅边缘相接不算相交
+        let c = Rect::new(100.0, 0.0, 50.0, 50.0);
+        assert_eq!(a.intersection(&c), None);
+
+        let d = Rect::new(300.0, 300.0, 10.0, 10.0);
+        assert_eq!(a.intersection(&d), None);
+    }
+
+    #[test]
+    fn test_rect_distance() {
+        let a = Rect::new(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(a.distance_to(&Rect::new(50.0, 50.0, 100.0, 100.0)), 0.0);
+        assert_eq!(a.distance_to(&Rect::new(100.0, 0.0, 10.0, 10.0)), 0.0);
+        assert_eq!(a.distance_to(&Rect::new(130.0, 20.0, 10.0, 10.0)), 30.0);
+        assert_eq!(a.distance_to(&Rect::new(130.0, 140.0, 10.0, 10.0)), 50.0);
+        assert_eq!(a.distance_to_point(Point { x: -3.0, y: 104.0 }), 5.0);
+        assert_eq!(a.distance_to_point(Point { x: 20.0, y: 20.0 }), 0.0);
+    }
+
+    #[test]
+    fn test_stable_key_prefers_edid_serial() {
+        let monitor = MonitorInfo {
+            id: 0x1234,
+            name: "DELL U2720Q".to_string(),
+            manufacturer: Some("DEL".to_string()),
+            product_code: Some(0x4109),
+            serial: Some("7X9KN13".to_string()),
+            connector: Some("DP-1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(monitor.stable_key(), "DEL-4109-7X9KN13");
+
+        // 序列号相同则与连接口、会话 id 无关
+        let replugged = MonitorInfo {
+            id: 0x9999,
+            connector: Some("HDMI-1".to_string()),
+            ..monitor.clone()
+        };
+        assert_eq!(replugged.stable_key(), monitor.stable_key());
+    }
+
+    #[test]
+    fn test_stable_key_fallbacks() {
+        let no_serial = MonitorInfo {
+            manufacturer: Some("APP".to_string()),
+            product_code: Some(0xA050),
+            connector: Some("eDP-1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(no_serial.stable_key(), "APP-A050@eDP-1");
+
+        let name_only = MonitorInfo {
+            name: "Built-in Display".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(name_only.stable_key(), "Built-in Display");
+
+        let anonymous = MonitorInfo {
+            id: 7,
+            ..Default::default()
+        };
+        assert_eq!(anonymous.stable_key(), "#7");
+    }
 }
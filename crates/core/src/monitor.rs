@@ -1,52 +1,383 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{MonitorInfo, Point, Rect};
 
-/// 计算窗口从当前显示器移动到目标显示器后的新位置
+/// 跳转方向（基于显示器的物理排布）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    /// 相反方向
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
+
+/// 窗口在目标显示器上的放置策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlacementStrategy {
+    /// 按比例映射左上角位置，保持窗口尺寸
+    #[default]
+    Proportional,
+    /// 按比例映射位置和尺寸
+    ProportionalResize,
+    /// 保持窗口尺寸，居中放置
+    Centered,
+    /// 按比例映射窗口中心点，保持窗口尺寸
+    CenterMapping,
+    /// 保持相对工作区左上角的绝对偏移（镜像位置）
+    Mirror,
+}
+
+impl PlacementStrategy {
+    /// 按策略计算窗口在目标工作区中的位置和尺寸（逻辑坐标，未做边界约束）
+    pub fn place(self, window: &Rect, from_work: &Rect, to_work: &Rect) -> Rect {
+        match self {
+            PlacementStrategy::Proportional => place_proportional(window, from_work, to_work),
+            PlacementStrategy::ProportionalResize => {
+                place_proportional_resize(window, from_work, to_work)
+            }
+            PlacementStrategy::Centered => place_centered(window, from_work, to_work),
+            PlacementStrategy::CenterMapping => place_center_mapped(window, from_work, to_work),
+            PlacementStrategy::Mirror => place_mirrored(window, from_work, to_work),
+        }
+    }
+}
+
+/// 窗口放置选项
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacementOptions {
+    pub strategy: PlacementStrategy,
+    /// 是否在目标显示器上保持贴靠布局（半屏、三分屏、四分屏、最大化）
+    pub preserve_snap: bool,
+    /// 贴靠检测容差（逻辑像素）
+    pub snap_tolerance: f64,
+    /// 目标工作区放不下时是否等比缩小窗口（保持宽高比）
+    pub keep_aspect_ratio: bool,
+    /// 等比缩小时窗口与工作区边缘保留的间距（逻辑像素）
+    pub fit_margin: f64,
+}
+
+/// 默认贴靠检测容差，覆盖 Windows 窗口约 7px 的不可见边框
+pub const DEFAULT_SNAP_TOLERANCE: f64 = 16.0;
+
+impl Default for PlacementOptions {
+    fn default() -> Self {
+        Self {
+            strategy: PlacementStrategy::default(),
+            preserve_snap: true,
+            snap_tolerance: DEFAULT_SNAP_TOLERANCE,
+            keep_aspect_ratio: false,
+            fit_margin: 0.0,
+        }
+    }
+}
+
+/// 贴靠区域（以工作区比例描述的窗口布局）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapZone {
+    /// 按尺寸判断的最大化（铺满工作区）
+    Maximized,
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+    TopLeftQuarter,
+    TopRightQuarter,
+    BottomLeftQuarter,
+    BottomRightQuarter,
+    LeftThird,
+    CenterThird,
+    RightThird,
+    LeftTwoThirds,
+    RightTwoThirds,
+}
+
+impl SnapZone {
+    /// 检测顺序：先大后小，容差范围内优先匹配更大的区域
+    pub const ALL: [SnapZone; 14] = [
+        SnapZone::Maximized,
+        SnapZone::LeftHalf,
+        SnapZone::RightHalf,
+        SnapZone::TopHalf,
+        SnapZone::BottomHalf,
+        SnapZone::TopLeftQuarter,
+        SnapZone::TopRightQuarter,
+        SnapZone::BottomLeftQuarter,
+        SnapZone::BottomRightQuarter,
+        SnapZone::LeftTwoThirds,
+        SnapZone::RightTwoThirds,
+        SnapZone::LeftThird,
+        SnapZone::CenterThird,
+        SnapZone::RightThird,
+    ];
+
+    /// 区域在工作区中的比例 `(x, y, width, height)`
+    fn fractions(self) -> (f64, f64, f64, f64) {
+        const THIRD: f64 = 1.0 / 3.0;
+        const TWO_THIRDS: f64 = 2.0 / 3.0;
+
+        match self {
+            SnapZone::Maximized => (0.0, 0.0, 1.0, 1.0),
+            SnapZone::LeftHalf => (0.0, 0.0, 0.5, 1.0),
+            SnapZone::RightHalf => (0.5, 0.0, 0.5, 1.0),
+            SnapZone::TopHalf => (0.0, 0.0, 1.0, 0.5),
+            SnapZone::BottomHalf => (0.0, 0.5, 1.0, 0.5),
+            SnapZone::TopLeftQuarter => (0.0, 0.0, 0.5, 0.5),
+            SnapZone::TopRightQuarter => (0.5, 0.0, 0.5, 0.5),
+            SnapZone::BottomLeftQuarter => (0.0, 0.5, 0.5, 0.5),
+            SnapZone::BottomRightQuarter => (0.5, 0.5, 0.5, 0.5),
+            SnapZone::LeftThird => (0.0, 0.0, THIRD, 1.0),
+            SnapZone::CenterThird => (THIRD, 0.0, THIRD, 1.0),
+            SnapZone::RightThird => (TWO_THIRDS, 0.0, THIRD, 1.0),
+            SnapZone::LeftTwoThirds => (0.0, 0.0, TWO_THIRDS, 1.0),
+            SnapZone::RightTwoThirds => (THIRD, 0.0, TWO_THIRDS, 1.0),
+        }
+    }
+
+    /// 区域在指定工作区中对应的矩形
+    pub fn rect_in(self, work: &Rect) -> Rect {
+        let (x, y, width, height) = self.fractions();
+        Rect::new(
+            work.x + work.width * x,
+            work.y + work.height * y,
+            work.width * width,
+            work.height * height,
+        )
+    }
+}
+
+/// 检测窗口是否贴靠在工作区的某个区域上（四条边都在容差范围内）
+pub fn detect_snap_zone(window: &Rect, work: &Rect, tolerance: f64) -> Option<SnapZone> {
+    SnapZone::ALL.into_iter().find(|zone| {
+        let target = zone.rect_in(work);
+        (window.min_x() - target.min_x()).abs() <= tolerance
+            && (window.min_y() - target.min_y()).abs() <= tolerance
+            && (window.max_x() - target.max_x()).abs() <= tolerance
+            && (window.max_y() - target.max_y()).abs() <= tolerance
+    })
+}
+
+/// 在目标工作区上重新应用贴靠区域
 ///
-/// 使用相对坐标映射：保持窗口在当前显示器上的相对位置比例，
-/// 映射到目标显示器的对应位置。
-pub fn calculate_new_position(
-    window_frame: &Rect,
-    current_monitor: &MonitorInfo,
-    next_monitor: &MonitorInfo,
-) -> (Point, f64, f64) {
-    let current_work = &current_monitor.work_area;
-    let next_work = &next_monitor.work_area;
+/// 窗口与区域之间的边缘偏差（如 Windows 的不可见边框）原样保留。
+fn reapply_snap_zone(zone: SnapZone, window: &Rect, from_work: &Rect, to_work: &Rect) -> Rect {
+    let from = zone.rect_in(from_work);
+    let to = zone.rect_in(to_work);
+    let x = to.x + (window.min_x() - from.min_x());
+    let y = to.y + (window.min_y() - from.min_y());
+    let max_x = to.max_x() + (window.max_x() - from.max_x());
+    let max_y = to.max_y() + (window.max_y() - from.max_y());
+
+    Rect::new(x, y, max_x - x, max_y - y)
+}
 
-    // 计算窗口在当前显示器上的相对位置
-    let rel_x = if current_work.width > 0.0 {
-        (window_frame.x - current_work.x) / current_work.width
+/// 计算 `value` 在 `[origin, origin + length]` 区间中的相对比例
+fn relative(value: f64, origin: f64, length: f64) -> f64 {
+    if length > 0.0 {
+        (value - origin) / length
     } else {
         0.0
+    }
+}
+
+/// 按比例映射左上角位置，保持窗口尺寸
+pub fn place_proportional(window: &Rect, from_work: &Rect, to_work: &Rect) -> Rect {
+    Rect::new(
+        to_work.x + to_work.width * relative(window.x, from_work.x, from_work.width),
+        to_work.y + to_work.height * relative(window.y, from_work.y, from_work.height),
+        window.width,
+        window.height,
+    )
+}
+
+/// 按比例映射位置和尺寸，窗口占工作区的比例保持不变
+pub fn place_proportional_resize(window: &Rect, from_work: &Rect, to_work: &Rect) -> Rect {
+    let pos = place_proportional(window, from_work, to_work);
+    let scale_x = if from_work.width > 0.0 {
+        to_work.width / from_work.width
+    } else {
+        1.0
     };
-    let rel_y = if current_work.height > 0.0 {
-        (window_frame.y - current_work.y) / current_work.height
+    let scale_y = if from_work.height > 0.0 {
+        to_work.height / from_work.height
     } else {
-        0.0
+        1.0
     };
 
-    // 映射到目标显示器
-    let mut new_x = next_work.x + (next_work.width * rel_x);
-    let mut new_y = next_work.y + (next_work.height * rel_y);
+    Rect::new(
+        pos.x,
+        pos.y,
+        window.width * scale_x,
+        window.height * scale_y,
+    )
+}
+
+/// 保持窗口尺寸，放在目标工作区正中
+pub fn place_centered(window: &Rect, _from_work: &Rect, to_work: &Rect) -> Rect {
+    Rect::new(
+        to_work.mid_x() - window.width / 2.0,
+        to_work.mid_y() - window.height / 2.0,
+        window.width,
+        window.height,
+    )
+}
 
-    // 计算窗口在目标显示器上的尺寸（不超过工作区）
-    let final_width = window_frame.width.min(next_work.width);
-    let final_height = window_frame.height.min(next_work.height);
+/// 按比例映射窗口中心点，保持窗口尺寸
+pub fn place_center_mapped(window: &Rect, from_work: &Rect, to_work: &Rect) -> Rect {
+    let center_x =
+        to_work.x + to_work.width * relative(window.mid_x(), from_work.x, from_work.width);
+    let center_y =
+        to_work.y + to_work.height * relative(window.mid_y(), from_work.y, from_work.height);
 
-    // 边界保护：确保窗口不超出工作区
-    if new_x < next_work.min_x() {
-        new_x = next_work.min_x();
-    }
-    if new_y < next_work.min_y() {
-        new_y = next_work.min_y();
-    }
-    if new_x + final_width > next_work.max_x() {
-        new_x = next_work.max_x() - final_width;
-    }
-    if new_y + final_height > next_work.max_y() {
-        new_y = next_work.max_y() - final_height;
+    Rect::new(
+        center_x - window.width / 2.0,
+        center_y - window.height / 2.0,
+        window.width,
+        window.height,
+    )
+}
+
+/// 保持窗口相对工作区左上角的绝对偏移和尺寸
+pub fn place_mirrored(window: &Rect, from_work: &Rect, to_work: &Rect) -> Rect {
+    Rect::new(
+        to_work.x + (window.x - from_work.x),
+        to_work.y + (window.y - from_work.y),
+        window.width,
+        window.height,
+    )
+}
+
+/// 将窗口约束在工作区内：尺寸不超过工作区，位置不超出边界
+pub fn fit_to_work_area(rect: &Rect, work: &Rect) -> Rect {
+    let width = rect.width.min(work.width);
+    let height = rect.height.min(work.height);
+    let x = rect.x.min(work.max_x() - width).max(work.min_x());
+    let y = rect.y.min(work.max_y() - height).max(work.min_y());
+
+    Rect::new(x, y, width, height)
+}
+
+/// 将窗口等比缩小到能放入工作区（四周保留 `margin`），并约束位置不超出边界
+///
+/// 窗口本来就放得下时不改变尺寸；间距大于工作区一半时按零间距处理。
+pub fn fit_to_work_area_uniform(rect: &Rect, work: &Rect, margin: f64) -> Rect {
+    let margin = margin.max(0.0);
+    let area = if work.width > margin * 2.0 && work.height > margin * 2.0 {
+        Rect::new(
+            work.x + margin,
+            work.y + margin,
+            work.width - margin * 2.0,
+            work.height - margin * 2.0,
+        )
+    } else {
+        *work
+    };
+
+    let scale = if rect.width > 0.0 && rect.height > 0.0 {
+        (area.width / rect.width).min(area.height / rect.height).min(1.0)
+    } else {
+        1.0
+    };
+
+    fit_to_work_area(
+        &Rect::new(rect.x, rect.y, rect.width * scale, rect.height * scale),
+        &area,
+    )
+}
+
+/// 将显示器坐标空间中的矩形转换为逻辑坐标（以显示器原点为锚点缩放）
+pub fn to_logical_rect(rect: &Rect, monitor: &MonitorInfo) -> Rect {
+    let scale = effective_scale(monitor);
+    let origin = &monitor.bounds;
+    Rect::new(
+        origin.x + (rect.x - origin.x) / scale,
+        origin.y + (rect.y - origin.y) / scale,
+        rect.width / scale,
+        rect.height / scale,
+    )
+}
+
+/// 将逻辑坐标矩形转换回显示器坐标空间（`to_logical_rect` 的逆变换）
+pub fn to_physical_rect(rect: &Rect, monitor: &MonitorInfo) -> Rect {
+    let scale = effective_scale(monitor);
+    let origin = &monitor.bounds;
+    Rect::new(
+        origin.x + (rect.x - origin.x) * scale,
+        origin.y + (rect.y - origin.y) * scale,
+        rect.width * scale,
+        rect.height * scale,
+    )
+}
+
+/// 有效缩放比例（防止无效值导致除零）
+fn effective_scale(monitor: &MonitorInfo) -> f64 {
+    if monitor.scale_factor > 0.0 {
+        monitor.scale_factor
+    } else {
+        1.0
     }
+}
+
+/// 计算窗口从当前显示器移动到目标显示器后的新位置
+///
+/// 窗口贴靠在半屏、三分屏、四分屏或铺满工作区时（`options.preserve_snap`），
+/// 在目标工作区上重新应用同一区域；否则按 `options.strategy` 计算目标位置和尺寸，
+/// 再约束在目标工作区内（默认策略为相对坐标映射：保持窗口在当前显示器上的相对位置比例）。
+/// 启用 `options.keep_aspect_ratio` 时，放不下的窗口等比缩小，而不是分别裁剪宽高。
+///
+/// 计算在逻辑坐标中进行：窗口和工作区先按各自显示器的 `scale_factor` 转换为逻辑坐标，
+/// 结果再按目标显示器的比例转换回坐标空间，因此窗口在不同缩放比例的显示器之间保持逻辑尺寸。
+pub fn calculate_new_position(
+    window_frame: &Rect,
+    current_monitor: &MonitorInfo,
+    next_monitor: &MonitorInfo,
+    options: &PlacementOptions,
+) -> (Point, f64, f64) {
+    let window_frame = to_logical_rect(window_frame, current_monitor);
+    let current_work = to_logical_rect(&current_monitor.work_area, current_monitor);
+    let next_work = to_logical_rect(&next_monitor.work_area, next_monitor);
+
+    let snap_zone = options
+        .preserve_snap
+        .then(|| detect_snap_zone(&window_frame, &current_work, options.snap_tolerance))
+        .flatten();
+
+    let placed = match snap_zone {
+        Some(zone) => reapply_snap_zone(zone, &window_frame, &current_work, &next_work),
+        None => {
+            let placed = options
+                .strategy
+                .place(&window_frame, &current_work, &next_work);
+            if options.keep_aspect_ratio {
+                fit_to_work_area_uniform(&placed, &next_work, options.fit_margin)
+            } else {
+                fit_to_work_area(&placed, &next_work)
+            }
+        }
+    };
+    let result = to_physical_rect(&placed, next_monitor);
 
-    (Point { x: new_x, y: new_y }, final_width, final_height)
+    (
+        Point {
+            x: result.x,
+            y: result.y,
+        },
+        result.width,
+        result.height,
+    )
 }
 
 /// 根据窗口中心点找到所在的显示器（返回索引）
@@ -54,11 +385,156 @@ pub fn find_monitor_for_point(point: Point, monitors: &[MonitorInfo]) -> Option<
     monitors.iter().position(|m| m.bounds.contains(point))
 }
 
+/// 按规范顺序排列显示器：从左到右，x 相同时从上到下
+///
+/// 系统枚举顺序在睡眠唤醒、重新插拔后可能变化，排序后循环跳转的顺序只取决于物理排布。
+pub fn sort_monitors(monitors: &mut [MonitorInfo]) {
+    monitors.sort_by(|a, b| {
+        a.bounds
+            .x
+            .total_cmp(&b.bounds.x)
+            .then(a.bounds.y.total_cmp(&b.bounds.y))
+    });
+}
+
+/// 根据窗口区域找到所在的显示器（返回索引）
+///
+/// 选择与窗口相交面积最大的显示器；窗口与所有显示器都不相交时（如中心点落在
+/// 高度不同的显示器之间的空隙里、窗口被拖出屏幕外），退化为距离最近的显示器。
+/// 只有 `monitors` 为空时返回 `None`。
+pub fn find_monitor_for_rect(rect: &Rect, monitors: &[MonitorInfo]) -> Option<usize> {
+    let best_overlap = monitors
+        .iter()
+        .enumerate()
+        .filter_map(|(i, m)| m.bounds.intersection(rect).map(|r| (i, r.area())))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i);
+
+    best_overlap.or_else(|| {
+        monitors
+            .iter()
+            .enumerate()
+            .min_by(|a, b| {
+                a.1.bounds
+                    .distance_to(rect)
+                    .total_cmp(&b.1.bounds.distance_to(rect))
+            })
+            .map(|(i, _)| i)
+    })
+}
+
 /// 获取下一个显示器的索引（循环）
 pub fn next_monitor_index(current: usize, total: usize) -> usize {
     (current + 1) % total
 }
 
+/// 获取上一个显示器的索引（循环）
+pub fn previous_monitor_index(current: usize, total: usize) -> usize {
+    (current + total - 1) % total
+}
+
+/// 候选显示器相对当前显示器的几何关系
+struct Candidate {
+    index: usize,
+    /// 垂直于跳转方向上是否与当前显示器有重叠
+    overlaps: bool,
+    /// 沿跳转方向两者相邻边之间的距离（交错时为 0）
+    gap: f64,
+    /// 垂直于跳转方向上两者中心点的偏移
+    center_offset: f64,
+}
+
+/// 判断 `to` 是否位于 `from` 的 `direction` 方向（中心点越过 `from` 对应的边），是则返回两者的几何关系
+fn candidate_in_direction(
+    from: &Rect,
+    to: &Rect,
+    index: usize,
+    direction: Direction,
+) -> Option<Candidate> {
+    let (ahead, gap) = match direction {
+        Direction::Right => (to.mid_x() > from.max_x(), to.min_x() - from.max_x()),
+        Direction::Left => (to.mid_x() < from.min_x(), from.min_x() - to.max_x()),
+        Direction::Down => (to.mid_y() > from.max_y(), to.min_y() - from.max_y()),
+        Direction::Up => (to.mid_y() < from.min_y(), from.min_y() - to.max_y()),
+    };
+
+    if !ahead {
+        return None;
+    }
+
+    let (overlap, center_offset) = match direction {
+        Direction::Left | Direction::Right => (
+            from.max_y().min(to.max_y()) - from.min_y().max(to.min_y()),
+            (to.mid_y() - from.mid_y()).abs(),
+        ),
+        Direction::Up | Direction::Down => (
+            from.max_x().min(to.max_x()) - from.min_x().max(to.min_x()),
+            (to.mid_x() - from.mid_x()).abs(),
+        ),
+    };
+
+    Some(Candidate {
+        index,
+        overlaps: overlap > 0.0,
+        gap: gap.max(0.0),
+        center_offset,
+    })
+}
+
+/// 找到指定方向上最近的相邻显示器（返回索引）
+///
+/// 优先选择在垂直方向上与当前显示器有重叠的显示器，其次比较相邻边距离，
+/// 最后比较中心点偏移。该方向上没有显示器时返回 `None`。
+pub fn neighbor_in_direction(
+    current: usize,
+    monitors: &[MonitorInfo],
+    direction: Direction,
+) -> Option<usize> {
+    let from = &monitors.get(current)?.bounds;
+
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != current)
+        .filter_map(|(i, m)| candidate_in_direction(from, &m.bounds, i, direction))
+        .min_by(|a, b| {
+            b.overlaps
+                .cmp(&a.overlaps)
+                .then(a.gap.total_cmp(&b.gap))
+                .then(a.center_offset.total_cmp(&b.center_offset))
+        })
+        .map(|c| c.index)
+}
+
+/// 获取指定方向上的下一个显示器索引
+///
+/// 该方向上存在相邻显示器时返回最近的一个；否则回绕到相反方向上最远的显示器，
+/// 使得沿固定方向连续跳转时能够循环所有显示器。只有一个显示器时返回 `None`。
+pub fn next_monitor_in_direction(
+    current: usize,
+    monitors: &[MonitorInfo],
+    direction: Direction,
+) -> Option<usize> {
+    if let Some(idx) = neighbor_in_direction(current, monitors, direction) {
+        return Some(idx);
+    }
+
+    let from = &monitors.get(current)?.bounds;
+
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != current)
+        .filter_map(|(i, m)| candidate_in_direction(from, &m.bounds, i, direction.opposite()))
+        .min_by(|a, b| {
+            b.overlaps
+                .cmp(&a.overlaps)
+                .then(b.gap.total_cmp(&a.gap))
+                .then(a.center_offset.total_cmp(&b.center_offset))
+        })
+        .map(|c| c.index)
+}
+
 /// 判断给定点是否在标题栏区域内
 pub fn is_in_title_bar(point: Point, window_frame: &Rect, title_bar_height: f64) -> bool {
     point.x >= window_frame.min_x()
@@ -76,6 +552,7 @@ mod tests {
             id,
             bounds: Rect::new(x, y, w, h),
             work_area: Rect::new(x, y, w, h),
+            ..Default::default()
         }
     }
 
@@ -100,6 +577,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sort_monitors_left_to_right_then_top_to_bottom() {
+        let mut monitors = vec![
+            make_monitor(1, 1920.0, 0.0, 2560.0, 1440.0),
+            make_monitor(2, 0.0, 1080.0, 1920.0, 1080.0),
+            make_monitor(3, -1080.0, -600.0, 1080.0, 1920.0),
+            make_monitor(4, 0.0, 0.0, 1920.0, 1080.0),
+        ];
+
+        sort_monitors(&mut monitors);
+
+        let ids: Vec<u64> = monitors.iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![3, 4, 2, 1]);
+    }
+
+    #[test]
+    fn test_cycle_order_independent_of_enumeration_order() {
+        let layout = [
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0),
+            make_monitor(3, 4480.0, 0.0, 1920.0, 1080.0),
+        ];
+
+        let cycle = |order: [usize; 3]| -> Vec<u64> {
+            let mut monitors: Vec<MonitorInfo> = order.iter().map(|&i| layout[i].clone()).collect();
+            sort_monitors(&mut monitors);
+
+            let mut idx = 0;
+            let mut visited = Vec::new();
+            for _ in 0..monitors.len() {
+                visited.push(monitors[idx].id);
+                idx = next_monitor_index(idx, monitors.len());
+            }
+            visited
+        };
+
+        assert_eq!(cycle([0, 1, 2]), vec![1, 2, 3]);
+        assert_eq!(cycle([2, 0, 1]), vec![1, 2, 3]);
+        assert_eq!(cycle([1, 2, 0]), vec![1, 2, 3]);
+    }
+
     #[test]
     fn test_next_monitor_index() {
         assert_eq!(next_monitor_index(0, 2), 1);
@@ -107,6 +625,123 @@ mod tests {
         assert_eq!(next_monitor_index(2, 3), 0);
     }
 
+    #[test]
+    fn test_previous_monitor_index() {
+        assert_eq!(previous_monitor_index(0, 2), 1);
+        assert_eq!(previous_monitor_index(1, 2), 0);
+        assert_eq!(previous_monitor_index(0, 3), 2);
+    }
+
+    #[test]
+    fn test_find_monitor_for_rect_largest_overlap() {
+        let monitors = vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0),
+        ];
+
+        // 窗口横跨两块屏幕，大部分在右侧
+        let window = Rect::new(1800.0, 100.0, 800.0, 600.0);
+        assert_eq!(find_monitor_for_rect(&window, &monitors), Some(1));
+
+        let window = Rect::new(1500.0, 100.0, 800.0, 600.0);
+        assert_eq!(find_monitor_for_rect(&window, &monitors), Some(0));
+    }
+
+    #[test]
+    fn test_find_monitor_for_rect_center_in_gap() {
+        // 左侧屏幕较矮，窗口中心落在左屏下方的空隙中
+        let monitors = vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0),
+        ];
+        let window = Rect::new(1000.0, 900.0, 800.0, 500.0);
+
+        assert_eq!(find_monitor_for_point(window.center(), &monitors), None);
+        assert_eq!(find_monitor_for_rect(&window, &monitors), Some(0));
+    }
+
+    #[test]
+    fn test_find_monitor_for_rect_falls_back_to_nearest() {
+        let monitors = vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0),
+        ];
+
+        // 完全位于所有显示器之外
+        let window = Rect::new(500.0, 1200.0, 400.0, 300.0);
+        assert_eq!(find_monitor_for_rect(&window, &monitors), Some(0));
+
+        let window = Rect::new(5000.0, 100.0, 400.0, 300.0);
+        assert_eq!(find_monitor_for_rect(&window, &monitors), Some(1));
+
+        assert_eq!(find_monitor_for_rect(&window, &[]), None);
+    }
+
+    /// 三屏布局：笔记本在下方居中，竖屏在左侧，外接屏在笔记本正上方
+    fn three_screen_desk() -> Vec<MonitorInfo> {
+        vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, -1080.0, -600.0, 1080.0, 1920.0),
+            make_monitor(3, -320.0, -1440.0, 2560.0, 1440.0),
+        ]
+    }
+
+    #[test]
+    fn test_neighbor_in_direction_side_by_side() {
+        let monitors = vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0),
+        ];
+
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Right), Some(1));
+        assert_eq!(neighbor_in_direction(1, &monitors, Direction::Left), Some(0));
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Left), None);
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Up), None);
+    }
+
+    #[test]
+    fn test_neighbor_in_direction_three_screen_desk() {
+        let monitors = three_screen_desk();
+
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Up), Some(2));
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Left), Some(1));
+        assert_eq!(neighbor_in_direction(2, &monitors, Direction::Down), Some(0));
+        assert_eq!(neighbor_in_direction(1, &monitors, Direction::Right), Some(0));
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Right), None);
+    }
+
+    #[test]
+    fn test_neighbor_prefers_overlapping_monitor() {
+        // 右侧有一块紧挨着但错开的屏幕，以及一块稍远但垂直方向重叠的屏幕
+        let monitors = vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 1200.0, 1920.0, 1080.0),
+            make_monitor(3, 2000.0, 200.0, 1920.0, 1080.0),
+        ];
+
+        assert_eq!(neighbor_in_direction(0, &monitors, Direction::Right), Some(2));
+    }
+
+    #[test]
+    fn test_next_monitor_in_direction_wraps() {
+        let monitors = vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 1920.0, 1080.0),
+            make_monitor(3, 3840.0, 0.0, 1920.0, 1080.0),
+        ];
+
+        assert_eq!(next_monitor_in_direction(0, &monitors, Direction::Right), Some(1));
+        assert_eq!(next_monitor_in_direction(1, &monitors, Direction::Right), Some(2));
+        assert_eq!(next_monitor_in_direction(2, &monitors, Direction::Right), Some(0));
+        assert_eq!(next_monitor_in_direction(0, &monitors, Direction::Left), Some(2));
+    }
+
+    #[test]
+    fn test_next_monitor_in_direction_single_monitor() {
+        let monitors = vec![make_monitor(1, 0.0, 0.0, 1920.0, 1080.0)];
+        assert_eq!(next_monitor_in_direction(0, &monitors, Direction::Right), None);
+    }
+
     #[test]
     fn test_calculate_new_position_centered() {
         let m1 = make_monitor(1, 0.0, 0.0, 1920.0, 1080.0);
@@ -114,7 +749,7 @@ mod tests {
 
         // 窗口在 M1 中心
         let window = Rect::new(760.0, 340.0, 400.0, 400.0);
-        let (pos, w, h) = calculate_new_position(&window, &m1, &m2);
+        let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
 
         // 相对位置应映射到 M2 的相对位置
         assert!(pos.x >= m2.work_area.min_x());
@@ -132,7 +767,7 @@ mod tests {
 
         // 窗口比目标显示器还大
         let window = Rect::new(0.0, 0.0, 1920.0, 1080.0);
-        let (pos, w, h) = calculate_new_position(&window, &m1, &m2);
+        let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
 
         assert_eq!(w, 800.0);
         assert_eq!(h, 600.0);
@@ -140,6 +775,80 @@ mod tests {
         assert_eq!(pos.y, 0.0);
     }
 
+    fn make_scaled_monitor(id: u64, x: f64, y: f64, w: f64, h: f64, scale: f64) -> MonitorInfo {
+        MonitorInfo {
+            scale_factor: scale,
+            ..make_monitor(id, x, y, w, h)
+        }
+    }
+
+    #[test]
+    fn test_logical_physical_roundtrip() {
+        let m = make_scaled_monitor(1, 1920.0, 0.0, 3840.0, 2160.0, 2.0);
+        let rect = Rect::new(2320.0, 400.0, 1600.0, 1200.0);
+
+        let logical = to_logical_rect(&rect, &m);
+        assert_eq!(logical, Rect::new(2120.0, 200.0, 800.0, 600.0));
+        assert_eq!(to_physical_rect(&logical, &m), rect);
+    }
+
+    #[test]
+    fn test_mixed_dpi_100_to_200_keeps_logical_size() {
+        let m1 = make_scaled_monitor(1, 0.0, 0.0, 1920.0, 1080.0, 1.0);
+        let m2 = make_scaled_monitor(2, 1920.0, 0.0, 3840.0, 2160.0, 2.0);
+
+        let window = Rect::new(560.0, 240.0, 800.0, 600.0);
+        let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+
+        assert_eq!(w, 1600.0);
+        assert_eq!(h, 1200.0);
+        // 相对位置不变：560/1920、240/1080
+        assert_eq!(pos.x, 1920.0 + 3840.0 * (560.0 / 1920.0));
+        assert_eq!(pos.y, 2160.0 * (240.0 / 1080.0));
+    }
+
+    #[test]
+    fn test_mixed_dpi_200_to_100_keeps_logical_size() {
+        let m1 = make_scaled_monitor(1, 0.0, 0.0, 1920.0, 1080.0, 1.0);
+        let m2 = make_scaled_monitor(2, 1920.0, 0.0, 3840.0, 2160.0, 2.0);
+
+        let window = Rect::new(2320.0, 400.0, 1600.0, 1200.0);
+        let (pos, w, h) = calculate_new_position(&window, &m2, &m1, &PlacementOptions::default());
+
+        assert_eq!(w, 800.0);
+        assert_eq!(h, 600.0);
+        assert!(pos.x >= 0.0 && pos.x + w <= 1920.0);
+        assert!(pos.y >= 0.0 && pos.y + h <= 1080.0);
+    }
+
+    #[test]
+    fn test_mixed_dpi_clamps_in_logical_space() {
+        // 150% 的 2560x1440 显示器（逻辑 1706x960）→ 100% 的 1280x720 显示器
+        let m1 = make_scaled_monitor(1, 0.0, 0.0, 2560.0, 1440.0, 1.5);
+        let m2 = make_scaled_monitor(2, 2560.0, 0.0, 1280.0, 720.0, 1.0);
+
+        let window = Rect::new(0.0, 0.0, 2400.0, 1300.0);
+        let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+
+        assert_eq!(w, 1280.0);
+        assert_eq!(h, 720.0);
+        assert_eq!(pos.x, 2560.0);
+        assert_eq!(pos.y, 0.0);
+    }
+
+    #[test]
+    fn test_mixed_dpi_125_to_175() {
+        let m1 = make_scaled_monitor(1, 0.0, 0.0, 2400.0, 1350.0, 1.25);
+        let m2 = make_scaled_monitor(2, 2400.0, 0.0, 3360.0, 1890.0, 1.75);
+
+        // 逻辑尺寸 800x400
+        let window = Rect::new(100.0, 100.0, 1000.0, 500.0);
+        let (_, w, h) = calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+
+        assert!((w - 1400.0).abs() < 1e-9);
+        assert!((h - 700.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_is_in_title_bar() {
         let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
@@ -147,4 +856,330 @@ mod tests {
         assert!(!is_in_title_bar(Point { x: 500.0, y: 200.0 }, &frame, 40.0));
         assert!(!is_in_title_bar(Point { x: 50.0, y: 110.0 }, &frame, 40.0));
     }
+
+    /// 1920x1080 → 2560x1440 的工作区对
+    fn work_areas() -> (Rect, Rect) {
+        (
+            Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            Rect::new(1920.0, 0.0, 2560.0, 1440.0),
+        )
+    }
+
+    #[test]
+    fn test_placement_strategy_serde() {
+        let json = serde_json::to_string(&PlacementStrategy::CenterMapping).unwrap();
+        assert_eq!(json, "\"center_mapping\"");
+        let strategy: PlacementStrategy = serde_json::from_str("\"proportional_resize\"").unwrap();
+        assert_eq!(strategy, PlacementStrategy::ProportionalResize);
+        assert_eq!(PlacementStrategy::default(), PlacementStrategy::Proportional);
+    }
+
+    #[test]
+    fn test_fit_to_work_area() {
+        let work = Rect::new(0.0, 0.0, 1000.0, 800.0);
+        assert_eq!(
+            fit_to_work_area(&Rect::new(900.0, 700.0, 400.0, 300.0), &work),
+            Rect::new(600.0, 500.0, 400.0, 300.0)
+        );
+        assert_eq!(
+            fit_to_work_area(&Rect::new(-50.0, 100.0, 1200.0, 300.0), &work),
+            Rect::new(0.0, 100.0, 1000.0, 300.0)
+        );
+    }
+
+    mod proportional {
+        use super::*;
+
+        #[test]
+        fn test_maps_top_left_keeps_size() {
+            let (from, to) = work_areas();
+            let placed = place_proportional(&Rect::new(960.0, 540.0, 400.0, 300.0), &from, &to);
+            assert_eq!(placed, Rect::new(3200.0, 720.0, 400.0, 300.0));
+        }
+
+        #[test]
+        fn test_origin_maps_to_origin() {
+            let (from, to) = work_areas();
+            let placed = place_proportional(&Rect::new(0.0, 0.0, 800.0, 600.0), &from, &to);
+            assert_eq!(placed, Rect::new(1920.0, 0.0, 800.0, 600.0));
+        }
+    }
+
+    mod proportional_resize {
+        use super::*;
+
+        #[test]
+        fn test_scales_position_and_size() {
+            let (from, to) = work_areas();
+            let placed =
+                place_proportional_resize(&Rect::new(480.0, 270.0, 960.0, 540.0), &from, &to);
+            assert_eq!(placed, Rect::new(2560.0, 360.0, 1280.0, 720.0));
+        }
+
+        #[test]
+        fn test_full_work_area_stays_full() {
+            let (from, to) = work_areas();
+            let placed = place_proportional_resize(&from, &from, &to);
+            assert_eq!(placed, to);
+        }
+    }
+
+    mod centered {
+        use super::*;
+
+        #[test]
+        fn test_keeps_size_and_centers() {
+            let (from, to) = work_areas();
+            let placed = place_centered(&Rect::new(10.0, 20.0, 800.0, 600.0), &from, &to);
+            assert_eq!(placed, Rect::new(2800.0, 420.0, 800.0, 600.0));
+        }
+
+        #[test]
+        fn test_oversized_window_is_fitted() {
+            let m1 = make_monitor(1, 0.0, 0.0, 2560.0, 1440.0);
+            let m2 = make_monitor(2, 2560.0, 0.0, 1920.0, 1080.0);
+            let options = PlacementOptions {
+                strategy: PlacementStrategy::Centered,
+                ..Default::default()
+            };
+
+            let window = Rect::new(0.0, 0.0, 2400.0, 1000.0);
+            let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &options);
+            assert_eq!((pos.x, pos.y, w, h), (2560.0, 40.0, 1920.0, 1000.0));
+        }
+    }
+
+    mod center_mapping {
+        use super::*;
+
+        #[test]
+        fn test_maps_center_point() {
+            let (from, to) = work_areas();
+            // 中心点 (960, 540) → (3200, 720)
+            let placed = place_center_mapped(&Rect::new(760.0, 390.0, 400.0, 300.0), &from, &to);
+            assert_eq!(placed, Rect::new(3000.0, 570.0, 400.0, 300.0));
+        }
+
+        #[test]
+        fn test_right_edge_window_stays_near_right_edge() {
+            let (from, to) = work_areas();
+            let window = Rect::new(1520.0, 0.0, 400.0, 300.0);
+            let placed = fit_to_work_area(&place_center_mapped(&window, &from, &to), &to);
+            // 中心点映射后靠右，右边缘仍在工作区内
+            assert!(placed.max_x() <= to.max_x());
+            assert!(placed.mid_x() > to.mid_x());
+        }
+    }
+
+    mod mirror {
+        use super::*;
+
+        #[test]
+        fn test_keeps_absolute_offset() {
+            let (from, to) = work_areas();
+            let placed = place_mirrored(&Rect::new(100.0, 50.0, 400.0, 300.0), &from, &to);
+            assert_eq!(placed, Rect::new(2020.0, 50.0, 400.0, 300.0));
+        }
+
+        #[test]
+        fn test_offset_clamped_on_smaller_target() {
+            let m1 = make_monitor(1, 0.0, 0.0, 2560.0, 1440.0);
+            let m2 = make_monitor(2, 2560.0, 0.0, 1920.0, 1080.0);
+            let options = PlacementOptions {
+                strategy: PlacementStrategy::Mirror,
+                ..Default::default()
+            };
+
+            let window = Rect::new(2000.0, 1000.0, 400.0, 300.0);
+            let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &options);
+            assert_eq!((pos.x, pos.y, w, h), (4080.0, 780.0, 400.0, 300.0));
+        }
+    }
+
+    mod snap {
+        use super::*;
+
+        fn work() -> Rect {
+            Rect::new(0.0, 0.0, 1920.0, 1040.0)
+        }
+
+        #[test]
+        fn test_detect_halves_and_maximized() {
+            let work = work();
+            assert_eq!(
+                detect_snap_zone(&work, &work, DEFAULT_SNAP_TOLERANCE),
+                Some(SnapZone::Maximized)
+            );
+            assert_eq!(
+                detect_snap_zone(&Rect::new(0.0, 0.0, 960.0, 1040.0), &work, 1.0),
+                Some(SnapZone::LeftHalf)
+            );
+            assert_eq!(
+                detect_snap_zone(&Rect::new(960.0, 0.0, 960.0, 1040.0), &work, 1.0),
+                Some(SnapZone::RightHalf)
+            );
+            assert_eq!(
+                detect_snap_zone(&Rect::new(0.0, 520.0, 1920.0, 520.0), &work, 1.0),
+                Some(SnapZone::BottomHalf)
+            );
+        }
+
+        #[test]
+        fn test_detect_quarters_and_thirds() {
+            let work = work();
+            assert_eq!(
+                detect_snap_zone(&Rect::new(960.0, 520.0, 960.0, 520.0), &work, 1.0),
+                Some(SnapZone::BottomRightQuarter)
+            );
+            assert_eq!(
+                detect_snap_zone(&Rect::new(640.0, 0.0, 640.0, 1040.0), &work, 1.0),
+                Some(SnapZone::CenterThird)
+            );
+            assert_eq!(
+                detect_snap_zone(&Rect::new(0.0, 0.0, 1280.0, 1040.0), &work, 1.0),
+                Some(SnapZone::LeftTwoThirds)
+            );
+        }
+
+        #[test]
+        fn test_detect_with_invisible_border() {
+            // Windows 的 frame 包含约 7px 的不可见边框
+            let window = Rect::new(-7.0, 0.0, 974.0, 1047.0);
+            assert_eq!(
+                detect_snap_zone(&window, &work(), DEFAULT_SNAP_TOLERANCE),
+                Some(SnapZone::LeftHalf)
+            );
+        }
+
+        #[test]
+        fn test_detect_outside_tolerance() {
+            let work = work();
+            assert_eq!(
+                detect_snap_zone(&Rect::new(100.0, 100.0, 800.0, 600.0), &work, 16.0),
+                None
+            );
+            assert_eq!(
+                detect_snap_zone(&Rect::new(0.0, 0.0, 930.0, 1040.0), &work, 16.0),
+                None
+            );
+        }
+
+        #[test]
+        fn test_left_half_stays_left_half() {
+            let m1 = make_monitor(1, 0.0, 0.0, 1920.0, 1080.0);
+            let m2 = make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0);
+
+            let window = Rect::new(0.0, 0.0, 960.0, 1080.0);
+            let (pos, w, h) =
+                calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+            assert_eq!((pos.x, pos.y, w, h), (1920.0, 0.0, 1280.0, 1440.0));
+        }
+
+        #[test]
+        fn test_quarter_keeps_border_offsets() {
+            let m1 = make_monitor(1, 0.0, 0.0, 2560.0, 1440.0);
+            let m2 = make_monitor(2, 2560.0, 0.0, 1920.0, 1080.0);
+
+            let window = Rect::new(1273.0, 720.0, 1294.0, 727.0);
+            let (pos, w, h) =
+                calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+            assert_eq!((pos.x, pos.y, w, h), (3513.0, 540.0, 974.0, 547.0));
+        }
+
+        #[test]
+        fn test_snap_across_mixed_dpi() {
+            let m1 = make_scaled_monitor(1, 0.0, 0.0, 1920.0, 1080.0, 1.0);
+            let m2 = make_scaled_monitor(2, 1920.0, 0.0, 3840.0, 2160.0, 2.0);
+
+            let window = Rect::new(960.0, 0.0, 960.0, 1080.0);
+            let (pos, w, h) =
+                calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+            assert_eq!((pos.x, pos.y, w, h), (3840.0, 0.0, 1920.0, 2160.0));
+        }
+
+        #[test]
+        fn test_disabled_falls_back_to_strategy() {
+            let m1 = make_monitor(1, 0.0, 0.0, 1920.0, 1080.0);
+            let m2 = make_monitor(2, 1920.0, 0.0, 2560.0, 1440.0);
+            let options = PlacementOptions {
+                preserve_snap: false,
+                ..Default::default()
+            };
+
+            let window = Rect::new(0.0, 0.0, 960.0, 1080.0);
+            let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &options);
+            assert_eq!((pos.x, pos.y, w, h), (1920.0, 0.0, 960.0, 1080.0));
+        }
+    }
+
+    mod aspect_ratio {
+        use super::*;
+
+        fn options(margin: f64) -> PlacementOptions {
+            PlacementOptions {
+                keep_aspect_ratio: true,
+                fit_margin: margin,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn test_fitting_window_is_unchanged() {
+            let work = Rect::new(0.0, 0.0, 1920.0, 1080.0);
+            let window = Rect::new(100.0, 100.0, 800.0, 450.0);
+            assert_eq!(fit_to_work_area_uniform(&window, &work, 20.0), window);
+        }
+
+        #[test]
+        fn test_portrait_target() {
+            let m1 = make_monitor(1, 0.0, 0.0, 2560.0, 1440.0);
+            let m2 = make_monitor(2, 2560.0, 0.0, 1080.0, 1920.0);
+
+            // 16:9 视频窗口放到竖屏上：按宽度缩小
+            let window = Rect::new(200.0, 200.0, 1600.0, 900.0);
+            let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &options(20.0));
+
+            assert_eq!(w, 1040.0);
+            assert_eq!(h, 585.0);
+            assert!((w / h - 16.0 / 9.0).abs() < 1e-9);
+            assert_eq!(pos.x, 2580.0);
+            assert!(pos.y >= 20.0 && pos.y + h <= 1900.0);
+        }
+
+        #[test]
+        fn test_ultrawide_target() {
+            let m1 = make_monitor(1, 0.0, 0.0, 1440.0, 2560.0);
+            let m2 = make_monitor(2, 1440.0, 0.0, 3440.0, 1440.0);
+
+            // 竖长窗口放到带鱼屏上：按高度缩小
+            let window = Rect::new(0.0, 400.0, 1200.0, 1600.0);
+            let (pos, w, h) = calculate_new_position(&window, &m1, &m2, &options(40.0));
+
+            assert_eq!(h, 1360.0);
+            assert_eq!(w, 1020.0);
+            assert_eq!(pos.y, 40.0);
+            assert!(pos.x >= 1480.0 && pos.x + w <= 4840.0);
+        }
+
+        #[test]
+        fn test_independent_clamp_without_option() {
+            let m1 = make_monitor(1, 0.0, 0.0, 2560.0, 1440.0);
+            let m2 = make_monitor(2, 2560.0, 0.0, 1080.0, 1920.0);
+
+            let window = Rect::new(200.0, 200.0, 1600.0, 900.0);
+            let (_, w, h) =
+                calculate_new_position(&window, &m1, &m2, &PlacementOptions::default());
+            assert_eq!((w, h), (1080.0, 900.0));
+        }
+
+        #[test]
+        fn test_oversized_margin_is_ignored() {
+            let work = Rect::new(0.0, 0.0, 100.0, 100.0);
+            let window = Rect::new(0.0, 0.0, 200.0, 100.0);
+            assert_eq!(
+                fit_to_work_area_uniform(&window, &work, 80.0),
+                Rect::new(0.0, 0.0, 100.0, 50.0)
+            );
+        }
+    }
 }
@@ -0,0 +1,292 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::monitor::PlacementStrategy;
+
+/// 用于匹配应用规则的窗口属性，平台取不到的属性为 `None`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowInfo {
+    /// 进程名（Windows 上为可执行文件名，如 `mstsc.exe`）
+    pub process_name: Option<String>,
+    /// macOS 应用的 bundle id（如 `com.microsoft.rdc.macos`）
+    pub bundle_id: Option<String>,
+    /// 窗口类（Windows 窗口类名、X11 WM_CLASS、Wayland app_id）
+    pub class: Option<String>,
+    /// 窗口标题
+    pub title: Option<String>,
+}
+
+/// 一条应用规则
+///
+/// 所有设置了的匹配条件都满足时规则生效；一个匹配条件都没有的规则不匹配任何窗口。
+/// 进程名、bundle id 和窗口类不区分大小写，进程名可以省略 `.exe` 后缀；
+/// 标题为正则表达式。
+///
+/// ```toml
+/// # 远程桌面客户端永远不移动
+/// [[rules]]
+/// process = "mstsc"
+/// hop = false
+///
+/// # 自绘标题栏较高的应用
+/// [[rules]]
+/// class = "obsidian"
+/// title_bar_height = 56.0
+/// placement = "centered"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppRule {
+    /// 匹配进程名
+    pub process: Option<String>,
+    /// 匹配 macOS bundle id
+    pub bundle_id: Option<String>,
+    /// 匹配窗口类
+    pub class: Option<String>,
+    /// 匹配窗口标题的正则表达式
+    pub title: Option<String>,
+
+    /// `false` 禁止移动匹配的窗口；`true` 让排在后面的 `hop = false` 规则对匹配的窗口不再生效
+    ///
+    /// `true` 只豁免后续的排除规则，仍然按触发位置检查标题栏和标签页。
+    pub hop: Option<bool>,
+    /// 覆盖标题栏检测高度（像素）
+    pub title_bar_height: Option<f64>,
+    /// 覆盖放置策略
+    pub placement: Option<PlacementStrategy>,
+    /// 点击标题栏时固定移到第 N 个显示器（从 1 开始，按 `sort_monitors` 的顺序）
+    pub monitor: Option<usize>,
+}
+
+impl AppRule {
+    fn has_matcher(&self) -> bool {
+        self.process.is_some()
+            || self.bundle_id.is_some()
+            || self.class.is_some()
+            || self.title.is_some()
+    }
+}
+
+/// 规则对某个窗口生效的设置，未被任何规则设置的项为 `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RuleOverrides {
+    pub hop: Option<bool>,
+    pub title_bar_height: Option<f64>,
+    pub placement: Option<PlacementStrategy>,
+    pub monitor: Option<usize>,
+}
+
+impl RuleOverrides {
+    /// 是否允许移动窗口（没有规则排除时允许）
+    pub fn allows_hop(&self) -> bool {
+        self.hop != Some(false)
+    }
+}
+
+/// 编译后的规则
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: AppRule,
+    title: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn matches(&self, info: &WindowInfo) -> bool {
+        matches_name(&self.rule.process, &info.process_name, true)
+            && matches_name(&self.rule.bundle_id, &info.bundle_id, false)
+            && matches_name(&self.rule.class, &info.class, false)
+            && self.title.as_ref().is_none_or(|regex| {
+                info.title
+                    .as_deref()
+                    .is_some_and(|title| regex.is_match(title))
+            })
+    }
+}
+
+/// 不区分大小写比较名称；`strip_exe` 时忽略 `.exe` 后缀
+fn matches_name(pattern: &Option<String>, value: &Option<String>, strip_exe: bool) -> bool {
+    let Some(pattern) = pattern else {
+        return true;
+    };
+    let Some(value) = value else {
+        return false;
+    };
+
+    let normalize = |name: &str| {
+        let name = name.to_lowercase();
+        match name.strip_suffix(".exe") {
+            Some(stem) if strip_exe => stem.to_string(),
+            _ => name,
+        }
+    };
+    normalize(pattern) == normalize(value)
+}
+
+/// 按配置顺序匹配的规则集
+///
+/// 每一项设置取第一条设置了该项的匹配规则，因此更具体的规则应该写在前面。
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// 编译规则；没有匹配条件或标题正则无效的规则会被跳过
+    pub fn new(rules: &[AppRule]) -> Self {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rule)| {
+                if !rule.has_matcher() {
+                    log::warn!("第 {} 条应用规则没有任何匹配条件，已忽略", index + 1);
+                    return None;
+                }
+
+                let title = match rule.title.as_deref().map(Regex::new).transpose() {
+                    Ok(title) => title,
+                    Err(e) => {
+                        log::warn!("第 {} 条应用规则的标题正则无效，已忽略: {}", index + 1, e);
+                        return None;
+                    }
+                };
+
+                Some(CompiledRule {
+                    rule: rule.clone(),
+                    title,
+                })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 是否有规则按标题匹配（标题会变化，匹配结果不能按窗口缓存）
+    pub fn matches_title(&self) -> bool {
+        self.rules.iter().any(|compiled| compiled.title.is_some())
+    }
+
+    /// 计算对窗口生效的设置
+    pub fn resolve(&self, info: &WindowInfo) -> RuleOverrides {
+        let mut overrides = RuleOverrides::default();
+        for compiled in self.rules.iter().filter(|compiled| compiled.matches(info)) {
+            let rule = &compiled.rule;
+            overrides.hop = overrides.hop.or(rule.hop);
+            overrides.title_bar_height = overrides.title_bar_height.or(rule.title_bar_height);
+            overrides.placement = overrides.placement.or(rule.placement);
+            overrides.monitor = overrides.monitor.or(rule.monitor);
+        }
+        overrides
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(process: &str, class: &str, title: &str) -> WindowInfo {
+        WindowInfo {
+            process_name: Some(process.to_string()),
+            bundle_id: None,
+            class: Some(class.to_string()),
+            title: Some(title.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_match_conditions() {
+        let rules = RuleSet::new(&[AppRule {
+            process: Some("MSTSC".to_string()),
+            title: Some("^远程桌面".to_string()),
+            hop: Some(false),
+            ..AppRule::default()
+        }]);
+
+        let matched = info("mstsc.exe", "TscShellContainerClass", "远程桌面连接");
+        assert!(!rules.resolve(&matched).allows_hop());
+
+        assert!(rules.matches_title());
+
+        let other_title = info("mstsc.exe", "TscShellContainerClass", "设置");
+        assert!(rules.resolve(&other_title).allows_hop());
+
+        // 取不到标题时标题条件不满足
+        let no_title = WindowInfo {
+            title: None,
+            ..matched
+        };
+        assert_eq!(rules.resolve(&no_title), RuleOverrides::default());
+    }
+
+    #[test]
+    fn test_first_rule_wins_per_setting() {
+        let rules = RuleSet::new(&[
+            AppRule {
+                class: Some("firefox".to_string()),
+                title: Some("Picture-in-Picture".to_string()),
+                hop: Some(true),
+                monitor: Some(2),
+                ..AppRule::default()
+            },
+            AppRule {
+                class: Some("Firefox".to_string()),
+                hop: Some(false),
+                title_bar_height: Some(48.0),
+                placement: Some(PlacementStrategy::Centered),
+                ..AppRule::default()
+            },
+        ]);
+
+        let pip = rules.resolve(&info("firefox", "firefox", "Picture-in-Picture"));
+        assert!(pip.allows_hop());
+        assert_eq!(pip.monitor, Some(2));
+        assert_eq!(pip.title_bar_height, Some(48.0));
+        assert_eq!(pip.placement, Some(PlacementStrategy::Centered));
+
+        let main = rules.resolve(&info("firefox", "firefox", "Mozilla Firefox"));
+        assert!(!main.allows_hop());
+        assert_eq!(main.monitor, None);
+    }
+
+    #[test]
+    fn test_invalid_rules_are_skipped() {
+        let rules = RuleSet::new(&[
+            AppRule {
+                hop: Some(false),
+                ..AppRule::default()
+            },
+            AppRule {
+                title: Some("(unclosed".to_string()),
+                hop: Some(false),
+                ..AppRule::default()
+            },
+        ]);
+
+        assert!(rules.is_empty());
+        assert!(!rules.matches_title());
+        assert!(rules
+            .resolve(&info("game.exe", "UnityWndClass", ""))
+            .allows_hop());
+    }
+
+    #[test]
+    fn test_rule_toml() {
+        let rule: AppRule = toml::from_str(
+            r#"
+            bundle_id = "com.parsec.www"
+            hop = false
+            placement = "center_mapping"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rule.bundle_id.as_deref(), Some("com.parsec.www"));
+        assert_eq!(rule.placement, Some(PlacementStrategy::CenterMapping));
+
+        let toml_str = toml::to_string_pretty(&rule).unwrap();
+        assert!(!toml_str.contains("process"));
+        assert_eq!(toml::from_str::<AppRule>(&toml_str).unwrap(), rule);
+    }
+}
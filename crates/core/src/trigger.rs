@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::hotkey::HotkeyAction;
+use crate::Point;
+
+/// 双击时两次点击之间允许的最大位移（逻辑像素）
+pub const DOUBLE_CLICK_DISTANCE: f64 = 4.0;
+
+/// 触发移动的鼠标按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MouseButton {
+    /// 中键
+    #[default]
+    Middle,
+    /// 侧键 X1（通常为“后退”）
+    X1,
+    /// 侧键 X2（通常为“前进”）
+    X2,
+}
+
+impl MouseButton {
+    /// 钩子上报的按键编号（2 = 中键，3 = X1，4 = X2）
+    pub fn number(self) -> u32 {
+        match self {
+            MouseButton::Middle => 2,
+            MouseButton::X1 => 3,
+            MouseButton::X2 => 4,
+        }
+    }
+
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
+            2 => Some(MouseButton::Middle),
+            3 => Some(MouseButton::X1),
+            4 => Some(MouseButton::X2),
+            _ => None,
+        }
+    }
+}
+
+/// 修饰键状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// macOS 的 Command，Windows 的 Win 键，Linux 的 Super
+    pub cmd: bool,
+}
+
+impl Modifiers {
+    /// 是否按下了 `required` 中的全部修饰键（允许额外按下其他修饰键）
+    pub fn contains(&self, required: &Modifiers) -> bool {
+        (!required.ctrl || self.ctrl)
+            && (!required.alt || self.alt)
+            && (!required.shift || self.shift)
+            && (!required.cmd || self.cmd)
+    }
+}
+
+/// 需要的点击次数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClickCount {
+    #[default]
+    Single,
+    /// 双击：第一次点击照常放行，第二次点击触发移动
+    Double,
+}
+
+/// 点击位置要求
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerRegion {
+    /// 只在标题栏上触发（跳过交互式标签页）
+    #[default]
+    TitleBar,
+    /// 窗口任意位置
+    Anywhere,
+}
+
+/// 触发移动的条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Trigger {
+    pub button: MouseButton,
+    /// 必须同时按下的修饰键
+    pub modifiers: Modifiers,
+    pub click_count: ClickCount,
+    pub region: TriggerRegion,
+    /// 双击的最大间隔（毫秒）
+    pub double_click_ms: u64,
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Middle,
+            modifiers: Modifiers::default(),
+            click_count: ClickCount::Single,
+            region: TriggerRegion::TitleBar,
+            double_click_ms: 400,
+        }
+    }
+}
+
+impl Trigger {
+    /// 按键和修饰键是否符合（不含点击次数判定）
+    pub fn matches_press(&self, button: u32, modifiers: &Modifiers) -> bool {
+        button == self.button.number() && modifiers.contains(&self.modifiers)
+    }
+}
+
+/// 点击次数判定（双击检测）
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last: Option<(Instant, Point)>,
+}
+
+impl ClickTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次符合按键条件的按下，返回是否满足点击次数要求
+    pub fn register(&mut self, trigger: &Trigger, point: Point, now: Instant) -> bool {
+        if trigger.click_count == ClickCount::Single {
+            return true;
+        }
+
+        let interval = Duration::from_millis(trigger.double_click_ms);
+        if let Some((at, last)) = self.last.take() {
+            let close = (point.x - last.x).abs() <= DOUBLE_CLICK_DISTANCE
+                && (point.y - last.y).abs() <= DOUBLE_CLICK_DISTANCE;
+            if close && now.saturating_duration_since(at) <= interval {
+                return true;
+            }
+        }
+
+        self.last = Some((now, point));
+        false
+    }
+}
+
+/// 在标题栏上滚动滚轮切换显示器
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WheelTrigger {
+    /// 默认关闭，避免影响标签栏等区域的正常滚动
+    pub enabled: bool,
+    /// 必须同时按下的修饰键
+    pub modifiers: Modifiers,
+    /// 反转方向（默认向上滚动移到上一个显示器，向下滚动移到下一个）
+    pub invert: bool,
+    /// 滚动停止超过该时间（毫秒）后才能再次触发，一次快速滚动只切换一个显示器
+    pub debounce_ms: u64,
+}
+
+impl Default for WheelTrigger {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            modifiers: Modifiers::default(),
+            invert: false,
+            debounce_ms: 300,
+        }
+    }
+}
+
+impl WheelTrigger {
+    /// 滚动方向对应的动作；`delta` 为正表示向上滚动，为 0 时返回 `None`
+    pub fn action(&self, delta: i32) -> Option<HotkeyAction> {
+        if delta == 0 {
+            return None;
+        }
+        if (delta > 0) != self.invert {
+            Some(HotkeyAction::Previous)
+        } else {
+            Some(HotkeyAction::Next)
+        }
+    }
+}
+
+/// 滚轮防抖：连续滚动时只有第一次滚动会触发
+#[derive(Debug, Default)]
+pub struct WheelDebouncer {
+    last: Option<Instant>,
+}
+
+impl WheelDebouncer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次滚动，返回距离上一次滚动是否已超过防抖间隔
+    ///
+    /// 每次滚动都会刷新计时，因此惯性滚动期间不会重复触发。
+    pub fn register(&mut self, trigger: &WheelTrigger, now: Instant) -> bool {
+        let interval = Duration::from_millis(trigger.debounce_ms);
+        let quiet = self
+            .last
+            .is_none_or(|at| now.saturating_duration_since(at) > interval);
+        self.last = Some(now);
+        quiet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: Point = Point { x: 100.0, y: 10.0 };
+
+    #[test]
+    fn test_matches_press() {
+        let trigger = Trigger {
+            button: MouseButton::X1,
+            modifiers: Modifiers {
+                ctrl: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let ctrl_shift = Modifiers {
+            ctrl: true,
+            shift: true,
+            ..Default::default()
+        };
+
+        assert!(trigger.matches_press(3, &ctrl_shift));
+        assert!(!trigger.matches_press(3, &Modifiers::default()));
+        assert!(!trigger.matches_press(2, &ctrl_shift));
+        assert!(Trigger::default().matches_press(2, &ctrl_shift));
+    }
+
+    #[test]
+    fn test_button_numbers() {
+        for button in [MouseButton::Middle, MouseButton::X1, MouseButton::X2] {
+            assert_eq!(MouseButton::from_number(button.number()), Some(button));
+        }
+        assert_eq!(MouseButton::from_number(1), None);
+    }
+
+    #[test]
+    fn test_single_click() {
+        let mut tracker = ClickTracker::new();
+        assert!(tracker.register(&Trigger::default(), ORIGIN, Instant::now()));
+    }
+
+    #[test]
+    fn test_double_click() {
+        let trigger = Trigger {
+            click_count: ClickCount::Double,
+            ..Default::default()
+        };
+        let mut tracker = ClickTracker::new();
+        let start = Instant::now();
+
+        assert!(!tracker.register(&trigger, ORIGIN, start));
+        assert!(tracker.register(&trigger, ORIGIN, start + Duration::from_millis(200)));
+        // 第三次点击重新开始计数
+        assert!(!tracker.register(&trigger, ORIGIN, start + Duration::from_millis(300)));
+
+        // 间隔太长
+        let mut tracker = ClickTracker::new();
+        assert!(!tracker.register(&trigger, ORIGIN, start));
+        assert!(!tracker.register(&trigger, ORIGIN, start + Duration::from_millis(500)));
+
+        // 位移太大
+        let mut tracker = ClickTracker::new();
+        let moved = Point { x: 120.0, y: 10.0 };
+        assert!(!tracker.register(&trigger, ORIGIN, start));
+        assert!(!tracker.register(&trigger, moved, start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_wheel_action() {
+        let wheel = WheelTrigger::default();
+        assert_eq!(wheel.action(120), Some(HotkeyAction::Previous));
+        assert_eq!(wheel.action(-1), Some(HotkeyAction::Next));
+        assert_eq!(wheel.action(0), None);
+
+        let inverted = WheelTrigger {
+            invert: true,
+            ..Default::default()
+        };
+        assert_eq!(inverted.action(120), Some(HotkeyAction::Next));
+    }
+
+    #[test]
+    fn test_wheel_debounce() {
+        let wheel = WheelTrigger::default();
+        let mut debouncer = WheelDebouncer::new();
+        let start = Instant::now();
+
+        assert!(debouncer.register(&wheel, start));
+        // 同一次滚动中的后续事件被忽略，即使总时长超过了防抖间隔
+        for ms in [100, 200, 300, 400] {
+            assert!(!debouncer.register(&wheel, start + Duration::from_millis(ms)));
+        }
+        // 停顿之后再次滚动
+        assert!(debouncer.register(&wheel, start + Duration::from_millis(800)));
+    }
+}
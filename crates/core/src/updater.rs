@@ -48,6 +48,11 @@ fn get_platform_keyword() -> &'static str {
             "x86_64" => "macOS-x86_64",
             _ => "macOS-universal",
         }
+    } else if cfg!(target_os = "linux") {
+        match std::env::consts::ARCH {
+            "aarch64" => "linux-arm64",
+            _ => "linux-x64",
+        }
     } else {
         "unknown"
     }
@@ -363,6 +368,8 @@ mod tests {
         assert!(keyword.starts_with("macOS"));
         #[cfg(target_os = "windows")]
         assert!(keyword.starts_with("win"));
+        #[cfg(target_os = "linux")]
+        assert!(keyword.starts_with("linux"));
     }
 
     /// 验证代理配置生效：向 OS 申请一个空闲端口后立即释放，确保该端口没有任何进程监听，
@@ -0,0 +1,1347 @@
+use screenhop_core::config::AppConfig;
+use screenhop_core::frame_memory::{app_key, FrameMemory};
+use screenhop_core::history::{HopHistory, HopRecord};
+use screenhop_core::hotkey::HotkeyAction;
+use screenhop_core::rules::{RuleOverrides, RuleSet};
+use screenhop_core::trigger::{ClickTracker, TriggerRegion, WheelDebouncer};
+use screenhop_core::{monitor, MonitorInfo, Point, Rect};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{HitTester, MonitorManager, MouseEvent, WindowHandle, WindowManager};
+
+/// 一次跳转的计算结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct HopPlan {
+    /// 当前显示器索引
+    pub from_index: usize,
+    /// 目标显示器索引
+    pub to_index: usize,
+    /// 当前显示器
+    pub from: MonitorInfo,
+    /// 目标显示器
+    pub to: MonitorInfo,
+    /// 窗口在目标显示器上的 frame
+    pub frame: Rect,
+    /// frame 是否来自窗口记忆
+    pub restored: bool,
+}
+
+/// 平台无法通知显示器变化时，显示器缓存的有效期
+const TOPOLOGY_TTL: Duration = Duration::from_secs(2);
+
+/// 按窗口缓存的判定信息最多保存的窗口数，超过后清空重新缓存
+const WINDOW_CACHE_CAPACITY: usize = 256;
+
+/// 按窗口缓存的 frame、标签页区域和按标题匹配的规则结果的有效期
+///
+/// 窗口可能被用户拖动、标签页会增减、标题会变化，这些都没有通知，只能按时间过期。
+const WINDOW_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// 缓存的显示器拓扑
+struct Topology {
+    monitors: Vec<MonitorInfo>,
+    fetched_at: Instant,
+}
+
+/// 缓存的规则匹配结果
+struct CachedOverrides {
+    overrides: RuleOverrides,
+    fetched_at: Instant,
+}
+
+/// 缓存的窗口几何信息，钩子回调中的判定只读这份缓存
+#[derive(Debug, Clone)]
+struct WindowGeometry {
+    frame: Option<Rect>,
+    /// 交互式标签页区域
+    tabs: Vec<Rect>,
+    fetched_at: Instant,
+}
+
+/// 按窗口 ID 缓存的值，超过容量时清空
+fn cache_insert<V>(cache: &mut HashMap<u64, V>, id: u64, value: V) {
+    if cache.len() >= WINDOW_CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(id, value);
+}
+
+/// 跳转引擎：点击判定、目标计算和窗口移动，与具体平台无关
+pub struct HopEngine<W, H, M> {
+    window_manager: W,
+    hit_tester: H,
+    monitor_manager: M,
+    config: AppConfig,
+    /// 编译后的应用规则
+    rules: RuleSet,
+    /// 按窗口 ID 缓存的规则匹配结果（有规则按标题匹配时按 `WINDOW_CACHE_TTL` 过期）
+    rule_cache: Mutex<HashMap<u64, CachedOverrides>>,
+    /// 按窗口 ID 缓存的 frame 和标签页区域，窗口被移动后清除
+    geometry_cache: Mutex<HashMap<u64, WindowGeometry>>,
+    frame_memory: Mutex<FrameMemory>,
+    frame_memory_path: Option<PathBuf>,
+    /// 显示器拓扑缓存，收到显示器变化通知时清空
+    topology: Arc<Mutex<Option<Topology>>>,
+    /// 平台是否会通知显示器变化（否则缓存按 `TOPOLOGY_TTL` 过期）
+    notified: bool,
+    /// 双击判定状态
+    clicks: Mutex<ClickTracker>,
+    /// 滚轮防抖状态
+    wheel: Mutex<WheelDebouncer>,
+    /// 最近的移动记录，用于撤销 / 重做
+    history: Mutex<HopHistory<WindowHandle>>,
+}
+
+impl<W, H, M> HopEngine<W, H, M>
+where
+    W: WindowManager,
+    H: HitTester,
+    M: MonitorManager,
+{
+    pub fn new(window_manager: W, hit_tester: H, monitor_manager: M, config: AppConfig) -> Self {
+        let topology: Arc<Mutex<Option<Topology>>> = Arc::new(Mutex::new(None));
+        let cache = topology.clone();
+        let notified = monitor_manager.subscribe_changes(Box::new(move || {
+            log::debug!("显示器布局变化，清空拓扑缓存");
+            *cache.lock().unwrap() = None;
+        }));
+        if !notified {
+            log::info!("平台不支持显示器变化通知，显示器列表将定期刷新");
+        }
+
+        Self {
+            window_manager,
+            hit_tester,
+            monitor_manager,
+            rules: RuleSet::new(&config.rules),
+            rule_cache: Mutex::new(HashMap::new()),
+            geometry_cache: Mutex::new(HashMap::new()),
+            config,
+            frame_memory: Mutex::new(FrameMemory::new()),
+            frame_memory_path: None,
+            topology,
+            notified,
+            clicks: Mutex::new(ClickTracker::new()),
+            wheel: Mutex::new(WheelDebouncer::new()),
+            history: Mutex::new(HopHistory::default()),
+        }
+    }
+
+    /// 从文件加载窗口记忆，并在每次跳转后写回
+    pub fn with_frame_memory_file(mut self, path: PathBuf) -> Self {
+        let memory = FrameMemory::load(&path).unwrap_or_else(|e| {
+            log::warn!("加载窗口记忆失败，使用空记忆: {}", e);
+            FrameMemory::new()
+        });
+
+        self.frame_memory = Mutex::new(memory);
+        self.frame_memory_path = Some(path);
+        self
+    }
+
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    pub fn window_manager(&self) -> &W {
+        &self.window_manager
+    }
+
+    pub fn monitor_manager(&self) -> &M {
+        &self.monitor_manager
+    }
+
+    /// 处理一次点击（同步判定并移动）
+    /// 返回 true 表示事件已消费（窗口已移动），返回 false 表示放行事件
+    pub fn handle_click(&self, point: Point) -> bool {
+        match self.hit_window(point) {
+            Some((handle, overrides)) => self.hop_window(&handle, &overrides),
+            None => false,
+        }
+    }
+
+    /// 处理钩子上报的鼠标事件：先按触发条件过滤，再判定并移动
+    pub fn handle_event(&self, event: &MouseEvent) -> bool {
+        if event.wheel_delta != 0 {
+            return self.handle_wheel(event);
+        }
+        self.matches_trigger(event) && self.handle_click(event.point)
+    }
+
+    /// 处理一次滚轮滚动（同步判定并移动）：标题栏上向上滚动移到上一个显示器，向下移到下一个
+    fn handle_wheel(&self, event: &MouseEvent) -> bool {
+        let Some(action) = self.wheel_action(event) else {
+            return false;
+        };
+        match self.hit_title_bar(event.point) {
+            Some((handle, overrides)) => self.hop_window_to(&handle, &overrides, action),
+            None => false,
+        }
+    }
+
+    /// 滚轮事件对应的移动动作
+    ///
+    /// 未启用滚轮切换、修饰键不符或处于同一次连续滚动中（防抖）时返回 `None`。
+    pub fn wheel_action(&self, event: &MouseEvent) -> Option<HotkeyAction> {
+        let wheel = &self.config.wheel;
+        if !wheel.enabled || !event.modifiers.contains(&wheel.modifiers) {
+            return None;
+        }
+        let action = wheel.action(event.wheel_delta)?;
+
+        self.wheel
+            .lock()
+            .unwrap()
+            .register(wheel, Instant::now())
+            .then_some(action)
+    }
+
+    /// 事件的按键、修饰键和点击次数是否满足配置的触发条件
+    ///
+    /// 双击模式下第一次点击返回 false（放行），第二次点击才返回 true。
+    pub fn matches_trigger(&self, event: &MouseEvent) -> bool {
+        let trigger = &self.config.trigger;
+        if !trigger.matches_press(event.button, &event.modifiers) {
+            return false;
+        }
+
+        self.clicks
+            .lock()
+            .unwrap()
+            .register(trigger, event.point, Instant::now())
+    }
+
+    /// 快速判定点击是否落在可移动窗口的标题栏上，返回需要移动的窗口和对它生效的应用规则
+    ///
+    /// 只做命中检测，不移动窗口，可以直接在系统钩子回调中调用：
+    /// 窗口的规则、frame 和标签页区域按窗口缓存，同一窗口的后续点击不再查询。
+    /// 触发位置配置为 `anywhere` 时不检查标题栏。
+    /// 窗口没有可去的目标显示器时放行点击（规则固定的显示器就是当前显示器，
+    /// 或 `hop_direction` 方向上没有显示器）。
+    pub fn hit_window(&self, point: Point) -> Option<(WindowHandle, RuleOverrides)> {
+        let (handle, overrides) = self.hit_window_in(point, self.config.trigger.region)?;
+
+        // 没有固定目标、也不限定方向时，总能移到下一个显示器
+        if overrides.monitor.is_none() && self.config.hop_direction.is_none() {
+            return Some((handle, overrides));
+        }
+
+        let monitors = self.monitors();
+        let has_target = self
+            .window_geometry(&handle)
+            .frame
+            .and_then(|frame| monitor::find_monitor_for_rect(&frame, &monitors))
+            .and_then(|from_index| self.click_target(from_index, &monitors, &overrides))
+            .is_some();
+        if !has_target {
+            log::debug!("窗口没有可去的目标显示器，放行");
+            return None;
+        }
+
+        Some((handle, overrides))
+    }
+
+    /// 与 [`Self::hit_window`] 相同，但无论触发位置如何配置都要求落在标题栏上（滚轮切换使用）
+    pub fn hit_title_bar(&self, point: Point) -> Option<(WindowHandle, RuleOverrides)> {
+        self.hit_window_in(point, TriggerRegion::TitleBar)
+    }
+
+    fn hit_window_in(
+        &self,
+        point: Point,
+        region: TriggerRegion,
+    ) -> Option<(WindowHandle, RuleOverrides)> {
+        if self.monitors().len() < 2 {
+            log::debug!("只有一个显示器，无法移动");
+            return None;
+        }
+
+        // 1. 获取点击位置的窗口
+        let handle = match self.window_manager.get_window_at(point) {
+            Some(h) => h,
+            None => {
+                log::debug!("点击位置没有窗口");
+                return None;
+            }
+        };
+
+        let overrides = self.overrides(&handle);
+        if !overrides.allows_hop() {
+            log::debug!("窗口被应用规则排除，跳过");
+            return None;
+        }
+
+        if region == TriggerRegion::Anywhere {
+            return Some((handle, overrides));
+        }
+
+        // 2. 检查是否点击在交互式标签页上（不移动）
+        let geometry = self.window_geometry(&handle);
+        if geometry.tabs.iter().any(|tab| tab.contains(point)) {
+            log::debug!("点击在交互式标签页上，跳过");
+            return None;
+        }
+
+        // 3. 检查是否在标题栏区域内（规则可以覆盖标题栏高度）
+        let on_title_bar = match overrides.title_bar_height {
+            Some(height) => self
+                .hit_tester
+                .is_title_bar_hit_with_height(&handle, point, height),
+            None => self.hit_tester.is_title_bar_hit(&handle, point),
+        };
+        if !on_title_bar {
+            log::debug!("点击不在标题栏内");
+            return None;
+        }
+
+        Some((handle, overrides))
+    }
+
+    /// 当前显示器列表（优先使用缓存的拓扑）
+    pub fn monitors(&self) -> Vec<MonitorInfo> {
+        let mut cache = self.topology.lock().unwrap();
+        if let Some(topology) = cache.as_ref() {
+            if self.notified || topology.fetched_at.elapsed() < TOPOLOGY_TTL {
+                return topology.monitors.clone();
+            }
+        }
+
+        let monitors = self.monitor_manager.get_monitors();
+        // 枚举失败时不缓存，下次点击重试
+        if !monitors.is_empty() {
+            *cache = Some(Topology {
+                monitors: monitors.clone(),
+                fetched_at: Instant::now(),
+            });
+        }
+        monitors
+    }
+
+    /// 将窗口移动到下一个显示器（或规则固定的显示器），返回是否成功
+    pub fn hop_window(&self, handle: &WindowHandle, overrides: &RuleOverrides) -> bool {
+        self.hop_window_with(handle, |frame| self.plan_hop(handle, frame, overrides))
+    }
+
+    /// 按快捷键动作移动窗口，返回是否成功
+    pub fn hop_window_to(
+        &self,
+        handle: &WindowHandle,
+        overrides: &RuleOverrides,
+        action: HotkeyAction,
+    ) -> bool {
+        self.hop_window_with(handle, |frame| {
+            self.plan_hop_to(handle, frame, overrides, action)
+        })
+    }
+
+    /// 按快捷键动作移动当前焦点窗口（撤销 / 重做不需要焦点窗口）
+    pub fn hop_focused(&self, action: HotkeyAction) -> bool {
+        match action {
+            HotkeyAction::Undo => return self.undo_hop(),
+            HotkeyAction::Redo => return self.redo_hop(),
+            _ => {}
+        }
+
+        match self.window_manager.get_focused_window() {
+            Some(handle) => self.hop_window_to(&handle, &self.overrides(&handle), action),
+            None => {
+                log::debug!("没有获得焦点的窗口");
+                false
+            }
+        }
+    }
+
+    fn hop_window_with(
+        &self,
+        handle: &WindowHandle,
+        plan: impl FnOnce(&Rect) -> Option<HopPlan>,
+    ) -> bool {
+        let frame = match self.window_manager.get_window_frame(handle) {
+            Some(f) => f,
+            None => {
+                log::debug!("无法获取窗口 frame");
+                return false;
+            }
+        };
+
+        let plan = match plan(&frame) {
+            Some(plan) => plan,
+            None => return false,
+        };
+
+        log::info!(
+            "移动窗口: 显示器 {} → {}, 位置 ({:.0},{:.0}) → ({:.0},{:.0}){}",
+            plan.from_index,
+            plan.to_index,
+            frame.x,
+            frame.y,
+            plan.frame.x,
+            plan.frame.y,
+            if plan.restored { "（恢复记忆）" } else { "" },
+        );
+
+        let maximized = self.window_manager.is_maximized(handle);
+        if !self.move_window(handle, &frame, plan.frame, maximized, maximized) {
+            return false;
+        }
+
+        if self.config.remember_frames {
+            self.remember_frame(handle, &plan.from, frame);
+        }
+
+        self.history.lock().unwrap().push(HopRecord {
+            handle: handle.clone(),
+            from: frame,
+            maximized,
+            to: plan.frame,
+        });
+        true
+    }
+
+    /// 撤销最近一次移动：恢复窗口移动前的 frame 和最大化状态
+    ///
+    /// 窗口已经关闭的记录会被跳过，继续撤销更早的一次移动。
+    pub fn undo_hop(&self) -> bool {
+        let Some(record) = self.replay("撤销", HopHistory::pop_undo, |record| record.from) else {
+            return false;
+        };
+        self.history.lock().unwrap().push_undone(record);
+        true
+    }
+
+    /// 重做最近一次撤销的移动，窗口已经关闭的记录会被跳过
+    pub fn redo_hop(&self) -> bool {
+        let Some(record) = self.replay("重做", HopHistory::pop_redo, |record| record.to) else {
+            return false;
+        };
+        self.history.lock().unwrap().push_redone(record);
+        true
+    }
+
+    /// 取出历史记录并把窗口移到 `target` 选出的 frame，返回成功执行的记录
+    fn replay(
+        &self,
+        label: &str,
+        pop: impl Fn(&mut HopHistory<WindowHandle>) -> Option<HopRecord<WindowHandle>>,
+        target: impl Fn(&HopRecord<WindowHandle>) -> Rect,
+    ) -> Option<HopRecord<WindowHandle>> {
+        loop {
+            let Some(record) = pop(&mut self.history.lock().unwrap()) else {
+                log::info!("没有可以{}的移动", label);
+                return None;
+            };
+            let Some(frame) = self.window_manager.get_window_frame(&record.handle) else {
+                log::debug!("窗口已不存在，跳过该条移动记录");
+                continue;
+            };
+
+            let target = target(&record);
+            log::info!(
+                "{}移动: 位置 ({:.0},{:.0}) → ({:.0},{:.0})",
+                label,
+                frame.x,
+                frame.y,
+                target.x,
+                target.y,
+            );
+            let maximized = self.window_manager.is_maximized(&record.handle);
+            return self
+                .move_window(&record.handle, &frame, target, maximized, record.maximized)
+                .then_some(record);
+        }
+    }
+
+    /// 对窗口生效的应用规则设置
+    ///
+    /// 没有配置规则时不查询窗口属性；结果按窗口 ID 缓存，
+    /// 有规则按标题匹配时缓存按 `WINDOW_CACHE_TTL` 过期，以便跟上标题变化。
+    pub fn overrides(&self, handle: &WindowHandle) -> RuleOverrides {
+        if self.rules.is_empty() {
+            return RuleOverrides::default();
+        }
+
+        let id = handle.stable_id();
+        if let Some(id) = id {
+            let cache = self.rule_cache.lock().unwrap();
+            if let Some(cached) = cache.get(&id).filter(|cached| {
+                !self.rules.matches_title() || cached.fetched_at.elapsed() < WINDOW_CACHE_TTL
+            }) {
+                return cached.overrides;
+            }
+        }
+
+        let overrides = self
+            .rules
+            .resolve(&self.window_manager.get_window_info(handle));
+        if let Some(id) = id {
+            let cached = CachedOverrides {
+                overrides,
+                fetched_at: Instant::now(),
+            };
+            cache_insert(&mut self.rule_cache.lock().unwrap(), id, cached);
+        }
+        overrides
+    }
+
+    /// 窗口的 frame 和标签页区域（优先使用缓存）
+    ///
+    /// 第一次点击某个窗口时查询，之后在 `WINDOW_CACHE_TTL` 内或窗口被移动之前都直接读缓存。
+    fn window_geometry(&self, handle: &WindowHandle) -> WindowGeometry {
+        let id = handle.stable_id();
+        if let Some(id) = id {
+            let cache = self.geometry_cache.lock().unwrap();
+            if let Some(geometry) = cache
+                .get(&id)
+                .filter(|geometry| geometry.fetched_at.elapsed() < WINDOW_CACHE_TTL)
+            {
+                return geometry.clone();
+            }
+        }
+
+        let geometry = WindowGeometry {
+            frame: self.window_manager.get_window_frame(handle),
+            tabs: self.hit_tester.interactive_tab_regions(handle),
+            fetched_at: Instant::now(),
+        };
+        if let Some(id) = id {
+            let mut cache = self.geometry_cache.lock().unwrap();
+            cache_insert(&mut cache, id, geometry.clone());
+        }
+        geometry
+    }
+
+    /// 计算窗口的跳转目标（不移动窗口）
+    ///
+    /// 应用规则固定了目标显示器时直接移到该显示器。
+    pub fn plan_hop(
+        &self,
+        handle: &WindowHandle,
+        frame: &Rect,
+        overrides: &RuleOverrides,
+    ) -> Option<HopPlan> {
+        self.plan_hop_with(handle, frame, overrides, |from_index, monitors| {
+            self.click_target(from_index, monitors, overrides)
+        })
+    }
+
+    /// 点击触发时的目标显示器索引：规则固定的显示器、`hop_direction` 方向上的显示器或下一个显示器
+    fn click_target(
+        &self,
+        from_index: usize,
+        monitors: &[MonitorInfo],
+        overrides: &RuleOverrides,
+    ) -> Option<usize> {
+        if let Some(number) = overrides.monitor {
+            let target = HotkeyAction::Monitor(number).target_index(from_index, monitors);
+            if target.is_none() {
+                log::debug!("窗口已在规则指定的显示器 {} 上或该显示器不存在", number);
+            }
+            return target;
+        }
+
+        match self.config.hop_direction {
+            Some(direction) => {
+                let target = monitor::next_monitor_in_direction(from_index, monitors, direction);
+                if target.is_none() {
+                    log::debug!("{:?} 方向上没有可用的显示器", direction);
+                }
+                target
+            }
+            None => Some(monitor::next_monitor_index(from_index, monitors.len())),
+        }
+    }
+
+    /// 按快捷键动作计算跳转目标（不移动窗口）
+    pub fn plan_hop_to(
+        &self,
+        handle: &WindowHandle,
+        frame: &Rect,
+        overrides: &RuleOverrides,
+        action: HotkeyAction,
+    ) -> Option<HopPlan> {
+        self.plan_hop_with(handle, frame, overrides, |from_index, monitors| {
+            let target = action.target_index(from_index, monitors);
+            if target.is_none() {
+                log::debug!("{:?} 没有可用的目标显示器", action);
+            }
+            target
+        })
+    }
+
+    /// 用 `target` 选出目标显示器索引，计算窗口的跳转目标
+    fn plan_hop_with(
+        &self,
+        handle: &WindowHandle,
+        frame: &Rect,
+        overrides: &RuleOverrides,
+        target: impl FnOnce(usize, &[MonitorInfo]) -> Option<usize>,
+    ) -> Option<HopPlan> {
+        if !overrides.allows_hop() {
+            log::debug!("窗口被应用规则排除，不移动");
+            return None;
+        }
+
+        // 1. 获取所有显示器
+        let monitors = self.monitors();
+        if monitors.len() < 2 {
+            log::debug!("只有一个显示器，无法移动");
+            return None;
+        }
+
+        // 2. 找到窗口当前所在的显示器（相交面积最大者）
+        let from_index = match monitor::find_monitor_for_rect(frame, &monitors) {
+            Some(idx) => idx,
+            None => {
+                log::debug!("无法确定窗口所在显示器");
+                return None;
+            }
+        };
+
+        // 3. 计算目标显示器
+        let to_index = target(from_index, &monitors)?;
+
+        let from = &monitors[from_index];
+        let to = &monitors[to_index];
+
+        // 4. 优先恢复窗口在目标显示器上记忆的 frame，否则按放置策略计算
+        let remembered = if self.config.remember_frames {
+            self.frame_key(handle)
+                .and_then(|key| self.frame_memory.lock().unwrap().recall(&key, to))
+        } else {
+            None
+        };
+
+        let (target, restored) = match remembered {
+            Some(remembered) => (remembered, true),
+            None => {
+                let mut options = self.config.placement_options();
+                if let Some(strategy) = overrides.placement {
+                    options.strategy = strategy;
+                }
+                let (pos, width, height) =
+                    monitor::calculate_new_position(frame, from, to, &options);
+                (Rect::new(pos.x, pos.y, width, height), false)
+            }
+        };
+
+        Some(HopPlan {
+            from_index,
+            to_index,
+            from: from.clone(),
+            to: to.clone(),
+            frame: target,
+            restored,
+        })
+    }
+
+    /// 把窗口从 `frame` 移到 `target`
+    ///
+    /// `maximized` 为窗口当前是否最大化（最大化的窗口先还原才能移动），
+    /// `maximize` 为移动后是否最大化。
+    fn move_window(
+        &self,
+        handle: &WindowHandle,
+        frame: &Rect,
+        target: Rect,
+        maximized: bool,
+        maximize: bool,
+    ) -> bool {
+        let wm = &self.window_manager;
+
+        // 窗口的 frame 和标签页位置即将变化，下次点击重新查询
+        if let Some(id) = handle.stable_id() {
+            self.geometry_cache.lock().unwrap().remove(&id);
+        }
+
+        // 如果窗口是最大化的，先还原
+        if maximized {
+            if let Err(e) = wm.restore_window(handle) {
+                log::error!("还原窗口失败: {}", e);
+            }
+        }
+
+        // 设置新位置和尺寸
+        if let Err(e) = wm.set_window_position(
+            handle,
+            Point {
+                x: target.x,
+                y: target.y,
+            },
+        ) {
+            log::error!("设置窗口位置失败: {}", e);
+            return false;
+        }
+
+        // 尺寸有变化时才调整（目标显示器更小、或恢复记忆的尺寸）
+        if (target.width - frame.width).abs() > 1.0 || (target.height - frame.height).abs() > 1.0
+        {
+            if let Err(e) = wm.set_window_size(handle, target.width, target.height) {
+                log::error!("设置窗口尺寸失败: {}", e);
+            }
+        }
+
+        // 移动前是最大化状态，移动后恢复最大化
+        if maximize {
+            if let Err(e) = wm.maximize_window(handle) {
+                log::error!("恢复窗口最大化失败: {}", e);
+            }
+        }
+
+        // 激活窗口
+        if let Err(e) = wm.activate_window(handle) {
+            log::error!("激活窗口失败: {}", e);
+        }
+
+        true
+    }
+
+    /// 窗口在 frame 记忆中的键
+    ///
+    /// 只在内存中记忆时使用窗口标识；写入磁盘的记忆会跨越会话，
+    /// 窗口标识届时早已被复用，因此改用应用标识和标题。
+    fn frame_key(&self, handle: &WindowHandle) -> Option<String> {
+        if self.frame_memory_path.is_some() {
+            return app_key(&self.window_manager.get_window_info(handle));
+        }
+        handle.stable_id().map(|id| id.to_string())
+    }
+
+    /// 记录窗口离开显示器时的 frame
+    ///
+    /// 没有稳定标识的窗口不记忆：下次查询到的标识会不同，记录永远无法命中。
+    fn remember_frame(&self, handle: &WindowHandle, monitor: &MonitorInfo, frame: Rect) {
+        let Some(key) = self.frame_key(handle) else {
+            log::debug!("窗口没有稳定的标识，不记忆 frame");
+            return;
+        };
+
+        let mut memory = self.frame_memory.lock().unwrap();
+        memory.remember(&key, monitor, frame);
+
+        if let Some(path) = &self.frame_memory_path {
+            if let Err(e) = memory.save(path) {
+                log::warn!("保存窗口记忆失败: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeDesktop, FakeHitTester, FakeMonitorManager, FakeWindowManager};
+    use screenhop_core::monitor::Direction;
+    use screenhop_core::rules::{AppRule, WindowInfo};
+
+    type FakeEngine = HopEngine<FakeWindowManager, FakeHitTester, FakeMonitorManager>;
+
+    fn make_monitor(id: u64, x: f64, y: f64, w: f64, h: f64) -> MonitorInfo {
+        MonitorInfo {
+            id,
+            name: format!("monitor-{}", id),
+            bounds: Rect::new(x, y, w, h),
+            work_area: Rect::new(x, y, w, h),
+            ..Default::default()
+        }
+    }
+
+    /// 左：2560x1440，右：1920x1080
+    fn two_monitors() -> FakeDesktop {
+        FakeDesktop::new(vec![
+            make_monitor(1, 0.0, 0.0, 2560.0, 1440.0),
+            make_monitor(2, 2560.0, 0.0, 1920.0, 1080.0),
+        ])
+    }
+
+    fn engine(desktop: &FakeDesktop, config: AppConfig) -> FakeEngine {
+        HopEngine::new(
+            desktop.window_manager(),
+            desktop.hit_tester(),
+            desktop.monitor_manager(),
+            config,
+        )
+    }
+
+    fn title_bar_of(frame: Rect) -> Point {
+        Point {
+            x: frame.mid_x(),
+            y: frame.y + 10.0,
+        }
+    }
+
+    #[test]
+    fn test_title_bar_click_hops_window() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(title_bar_of(frame)));
+
+        let moved = desktop.window_frame(id).unwrap();
+        assert!(moved.x >= 2560.0);
+        assert_eq!((moved.width, moved.height), (800.0, 600.0));
+        assert_eq!(desktop.active_window(), Some(id));
+    }
+
+    #[test]
+    fn test_clicks_that_are_passed_through() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        desktop.add_tab(id, Rect::new(100.0, 100.0, 200.0, 30.0));
+        let engine = engine(&desktop, AppConfig::default());
+
+        // 桌面空白处
+        assert!(!engine.handle_click(Point { x: 2000.0, y: 1000.0 }));
+        // 窗口内容区
+        assert!(!engine.handle_click(Point { x: 500.0, y: 400.0 }));
+        // 标签页
+        assert!(!engine.handle_click(Point { x: 150.0, y: 110.0 }));
+
+        assert_eq!(desktop.window_frame(id), Some(frame));
+        assert_eq!(desktop.active_window(), None);
+    }
+
+    #[test]
+    fn test_tab_regions_cached_until_window_moves() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        desktop.add_tab(id, Rect::new(100.0, 100.0, 200.0, 30.0));
+        let engine = engine(&desktop, AppConfig::default());
+
+        // 同一窗口的多次点击只查询一次标签页区域
+        assert!(engine.hit_window(Point { x: 150.0, y: 110.0 }).is_none());
+        assert!(engine.hit_window(Point { x: 160.0, y: 110.0 }).is_none());
+        assert!(engine.hit_window(Point { x: 500.0, y: 110.0 }).is_some());
+        assert_eq!(desktop.tab_queries(), 1);
+
+        // 窗口被移动后重新查询
+        assert!(engine.handle_click(Point { x: 500.0, y: 110.0 }));
+        let moved = desktop.window_frame(id).unwrap();
+        assert!(engine.hit_window(title_bar_of(moved)).is_some());
+        assert_eq!(desktop.tab_queries(), 2);
+    }
+
+    #[test]
+    fn test_trigger_filters_events() {
+        use screenhop_core::trigger::{ClickCount, Modifiers, MouseButton, Trigger};
+
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let config = AppConfig {
+            trigger: Trigger {
+                button: MouseButton::X1,
+                modifiers: Modifiers {
+                    alt: true,
+                    ..Default::default()
+                },
+                click_count: ClickCount::Double,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let engine = engine(&desktop, config);
+        let event = |button, alt| MouseEvent {
+            point: title_bar_of(frame),
+            button,
+            wheel_delta: 0,
+            modifiers: Modifiers {
+                alt,
+                ..Default::default()
+            },
+        };
+
+        // 按键或修饰键不符
+        assert!(!engine.handle_event(&event(2, true)));
+        assert!(!engine.handle_event(&event(3, false)));
+        // 双击的第一次放行，第二次移动
+        assert!(!engine.handle_event(&event(3, true)));
+        assert!(engine.handle_event(&event(3, true)));
+        assert!(desktop.window_frame(id).unwrap().x >= 2560.0);
+    }
+
+    #[test]
+    fn test_wheel_cycles_monitors() {
+        use screenhop_core::trigger::{Modifiers, WheelTrigger};
+
+        let desktop = FakeDesktop::new(vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 1920.0, 1080.0),
+            make_monitor(3, 3840.0, 0.0, 1920.0, 1080.0),
+        ]);
+        let id = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        let wheel = |delta, shift| MouseEvent {
+            point: title_bar_of(desktop.window_frame(id).unwrap()),
+            button: 0,
+            wheel_delta: delta,
+            modifiers: Modifiers {
+                shift,
+                ..Default::default()
+            },
+        };
+        let enabled = |modifiers| AppConfig {
+            wheel: WheelTrigger {
+                enabled: true,
+                modifiers,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // 默认关闭
+        let disabled = engine(&desktop, AppConfig::default());
+        assert!(!disabled.handle_event(&wheel(120, false)));
+
+        // 向上滚动移到上一个显示器（从第 1 个回绕到第 3 个），同一次滚动的后续事件被忽略
+        let plain = engine(&desktop, enabled(Modifiers::default()));
+        assert!(plain.handle_event(&wheel(120, false)));
+        assert!(desktop.window_frame(id).unwrap().x >= 3840.0);
+        assert!(!plain.handle_event(&wheel(-120, false)));
+        assert!(desktop.window_frame(id).unwrap().x >= 3840.0);
+
+        // 需要修饰键时，不按修饰键的滚动直接放行；向下滚动移到下一个显示器
+        let shift = Modifiers {
+            shift: true,
+            ..Default::default()
+        };
+        let with_shift = engine(&desktop, enabled(shift));
+        assert!(!with_shift.handle_event(&wheel(-120, false)));
+        assert!(with_shift.handle_event(&wheel(-120, true)));
+        assert!(desktop.window_frame(id).unwrap().x < 1920.0);
+    }
+
+    #[test]
+    fn test_anywhere_region_skips_title_bar_check() {
+        use screenhop_core::trigger::{Trigger, TriggerRegion};
+
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let config = AppConfig {
+            trigger: Trigger {
+                region: TriggerRegion::Anywhere,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let engine = engine(&desktop, config);
+
+        // 窗口内容区
+        assert!(engine.handle_click(Point { x: 500.0, y: 400.0 }));
+        assert!(desktop.window_frame(id).unwrap().x >= 2560.0);
+        // 桌面空白处仍然放行
+        assert!(!engine.handle_click(Point { x: 50.0, y: 1300.0 }));
+    }
+
+    #[test]
+    fn test_topmost_window_is_hopped() {
+        let desktop = two_monitors();
+        let below = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        let above = desktop.add_window(Rect::new(300.0, 80.0, 800.0, 600.0));
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(Point { x: 500.0, y: 100.0 }));
+        assert!(desktop.window_frame(above).unwrap().x >= 2560.0);
+        assert_eq!(desktop.window_frame(below).unwrap().x, 100.0);
+    }
+
+    #[test]
+    fn test_single_monitor_does_nothing() {
+        let desktop = FakeDesktop::new(vec![make_monitor(1, 0.0, 0.0, 1920.0, 1080.0)]);
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(!engine.handle_click(title_bar_of(frame)));
+        assert_eq!(desktop.window_frame(id), Some(frame));
+    }
+
+    #[test]
+    fn test_hop_direction() {
+        let desktop = FakeDesktop::new(vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 1920.0, 1080.0),
+            make_monitor(3, 0.0, -1080.0, 1920.0, 1080.0),
+        ]);
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let config = AppConfig {
+            hop_direction: Some(Direction::Up),
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        assert!(engine.handle_click(title_bar_of(frame)));
+        let moved = desktop.window_frame(id).unwrap();
+        assert!(moved.y < 0.0 && moved.x < 1920.0);
+    }
+
+    #[test]
+    fn test_hop_direction_without_target_is_passed_through() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let config = AppConfig {
+            hop_direction: Some(Direction::Up),
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        // 上下方向都没有显示器：判定时就放行点击，而不是吞掉它
+        assert!(engine.hit_window(title_bar_of(frame)).is_none());
+        assert!(!engine.handle_click(title_bar_of(frame)));
+        assert_eq!(desktop.window_frame(id), Some(frame));
+    }
+
+    #[test]
+    fn test_hotkey_hops_focused_window() {
+        let desktop = FakeDesktop::new(vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 1920.0, 1080.0),
+            make_monitor(3, 3840.0, 0.0, 1920.0, 1080.0),
+        ]);
+        let focused = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        let other = desktop.add_window(Rect::new(300.0, 200.0, 800.0, 600.0));
+        desktop.focus(focused);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.hop_focused(HotkeyAction::Monitor(3)));
+        assert!(desktop.window_frame(focused).unwrap().x >= 3840.0);
+        assert_eq!(desktop.window_frame(other).unwrap().x, 300.0);
+
+        assert!(engine.hop_focused(HotkeyAction::Previous));
+        let frame = desktop.window_frame(focused).unwrap();
+        assert!(frame.x >= 1920.0 && frame.x < 3840.0);
+
+        assert!(engine.hop_focused(HotkeyAction::Direction(Direction::Left)));
+        assert!(desktop.window_frame(focused).unwrap().x < 1920.0);
+        // 已经在第 1 个显示器上
+        assert!(!engine.hop_focused(HotkeyAction::Monitor(1)));
+    }
+
+    #[test]
+    fn test_rules_exclude_and_override_title_bar() {
+        let desktop = two_monitors();
+        let excluded_frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let excluded = desktop.add_window(excluded_frame);
+        desktop.set_window_info(
+            excluded,
+            WindowInfo {
+                process_name: Some("mstsc.exe".to_string()),
+                ..Default::default()
+            },
+        );
+        let tall_frame = Rect::new(1000.0, 500.0, 800.0, 600.0);
+        let tall = desktop.add_window(tall_frame);
+        desktop.set_window_info(
+            tall,
+            WindowInfo {
+                class: Some("Obsidian".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let config = AppConfig {
+            rules: vec![
+                AppRule {
+                    process: Some("mstsc".to_string()),
+                    hop: Some(false),
+                    ..Default::default()
+                },
+                AppRule {
+                    class: Some("obsidian".to_string()),
+                    title_bar_height: Some(80.0),
+                    ..Default::default()
+                },
+            ],
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        // 被排除的窗口：点击放行，快捷键也不移动
+        assert!(!engine.handle_click(title_bar_of(excluded_frame)));
+        desktop.focus(excluded);
+        assert!(!engine.hop_focused(HotkeyAction::Next));
+        assert_eq!(desktop.window_frame(excluded), Some(excluded_frame));
+
+        // 默认标题栏高度为 30，规则把它提高到 80
+        let below_default = Point {
+            x: tall_frame.mid_x(),
+            y: tall_frame.y + 60.0,
+        };
+        assert!(engine.handle_click(below_default));
+        assert!(desktop.window_frame(tall).unwrap().x >= 2560.0);
+    }
+
+    #[test]
+    fn test_rule_overrides_cached_per_window() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        desktop.set_window_info(
+            id,
+            WindowInfo {
+                class: Some("Obsidian".to_string()),
+                ..Default::default()
+            },
+        );
+        let config = AppConfig {
+            rules: vec![AppRule {
+                class: Some("obsidian".to_string()),
+                title_bar_height: Some(80.0),
+                ..Default::default()
+            }],
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        // 判定时解析一次规则，移动时沿用判定的结果
+        assert!(engine.handle_click(title_bar_of(frame)));
+        assert_eq!(desktop.info_queries(), 1);
+        let moved = desktop.window_frame(id).unwrap();
+        assert!(engine.handle_click(title_bar_of(moved)));
+        assert_eq!(desktop.info_queries(), 1);
+    }
+
+    #[test]
+    fn test_title_rule_overrides_cached_briefly() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        desktop.set_window_info(
+            id,
+            WindowInfo {
+                title: Some("Player - Video".to_string()),
+                ..Default::default()
+            },
+        );
+        let config = AppConfig {
+            rules: vec![AppRule {
+                title: Some("Video".to_string()),
+                hop: Some(false),
+                ..Default::default()
+            }],
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        // 按标题匹配的规则也会缓存，连续点击不重复查询窗口属性
+        assert!(engine.hit_window(title_bar_of(frame)).is_none());
+        assert!(engine.hit_window(title_bar_of(frame)).is_none());
+        assert_eq!(desktop.info_queries(), 1);
+    }
+
+    #[test]
+    fn test_rule_forces_target_monitor_and_placement() {
+        use screenhop_core::monitor::PlacementStrategy;
+
+        let desktop = FakeDesktop::new(vec![
+            make_monitor(1, 0.0, 0.0, 1920.0, 1080.0),
+            make_monitor(2, 1920.0, 0.0, 1920.0, 1080.0),
+            make_monitor(3, 3840.0, 0.0, 1920.0, 1080.0),
+        ]);
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        desktop.set_window_info(
+            id,
+            WindowInfo {
+                title: Some("Player - Video".to_string()),
+                ..Default::default()
+            },
+        );
+        let config = AppConfig {
+            rules: vec![AppRule {
+                title: Some("^Player".to_string()),
+                placement: Some(PlacementStrategy::Centered),
+                monitor: Some(3),
+                ..Default::default()
+            }],
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        assert!(engine.handle_click(title_bar_of(frame)));
+        let moved = desktop.window_frame(id).unwrap();
+        assert_eq!(moved, Rect::new(4400.0, 240.0, 800.0, 600.0));
+
+        // 已经在指定的显示器上：放行点击，而不是吞掉它
+        assert!(engine.hit_window(title_bar_of(moved)).is_none());
+        assert!(!engine.handle_click(title_bar_of(moved)));
+        assert_eq!(desktop.window_frame(id), Some(moved));
+        // 按标题匹配的规则在有效期内沿用缓存
+        assert_eq!(desktop.info_queries(), 1);
+    }
+
+    #[test]
+    fn test_ping_pong_restores_original_frame() {
+        let desktop = two_monitors();
+        let original = Rect::new(100.0, 100.0, 2200.0, 1200.0);
+        let id = desktop.add_window(original);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(title_bar_of(original)));
+        let shrunk = desktop.window_frame(id).unwrap();
+        assert!(shrunk.width <= 1920.0 && shrunk.height <= 1080.0);
+
+        assert!(engine.handle_click(title_bar_of(shrunk)));
+        assert_eq!(desktop.window_frame(id), Some(original));
+    }
+
+    #[test]
+    fn test_persisted_frames_keyed_by_app() {
+        let path = crate::testing::temp_path("screenhop_engine_frames");
+        let info = WindowInfo {
+            process_name: Some("editor".to_string()),
+            title: Some("notes.md".to_string()),
+            ..Default::default()
+        };
+        let desktop = two_monitors();
+        let original = Rect::new(100.0, 100.0, 2200.0, 1200.0);
+        let id = desktop.add_window(original);
+        desktop.set_window_info(id, info.clone());
+        let first = engine(&desktop, AppConfig::default()).with_frame_memory_file(path.clone());
+        assert!(first.handle_click(title_bar_of(original)));
+        let shrunk = desktop.window_frame(id).unwrap();
+
+        // 重启后窗口标识已经不同（旧标识可能被别的窗口复用），按应用和标题恢复
+        let desktop = two_monitors();
+        let reused = desktop.add_window(shrunk);
+        let reopened = desktop.add_window(shrunk);
+        assert_eq!(reused, id);
+        desktop.set_window_info(reopened, info);
+        let restarted = engine(&desktop, AppConfig::default()).with_frame_memory_file(path.clone());
+        let _ = std::fs::remove_file(&path);
+
+        assert!(restarted.handle_click(title_bar_of(shrunk)));
+        assert_eq!(desktop.window_frame(reopened), Some(original));
+        desktop.focus(reused);
+        assert!(restarted.hop_focused(HotkeyAction::Next));
+        assert_ne!(desktop.window_frame(reused), Some(original));
+    }
+
+    #[test]
+    fn test_ping_pong_without_memory_stays_shrunk() {
+        let desktop = two_monitors();
+        let original = Rect::new(100.0, 100.0, 2200.0, 900.0);
+        let id = desktop.add_window(original);
+        let config = AppConfig {
+            remember_frames: false,
+            ..AppConfig::default()
+        };
+        let engine = engine(&desktop, config);
+
+        assert!(engine.handle_click(title_bar_of(original)));
+        let shrunk = desktop.window_frame(id).unwrap();
+        assert!(engine.handle_click(title_bar_of(shrunk)));
+        assert_eq!(desktop.window_frame(id).unwrap().width, shrunk.width);
+    }
+
+    #[test]
+    fn test_maximized_window_stays_maximized() {
+        let desktop = two_monitors();
+        let id = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        desktop.maximize(id);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(Point { x: 1280.0, y: 10.0 }));
+
+        let window = desktop.window(id).unwrap();
+        assert!(window.maximized);
+        assert_eq!(window.frame, Rect::new(2560.0, 0.0, 1920.0, 1080.0));
+    }
+
+    #[test]
+    fn test_undo_and_redo_hop() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(!engine.undo_hop());
+        assert!(engine.handle_click(title_bar_of(frame)));
+        let moved = desktop.window_frame(id).unwrap();
+
+        assert!(engine.undo_hop());
+        assert_eq!(desktop.window_frame(id), Some(frame));
+        assert!(!engine.undo_hop());
+
+        assert!(engine.hop_focused(HotkeyAction::Redo));
+        assert_eq!(desktop.window_frame(id), Some(moved));
+        assert!(!engine.redo_hop());
+    }
+
+    #[test]
+    fn test_undo_restores_maximized_state() {
+        let desktop = two_monitors();
+        let id = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        desktop.maximize(id);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(Point { x: 1280.0, y: 10.0 }));
+        assert!(engine.undo_hop());
+
+        let window = desktop.window(id).unwrap();
+        assert!(window.maximized);
+        assert_eq!(window.frame, Rect::new(0.0, 0.0, 2560.0, 1440.0));
+    }
+
+    #[test]
+    fn test_undo_skips_closed_windows() {
+        let desktop = two_monitors();
+        let first = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let second = Rect::new(300.0, 800.0, 800.0, 600.0);
+        let kept = desktop.add_window(first);
+        let closed = desktop.add_window(second);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(title_bar_of(first)));
+        assert!(engine.handle_click(title_bar_of(second)));
+        desktop.remove_window(closed);
+
+        assert!(engine.undo_hop());
+        assert_eq!(desktop.window_frame(kept), Some(first));
+    }
+
+    #[test]
+    fn test_topology_is_cached_until_monitors_change() {
+        let desktop = two_monitors();
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let engine = engine(&desktop, AppConfig::default());
+
+        assert!(engine.handle_click(title_bar_of(frame)));
+        let moved = desktop.window_frame(id).unwrap();
+        assert!(engine.handle_click(title_bar_of(moved)));
+        assert_eq!(desktop.monitor_queries(), 1);
+
+        // 拔掉右侧显示器后不再移动
+        desktop.set_monitors(vec![make_monitor(1, 0.0, 0.0, 2560.0, 1440.0)]);
+        assert!(!engine.handle_click(title_bar_of(frame)));
+        assert_eq!(desktop.monitor_queries(), 2);
+        assert_eq!(engine.monitors().len(), 1);
+    }
+
+    #[test]
+    fn test_plan_hop_does_not_move_window() {
+        let desktop = two_monitors();
+        let frame = Rect::new(0.0, 0.0, 1280.0, 1440.0);
+        let id = desktop.add_window(frame);
+        let engine = engine(&desktop, AppConfig::default());
+
+        let handle = desktop.window_manager().get_window_at(title_bar_of(frame)).unwrap();
+        let plan = engine
+            .plan_hop(&handle, &frame, &RuleOverrides::default())
+            .unwrap();
+
+        assert_eq!((plan.from_index, plan.to_index), (0, 1));
+        assert_eq!(plan.frame, Rect::new(2560.0, 0.0, 960.0, 1080.0));
+        assert!(!plan.restored);
+        assert_eq!(desktop.window_frame(id), Some(frame));
+    }
+}
@@ -0,0 +1,339 @@
+use anyhow::Result;
+use screenhop_core::rules::WindowInfo;
+use screenhop_core::{monitor, MonitorInfo, Point, Rect};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{
+    ChangeListeners, HitTester, MonitorChangeCallback, MonitorManager, WindowHandle, WindowManager,
+};
+
+/// 默认标题栏高度
+const DEFAULT_TITLE_BAR_HEIGHT: f64 = 30.0;
+
+/// 假窗口
+#[derive(Debug, Clone)]
+pub struct FakeWindow {
+    pub id: u64,
+    pub frame: Rect,
+    pub maximized: bool,
+    /// 最大化前的 frame，还原时使用
+    pub restore_frame: Rect,
+    /// 交互式标签页区域
+    pub tabs: Vec<Rect>,
+    /// 进程名、窗口类等属性
+    pub info: WindowInfo,
+}
+
+#[derive(Debug, Default)]
+struct FakeState {
+    monitors: Vec<MonitorInfo>,
+    /// 按 Z 序排列，最后一个在最上层
+    windows: Vec<FakeWindow>,
+    active: Option<u64>,
+    next_id: u64,
+    title_bar_height: f64,
+    /// `get_monitors` 的调用次数
+    monitor_queries: usize,
+    /// `get_window_info` 的调用次数
+    info_queries: usize,
+    /// `interactive_tab_regions` 的调用次数
+    tab_queries: usize,
+}
+
+impl FakeState {
+    fn window_mut(&mut self, handle: &WindowHandle) -> Result<&mut FakeWindow> {
+        let id = handle.id();
+        self.windows
+            .iter_mut()
+            .find(|w| w.id == id)
+            .ok_or_else(|| anyhow::anyhow!("窗口不存在: {}", id))
+    }
+}
+
+/// 假桌面：多个假管理器共享同一份状态，测试中可随时检查窗口状态
+#[derive(Debug, Clone)]
+pub struct FakeDesktop {
+    state: Arc<Mutex<FakeState>>,
+    listeners: ChangeListeners,
+}
+
+impl FakeDesktop {
+    pub fn new(monitors: Vec<MonitorInfo>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FakeState {
+                monitors,
+                next_id: 1,
+                title_bar_height: DEFAULT_TITLE_BAR_HEIGHT,
+                ..Default::default()
+            })),
+            listeners: ChangeListeners::default(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap()
+    }
+
+    /// 添加一个窗口（置于最上层），返回窗口标识
+    pub fn add_window(&self, frame: Rect) -> u64 {
+        let mut state = self.state();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.windows.push(FakeWindow {
+            id,
+            frame,
+            maximized: false,
+            restore_frame: frame,
+            tabs: Vec::new(),
+            info: WindowInfo::default(),
+        });
+        id
+    }
+
+    /// 关闭窗口
+    pub fn remove_window(&self, id: u64) {
+        self.state().windows.retain(|w| w.id != id);
+    }
+
+    /// 在窗口上添加一个交互式标签页区域
+    pub fn add_tab(&self, id: u64, rect: Rect) {
+        if let Some(window) = self.state().windows.iter_mut().find(|w| w.id == id) {
+            window.tabs.push(rect);
+        }
+    }
+
+    /// 设置窗口的进程名、窗口类等属性
+    pub fn set_window_info(&self, id: u64, info: WindowInfo) {
+        if let Some(window) = self.state().windows.iter_mut().find(|w| w.id == id) {
+            window.info = info;
+        }
+    }
+
+    /// 最大化窗口（frame 铺满所在显示器的工作区）
+    pub fn maximize(&self, id: u64) {
+        let handle = WindowHandle::from_raw(id);
+        let _ = self.window_manager().maximize_window(&handle);
+    }
+
+    pub fn window(&self, id: u64) -> Option<FakeWindow> {
+        self.state().windows.iter().find(|w| w.id == id).cloned()
+    }
+
+    pub fn window_frame(&self, id: u64) -> Option<Rect> {
+        self.window(id).map(|w| w.frame)
+    }
+
+    pub fn active_window(&self) -> Option<u64> {
+        self.state().active
+    }
+
+    /// 激活窗口（置于最上层并获得焦点）
+    pub fn focus(&self, id: u64) {
+        let handle = WindowHandle::from_raw(id);
+        let _ = self.window_manager().activate_window(&handle);
+    }
+
+    /// 替换显示器并通知订阅者（模拟热插拔）
+    pub fn set_monitors(&self, monitors: Vec<MonitorInfo>) {
+        self.state().monitors = monitors;
+        self.listeners.notify();
+    }
+
+    /// 显示器被枚举的次数
+    pub fn monitor_queries(&self) -> usize {
+        self.state().monitor_queries
+    }
+
+    /// 窗口属性被查询的次数
+    pub fn info_queries(&self) -> usize {
+        self.state().info_queries
+    }
+
+    /// 标签页区域被查询的次数
+    pub fn tab_queries(&self) -> usize {
+        self.state().tab_queries
+    }
+
+    pub fn set_title_bar_height(&self, height: f64) {
+        self.state().title_bar_height = height;
+    }
+
+    pub fn window_manager(&self) -> FakeWindowManager {
+        FakeWindowManager {
+            desktop: self.clone(),
+        }
+    }
+
+    pub fn hit_tester(&self) -> FakeHitTester {
+        FakeHitTester {
+            desktop: self.clone(),
+        }
+    }
+
+    pub fn monitor_manager(&self) -> FakeMonitorManager {
+        FakeMonitorManager {
+            desktop: self.clone(),
+        }
+    }
+}
+
+/// 假窗口管理器
+#[derive(Debug, Clone)]
+pub struct FakeWindowManager {
+    desktop: FakeDesktop,
+}
+
+impl WindowManager for FakeWindowManager {
+    fn get_window_at(&self, point: Point) -> Option<WindowHandle> {
+        self.desktop
+            .state()
+            .windows
+            .iter()
+            .rev()
+            .find(|w| w.frame.contains(point))
+            .map(|w| WindowHandle::from_raw(w.id))
+    }
+
+    fn get_focused_window(&self) -> Option<WindowHandle> {
+        let state = self.desktop.state();
+        state
+            .active
+            .filter(|id| state.windows.iter().any(|w| w.id == *id))
+            .map(WindowHandle::from_raw)
+    }
+
+    fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect> {
+        self.desktop.window_frame(handle.id())
+    }
+
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo {
+        self.desktop.state().info_queries += 1;
+        self.desktop
+            .window(handle.id())
+            .map(|w| w.info)
+            .unwrap_or_default()
+    }
+
+    fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()> {
+        let mut state = self.desktop.state();
+        let window = state.window_mut(handle)?;
+        window.frame.x = pos.x;
+        window.frame.y = pos.y;
+        Ok(())
+    }
+
+    fn set_window_size(&self, handle: &WindowHandle, width: f64, height: f64) -> Result<()> {
+        let mut state = self.desktop.state();
+        let window = state.window_mut(handle)?;
+        window.frame.width = width;
+        window.frame.height = height;
+        Ok(())
+    }
+
+    fn activate_window(&self, handle: &WindowHandle) -> Result<()> {
+        let mut state = self.desktop.state();
+        let index = state
+            .windows
+            .iter()
+            .position(|w| w.id == handle.id())
+            .ok_or_else(|| anyhow::anyhow!("窗口不存在: {}", handle.id()))?;
+        let window = state.windows.remove(index);
+        state.active = Some(window.id);
+        state.windows.push(window);
+        Ok(())
+    }
+
+    fn is_maximized(&self, handle: &WindowHandle) -> bool {
+        self.desktop
+            .window(handle.id())
+            .is_some_and(|w| w.maximized)
+    }
+
+    fn restore_window(&self, handle: &WindowHandle) -> Result<()> {
+        let mut state = self.desktop.state();
+        let window = state.window_mut(handle)?;
+        if window.maximized {
+            window.maximized = false;
+            window.frame = window.restore_frame;
+        }
+        Ok(())
+    }
+
+    fn maximize_window(&self, handle: &WindowHandle) -> Result<()> {
+        let mut state = self.desktop.state();
+        let monitors = state.monitors.clone();
+        let window = state.window_mut(handle)?;
+        let idx = monitor::find_monitor_for_rect(&window.frame, &monitors)
+            .ok_or_else(|| anyhow::anyhow!("窗口不在任何显示器上"))?;
+
+        if !window.maximized {
+            window.restore_frame = window.frame;
+        }
+        window.maximized = true;
+        window.frame = monitors[idx].work_area;
+        Ok(())
+    }
+}
+
+/// 假命中检测器：窗口顶部 `title_bar_height` 为标题栏
+#[derive(Debug, Clone)]
+pub struct FakeHitTester {
+    desktop: FakeDesktop,
+}
+
+impl HitTester for FakeHitTester {
+    fn is_title_bar_hit(&self, handle: &WindowHandle, point: Point) -> bool {
+        let height = self.desktop.state().title_bar_height;
+        self.is_title_bar_hit_with_height(handle, point, height)
+    }
+
+    fn is_title_bar_hit_with_height(
+        &self,
+        handle: &WindowHandle,
+        point: Point,
+        height: f64,
+    ) -> bool {
+        self.desktop
+            .window_frame(handle.id())
+            .is_some_and(|frame| monitor::is_in_title_bar(point, &frame, height))
+    }
+
+    fn interactive_tab_regions(&self, handle: &WindowHandle) -> Vec<Rect> {
+        let mut state = self.desktop.state();
+        state.tab_queries += 1;
+        let id = handle.id();
+        state
+            .windows
+            .iter()
+            .find(|w| w.id == id)
+            .map(|w| w.tabs.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// 假显示器管理器
+#[derive(Debug, Clone)]
+pub struct FakeMonitorManager {
+    desktop: FakeDesktop,
+}
+
+impl MonitorManager for FakeMonitorManager {
+    fn get_monitors(&self) -> Vec<MonitorInfo> {
+        let mut state = self.desktop.state();
+        state.monitor_queries += 1;
+        let mut monitors = state.monitors.clone();
+        monitor::sort_monitors(&mut monitors);
+        monitors
+    }
+
+    fn get_monitor_for_window(&self, handle: &WindowHandle) -> Option<MonitorInfo> {
+        let frame = self.desktop.window_frame(handle.id())?;
+        let monitors = self.get_monitors();
+        monitor::find_monitor_for_rect(&frame, &monitors).map(|idx| monitors[idx].clone())
+    }
+
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        self.desktop.listeners.add(callback);
+        true
+    }
+}
@@ -0,0 +1,53 @@
+use screenhop_core::{Point, Rect};
+use std::sync::Arc;
+
+use super::window::HyprlandWindowManager;
+use super::HyprlandClient;
+use crate::{HitTester, WindowHandle, WindowManager};
+
+/// Hyprland 命中检测器
+///
+/// Hyprland 默认不绘制标题栏（hyprbars 等插件或客户端装饰除外），
+/// IPC 也不提供装饰高度，因此使用配置的标题栏高度。
+pub struct HyprlandHitTester {
+    client: Arc<HyprlandClient>,
+    title_bar_height: f64,
+}
+
+impl HyprlandHitTester {
+    pub fn new(client: Arc<HyprlandClient>) -> Self {
+        Self {
+            client,
+            title_bar_height: 40.0,
+        }
+    }
+
+    pub fn set_title_bar_height(&mut self, height: f64) {
+        self.title_bar_height = height;
+    }
+}
+
+impl HitTester for HyprlandHitTester {
+    fn is_title_bar_hit(&self, handle: &WindowHandle, point: Point) -> bool {
+        self.is_title_bar_hit_with_height(handle, point, self.title_bar_height)
+    }
+
+    fn is_title_bar_hit_with_height(
+        &self,
+        handle: &WindowHandle,
+        point: Point,
+        height: f64,
+    ) -> bool {
+        let wm = HyprlandWindowManager::new(self.client.clone());
+
+        if let Some(frame) = wm.get_window_frame(handle) {
+            screenhop_core::monitor::is_in_title_bar(point, &frame, height)
+        } else {
+            false
+        }
+    }
+
+    fn interactive_tab_regions(&self, _handle: &WindowHandle) -> Vec<Rect> {
+        Vec::new()
+    }
+}
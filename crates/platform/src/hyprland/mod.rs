@@ -0,0 +1,402 @@
+pub mod hittest;
+pub mod monitor;
+pub mod window;
+
+use anyhow::{bail, Context, Result};
+use screenhop_core::Point;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::wayland::CursorLocator;
+
+/// 请求套接字（hyprctl 使用的那个）
+const REQUEST_SOCKET: &str = ".socket.sock";
+/// 事件套接字（每行一个 `事件名>>数据`）
+const EVENT_SOCKET: &str = ".socket2.sock";
+
+/// 解析一行事件，返回（事件名, 数据）
+pub(crate) fn parse_event(line: &str) -> Option<(&str, &str)> {
+    line.split_once(">>")
+}
+
+/// 窗口地址（如 "0x55d4c4e0a8f0"）转为句柄中保存的数值
+pub(crate) fn parse_address(address: &str) -> Option<u64> {
+    u64::from_str_radix(address.trim_start_matches("0x"), 16).ok()
+}
+
+/// 句柄数值转为 dispatcher 使用的窗口选择器
+pub(crate) fn address_selector(address: u64) -> String {
+    format!("address:0x{:x}", address)
+}
+
+/// Hyprland 中的工作区引用
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct WorkspaceRef {
+    pub id: i64,
+}
+
+/// `j/monitors` 的单项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Monitor {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub make: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub serial: String,
+    /// 物理分辨率
+    pub width: i32,
+    pub height: i32,
+    /// 逻辑坐标
+    pub x: i32,
+    pub y: i32,
+    pub scale: f64,
+    #[serde(default)]
+    pub transform: i32,
+    /// 保留区域（left, top, right, bottom），逻辑像素
+    #[serde(default)]
+    pub reserved: [i32; 4],
+    pub active_workspace: WorkspaceRef,
+    pub special_workspace: Option<WorkspaceRef>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// `j/clients` 的单项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Client {
+    pub address: String,
+    #[serde(default = "default_true")]
+    pub mapped: bool,
+    #[serde(default)]
+    pub hidden: bool,
+    /// 逻辑坐标
+    pub at: [i32; 2],
+    pub size: [i32; 2],
+    pub workspace: WorkspaceRef,
+    pub floating: bool,
+    /// 0 = 普通，1 = 最大化，2 = 全屏（旧版本为布尔值）
+    #[serde(default, deserialize_with = "deserialize_fullscreen")]
+    pub fullscreen: u8,
+    /// 0 表示最近获得焦点
+    #[serde(rename = "focusHistoryID", default)]
+    pub focus_history_id: i64,
+    #[serde(default)]
+    pub class: String,
+    #[serde(default)]
+    pub title: String,
+    /// 进程 id，未知时为 -1
+    #[serde(default)]
+    pub pid: i64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn deserialize_fullscreen<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Fullscreen {
+        Mode(u8),
+        Flag(bool),
+    }
+
+    Ok(match Fullscreen::deserialize(deserializer)? {
+        Fullscreen::Mode(mode) => mode,
+        Fullscreen::Flag(flag) => u8::from(flag) * 2,
+    })
+}
+
+impl Client {
+    pub(crate) fn id(&self) -> Option<u64> {
+        parse_address(&self.address)
+    }
+
+    pub(crate) fn frame(&self) -> screenhop_core::Rect {
+        screenhop_core::Rect::new(
+            self.at[0] as f64,
+            self.at[1] as f64,
+            self.size[0] as f64,
+            self.size[1] as f64,
+        )
+    }
+}
+
+/// Hyprland IPC 客户端
+///
+/// 每次请求使用一条新连接：写入命令后读到对端关闭为止。
+pub struct HyprlandClient {
+    socket_dir: PathBuf,
+}
+
+impl HyprlandClient {
+    /// `socket_dir` 为包含 `.socket.sock` / `.socket2.sock` 的目录
+    pub fn new(socket_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_dir: socket_dir.into(),
+        }
+    }
+
+    /// 根据 `$HYPRLAND_INSTANCE_SIGNATURE` 定位套接字目录
+    ///
+    /// 新版本位于 `$XDG_RUNTIME_DIR/hypr/<sig>`，旧版本位于 `/tmp/hypr/<sig>`。
+    pub fn from_env() -> Result<Self> {
+        let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE")
+            .context("未设置 HYPRLAND_INSTANCE_SIGNATURE，当前不是 Hyprland 会话")?;
+
+        let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")
+            .map(|dir| Path::new(&dir).join("hypr").join(&signature))
+            .filter(|dir| dir.join(REQUEST_SOCKET).exists());
+        let socket_dir = runtime_dir.unwrap_or_else(|| Path::new("/tmp/hypr").join(&signature));
+
+        Ok(Self::new(socket_dir))
+    }
+
+    pub(crate) fn event_socket_path(&self) -> PathBuf {
+        self.socket_dir.join(EVENT_SOCKET)
+    }
+
+    fn request(&self, command: &str) -> Result<String> {
+        let path = self.socket_dir.join(REQUEST_SOCKET);
+        let mut stream = UnixStream::connect(&path)
+            .with_context(|| format!("无法连接 Hyprland 套接字: {}", path.display()))?;
+
+        stream.write_all(command.as_bytes())?;
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply)?;
+        Ok(reply)
+    }
+
+    fn request_json<T: DeserializeOwned>(&self, command: &str) -> Result<T> {
+        let reply = self.request(command)?;
+        serde_json::from_str(&reply).with_context(|| format!("无法解析 `{}` 的回复", command))
+    }
+
+    pub(crate) fn get_monitors(&self) -> Result<Vec<Monitor>> {
+        self.request_json("j/monitors")
+    }
+
+    pub(crate) fn get_clients(&self) -> Result<Vec<Client>> {
+        self.request_json("j/clients")
+    }
+
+    /// 依次执行多个 dispatcher，任意一个失败即返回错误
+    pub(crate) fn dispatch(&self, dispatches: &[String]) -> Result<()> {
+        let command = match dispatches {
+            [single] => format!("dispatch {}", single),
+            _ => format!(
+                "[[BATCH]]{}",
+                dispatches
+                    .iter()
+                    .map(|d| format!("dispatch {}", d))
+                    .collect::<Vec<_>>()
+                    .join(";")
+            ),
+        };
+
+        let reply = self.request(&command)?;
+        // 批量执行时各条回复以空行分隔
+        if let Some(error) = reply
+            .split("\n\n")
+            .map(str::trim)
+            .find(|r| !r.is_empty() && *r != "ok")
+        {
+            bail!("dispatcher 执行失败 `{}`: {}", command, error);
+        }
+        Ok(())
+    }
+}
+
+/// `j/cursorpos` 的回复
+#[derive(Debug, Deserialize)]
+struct CursorPos {
+    x: f64,
+    y: f64,
+}
+
+impl CursorLocator for HyprlandClient {
+    fn cursor_position(&self) -> Option<Point> {
+        match self.request_json::<CursorPos>("j/cursorpos") {
+            Ok(pos) => Some(Point { x: pos.x, y: pos.y }),
+            Err(e) => {
+                log::error!("获取光标位置失败: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Hyprland 平台实现集合
+pub struct HyprlandPlatform {
+    pub window_manager: window::HyprlandWindowManager,
+    pub hit_tester: hittest::HyprlandHitTester,
+    pub monitor_manager: monitor::HyprlandMonitorManager,
+}
+
+impl HyprlandPlatform {
+    pub fn new() -> Result<Self> {
+        let client = Arc::new(HyprlandClient::from_env()?);
+        let monitor_manager = monitor::HyprlandMonitorManager::new(client.clone());
+        if let Err(e) = monitor_manager.watch_hotplug() {
+            log::warn!("无法监听 Hyprland 事件，每次都会重新获取显示器: {}", e);
+        }
+
+        Ok(Self {
+            window_manager: window::HyprlandWindowManager::new(client.clone()),
+            hit_tester: hittest::HyprlandHitTester::new(client),
+            monitor_manager,
+        })
+    }
+}
+
+/// 回放录制好的回复的假 Hyprland 套接字
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::Mutex;
+
+    pub(crate) const MONITORS: &str = include_str!("testdata/monitors.json");
+    pub(crate) const CLIENTS: &str = include_str!("testdata/clients.json");
+
+    pub(crate) struct MockHyprland {
+        dir: PathBuf,
+        commands: Arc<Mutex<Vec<String>>>,
+        event_listener: UnixListener,
+    }
+
+    impl MockHyprland {
+        /// 启动请求套接字；dispatch 总是回复 ok 并记录命令
+        pub(crate) fn start() -> Self {
+            let dir = crate::testing::temp_path("screenhop_hypr");
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let commands = Arc::new(Mutex::new(Vec::new()));
+            let listener = UnixListener::bind(dir.join(REQUEST_SOCKET)).unwrap();
+            let recorded = commands.clone();
+            std::thread::spawn(move || {
+                for mut stream in listener.incoming().map_while(|s| s.ok()) {
+                    let mut buf = [0u8; 4096];
+                    let len = stream.read(&mut buf).unwrap();
+                    let command = String::from_utf8_lossy(&buf[..len]).to_string();
+
+                    let reply = match command.as_str() {
+                        "j/monitors" => MONITORS.to_string(),
+                        "j/clients" => CLIENTS.to_string(),
+                        "j/cursorpos" => r#"{"x": 1600, "y": 20}"#.to_string(),
+                        _ => {
+                            let count = command.matches("dispatch ").count();
+                            recorded.lock().unwrap().push(command);
+                            vec!["ok"; count].join("\n\n")
+                        }
+                    };
+                    let _ = stream.write_all(reply.as_bytes());
+                }
+            });
+
+            let event_listener = UnixListener::bind(dir.join(EVENT_SOCKET)).unwrap();
+
+            Self {
+                dir,
+                commands,
+                event_listener,
+            }
+        }
+
+        pub(crate) fn client(&self) -> Arc<HyprlandClient> {
+            Arc::new(HyprlandClient::new(&self.dir))
+        }
+
+        pub(crate) fn commands(&self) -> Vec<String> {
+            self.commands.lock().unwrap().clone()
+        }
+
+        /// 等待事件监听方连接
+        pub(crate) fn accept_event_client(&self) -> UnixStream {
+            self.event_listener.accept().unwrap().0
+        }
+    }
+
+    impl Drop for MockHyprland {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::MockHyprland;
+    use super::*;
+
+    #[test]
+    fn test_parse_event() {
+        assert_eq!(
+            parse_event("monitoraddedv2>>1,DP-2,Dell Inc. DELL U2720Q"),
+            Some(("monitoraddedv2", "1,DP-2,Dell Inc. DELL U2720Q"))
+        );
+        assert_eq!(parse_event("garbage"), None);
+    }
+
+    #[test]
+    fn test_address_roundtrip() {
+        let id = parse_address("0x55d4c4e0a8f0").unwrap();
+        assert_eq!(address_selector(id), "address:0x55d4c4e0a8f0");
+    }
+
+    #[test]
+    fn test_client_parses_recorded_replies() {
+        let mock = MockHyprland::start();
+        let client = mock.client();
+
+        assert_eq!(client.get_monitors().unwrap().len(), 2);
+        let clients = client.get_clients().unwrap();
+        assert_eq!(clients.len(), 4);
+        assert_eq!(clients[3].fullscreen, 1);
+    }
+
+    #[test]
+    fn test_cursor_position() {
+        let mock = MockHyprland::start();
+        assert_eq!(
+            mock.client().cursor_position(),
+            Some(Point { x: 1600.0, y: 20.0 })
+        );
+    }
+
+    #[test]
+    fn test_legacy_fullscreen_flag() {
+        let client: Client = serde_json::from_str(
+            r#"{"address": "0x1", "at": [0, 0], "size": [10, 10],
+                "workspace": {"id": 1}, "floating": false, "fullscreen": true}"#,
+        )
+        .unwrap();
+        assert_eq!(client.fullscreen, 2);
+    }
+
+    #[test]
+    fn test_dispatch_batch() {
+        let mock = MockHyprland::start();
+        let client = mock.client();
+
+        client
+            .dispatch(&[
+                "focuswindow address:0x1".into(),
+                "movewindow mon:DP-2".into(),
+            ])
+            .unwrap();
+        assert_eq!(
+            mock.commands(),
+            vec!["[[BATCH]]dispatch focuswindow address:0x1;dispatch movewindow mon:DP-2"]
+        );
+    }
+}
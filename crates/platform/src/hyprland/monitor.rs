@@ -0,0 +1,230 @@
+use anyhow::{Context, Result};
+use screenhop_core::{monitor, MonitorInfo, Rect};
+use std::io::{BufRead, BufReader};
+use std::os::unix::net::UnixStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::window::HyprlandWindowManager;
+use super::{parse_event, HyprlandClient, Monitor};
+use crate::{ChangeListeners, MonitorChangeCallback, MonitorManager, WindowHandle, WindowManager};
+
+/// 会改变显示器布局或工作区域的事件
+///
+/// 配置重载可能改变分辨率、缩放和排列；bar、dock 等 layer surface
+/// 的出现和消失会改变显示器的保留区域。
+const MONITOR_EVENTS: &[&str] = &[
+    "monitoradded",
+    "monitoraddedv2",
+    "monitorremoved",
+    "monitorremovedv2",
+    "configreloaded",
+    "openlayer",
+    "closelayer",
+];
+
+/// 显示器的逻辑范围
+///
+/// Hyprland 报告的宽高是物理分辨率，需要除以缩放比例；旋转 90° / 270° 时宽高互换。
+pub(crate) fn logical_bounds(monitor: &Monitor) -> Rect {
+    let (width, height) = if monitor.transform % 2 == 1 {
+        (monitor.height, monitor.width)
+    } else {
+        (monitor.width, monitor.height)
+    };
+
+    Rect::new(
+        monitor.x as f64,
+        monitor.y as f64,
+        (width as f64 / monitor.scale).round(),
+        (height as f64 / monitor.scale).round(),
+    )
+}
+
+/// 把 Hyprland 显示器转换为显示器信息（工作区域 = 逻辑范围扣除 `reserved`）
+pub(crate) fn to_monitor_info(monitor: &Monitor) -> MonitorInfo {
+    let bounds = logical_bounds(monitor);
+    let [left, top, right, bottom] = monitor.reserved.map(|v| v as f64);
+    let known = |value: &str| (!value.is_empty()).then(|| value.to_string());
+
+    MonitorInfo {
+        id: monitor.id as u64,
+        bounds,
+        work_area: Rect::new(
+            bounds.x + left,
+            bounds.y + top,
+            (bounds.width - left - right).max(0.0),
+            (bounds.height - top - bottom).max(0.0),
+        ),
+        name: known(&monitor.model).unwrap_or_else(|| monitor.name.clone()),
+        manufacturer: known(&monitor.make),
+        product_code: None,
+        serial: known(&monitor.serial),
+        connector: Some(monitor.name.clone()),
+        is_primary: false,
+        // 窗口与显示器坐标都是逻辑坐标
+        scale_factor: 1.0,
+    }
+}
+
+/// Hyprland 显示器管理器
+///
+/// 监听事件套接字期间缓存显示器列表，收到热插拔事件时失效并通知订阅者。
+pub struct HyprlandMonitorManager {
+    client: Arc<HyprlandClient>,
+    cache: Arc<Mutex<Option<Vec<MonitorInfo>>>>,
+    watching: Arc<AtomicBool>,
+    listeners: ChangeListeners,
+}
+
+impl HyprlandMonitorManager {
+    pub fn new(client: Arc<HyprlandClient>) -> Self {
+        Self {
+            client,
+            cache: Arc::new(Mutex::new(None)),
+            watching: Arc::new(AtomicBool::new(false)),
+            listeners: ChangeListeners::default(),
+        }
+    }
+
+    /// 连接事件套接字，在后台线程中监听显示器热插拔
+    pub fn watch_hotplug(&self) -> Result<()> {
+        let path = self.client.event_socket_path();
+        let stream = UnixStream::connect(&path)
+            .with_context(|| format!("无法连接 Hyprland 事件套接字: {}", path.display()))?;
+
+        let cache = self.cache.clone();
+        let watching = self.watching.clone();
+        let listeners = self.listeners.clone();
+        watching.store(true, Ordering::SeqCst);
+
+        std::thread::Builder::new()
+            .name("hyprland-events".to_string())
+            .spawn(move || {
+                for line in BufReader::new(stream).lines().map_while(|l| l.ok()) {
+                    let Some((event, data)) = parse_event(&line) else {
+                        continue;
+                    };
+                    if MONITOR_EVENTS.contains(&event) {
+                        log::info!("显示器布局变化: {} {}", event, data);
+                        *cache.lock().unwrap() = None;
+                        listeners.notify();
+                    }
+                }
+
+                log::warn!("Hyprland 事件套接字已断开");
+                watching.store(false, Ordering::SeqCst);
+                *cache.lock().unwrap() = None;
+                listeners.notify();
+            })
+            .context("无法创建 Hyprland 事件线程")?;
+
+        Ok(())
+    }
+
+    fn fetch_monitors(&self) -> Vec<MonitorInfo> {
+        let monitors = match self.client.get_monitors() {
+            Ok(monitors) => monitors,
+            Err(e) => {
+                log::error!("获取 Hyprland 显示器失败: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut monitors: Vec<MonitorInfo> = monitors
+            .iter()
+            .filter(|m| !m.disabled)
+            .map(to_monitor_info)
+            .collect();
+        monitor::sort_monitors(&mut monitors);
+        monitors
+    }
+}
+
+impl MonitorManager for HyprlandMonitorManager {
+    fn get_monitors(&self) -> Vec<MonitorInfo> {
+        if !self.watching.load(Ordering::SeqCst) {
+            return self.fetch_monitors();
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(monitors) = cache.as_ref() {
+            return monitors.clone();
+        }
+
+        let monitors = self.fetch_monitors();
+        if !monitors.is_empty() {
+            *cache = Some(monitors.clone());
+        }
+        monitors
+    }
+
+    fn get_monitor_for_window(&self, handle: &WindowHandle) -> Option<MonitorInfo> {
+        let wm = HyprlandWindowManager::new(self.client.clone());
+        let frame = wm.get_window_frame(handle)?;
+        let monitors = self.get_monitors();
+
+        monitor::find_monitor_for_rect(&frame, &monitors).map(|idx| monitors[idx].clone())
+    }
+
+    /// 需要先调用 `watch_hotplug`（`HyprlandPlatform::new` 会自动调用）
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        self.listeners.add(callback);
+        self.watching.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::MockHyprland;
+    use super::*;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_get_monitors() {
+        let mock = MockHyprland::start();
+        let manager = HyprlandMonitorManager::new(mock.client());
+        let monitors = manager.get_monitors();
+
+        assert_eq!(monitors.len(), 2);
+        // 2880x1800 @ 2x，顶部 bar 30px
+        assert_eq!(monitors[0].bounds, Rect::new(0.0, 0.0, 1440.0, 900.0));
+        assert_eq!(monitors[0].work_area, Rect::new(0.0, 30.0, 1440.0, 870.0));
+        assert_eq!(monitors[0].serial, None);
+        // 3840x2160 @ 1.5x，竖屏
+        assert_eq!(monitors[1].bounds, Rect::new(1440.0, 0.0, 1440.0, 2560.0));
+        assert_eq!(monitors[1].connector.as_deref(), Some("DP-2"));
+    }
+
+    #[test]
+    fn test_hotplug_event_invalidates_cache() {
+        let mock = MockHyprland::start();
+        let manager = HyprlandMonitorManager::new(mock.client());
+        manager.watch_hotplug().unwrap();
+        let mut events = mock.accept_event_client();
+        let (tx, rx) = std::sync::mpsc::channel();
+        assert!(manager.subscribe_changes(Box::new(move || {
+            let _ = tx.send(());
+        })));
+
+        manager.get_monitors();
+        assert!(manager.cache.lock().unwrap().is_some());
+
+        events
+            .write_all(b"workspace>>2\nmonitorremoved>>DP-2\n")
+            .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while manager.cache.lock().unwrap().is_some() {
+            assert!(Instant::now() < deadline, "缓存未失效");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        rx.recv_timeout(Duration::from_secs(2)).unwrap();
+
+        // bar 启动会改变保留区域，同样清空缓存
+        manager.get_monitors();
+        events.write_all(b"openlayer>>waybar\n").unwrap();
+        rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert!(manager.cache.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use anyhow::{Context, Result};
+use screenhop_core::rules::WindowInfo;
+use screenhop_core::{Point, Rect};
+use std::sync::Arc;
+
+use super::monitor::logical_bounds;
+use super::{address_selector, Client, HyprlandClient};
+use crate::{WindowHandle, WindowManager};
+
+/// Hyprland 窗口管理器（通过 dispatcher 操作窗口）
+///
+/// 平铺窗口只能整体送到另一块显示器，尺寸由布局决定；浮动窗口可以精确设置位置和尺寸。
+/// “最大化”对应 Hyprland 的 `fullscreen 1`（保留间隙与 bar 的最大化模式）。
+pub struct HyprlandWindowManager {
+    client: Arc<HyprlandClient>,
+}
+
+/// 可见窗口的层叠顺序：全屏在最上，其次是浮动窗口，同类按最近焦点排序
+pub(crate) fn stacking_order(clients: &mut [Client]) {
+    clients.sort_by_key(|c| (c.fullscreen == 0, !c.floating, c.focus_history_id));
+}
+
+impl HyprlandWindowManager {
+    pub fn new(client: Arc<HyprlandClient>) -> Self {
+        Self { client }
+    }
+
+    fn find_client(&self, handle: &WindowHandle) -> Option<Client> {
+        match self.client.get_clients() {
+            Ok(clients) => clients.into_iter().find(|c| c.id() == Some(handle.inner)),
+            Err(e) => {
+                log::error!("获取 Hyprland 窗口列表失败: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 对指定窗口执行需要焦点的 dispatcher（先聚焦再执行）
+    fn dispatch_focused(&self, handle: &WindowHandle, dispatch: String) -> Result<()> {
+        self.client.dispatch(&[
+            format!("focuswindow {}", address_selector(handle.inner)),
+            dispatch,
+        ])
+    }
+
+    /// 包含指定点的显示器名称
+    fn monitor_at(&self, point: Point) -> Result<String> {
+        self.client
+            .get_monitors()?
+            .into_iter()
+            .find(|m| !m.disabled && logical_bounds(m).contains(point))
+            .map(|m| m.name)
+            .with_context(|| format!("({}, {}) 不在任何显示器上", point.x, point.y))
+    }
+}
+
+impl WindowManager for HyprlandWindowManager {
+    fn get_window_at(&self, point: Point) -> Option<WindowHandle> {
+        let monitors = self.client.get_monitors().ok()?;
+        let visible: Vec<i64> = monitors
+            .iter()
+            .flat_map(|m| {
+                let special = m.special_workspace.as_ref().map(|ws| ws.id);
+                [Some(m.active_workspace.id), special.filter(|id| *id != 0)]
+            })
+            .flatten()
+            .collect();
+
+        let mut clients: Vec<Client> = self
+            .client
+            .get_clients()
+            .ok()?
+            .into_iter()
+            .filter(|c| c.mapped && !c.hidden && visible.contains(&c.workspace.id))
+            .collect();
+        stacking_order(&mut clients);
+
+        clients
+            .iter()
+            .find(|c| c.frame().contains(point))
+            .and_then(|c| c.id())
+            .map(|inner| WindowHandle { inner })
+    }
+
+    fn get_focused_window(&self) -> Option<WindowHandle> {
+        let clients = match self.client.get_clients() {
+            Ok(clients) => clients,
+            Err(e) => {
+                log::error!("获取 Hyprland 窗口列表失败: {}", e);
+                return None;
+            }
+        };
+
+        clients
+            .into_iter()
+            .find(|c| c.mapped && !c.hidden && c.focus_history_id == 0)
+            .and_then(|c| c.id())
+            .map(|inner| WindowHandle { inner })
+    }
+
+    fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect> {
+        self.find_client(handle).map(|c| c.frame())
+    }
+
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo {
+        let Some(client) = self.find_client(handle) else {
+            return WindowInfo::default();
+        };
+
+        WindowInfo {
+            process_name: u32::try_from(client.pid)
+                .ok()
+                .and_then(crate::linux::process_name),
+            bundle_id: None,
+            class: (!client.class.is_empty()).then_some(client.class),
+            title: (!client.title.is_empty()).then_some(client.title),
+        }
+    }
+
+    fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()> {
+        let client = self.find_client(handle).context("窗口已不存在")?;
+
+        if client.floating {
+            self.client.dispatch(&[format!(
+                "movewindowpixel exact {} {},{}",
+                pos.x.round(),
+                pos.y.round(),
+                address_selector(handle.inner)
+            )])
+        } else {
+            let monitor = self.monitor_at(pos)?;
+            self.dispatch_focused(handle, format!("movewindow mon:{}", monitor))
+        }
+    }
+
+    fn set_window_size(&self, handle: &WindowHandle, width: f64, height: f64) -> Result<()> {
+        let client = self.find_client(handle).context("窗口已不存在")?;
+
+        if !client.floating {
+            // 平铺窗口的尺寸由布局决定
+            return Ok(());
+        }
+
+        self.client.dispatch(&[format!(
+            "resizewindowpixel exact {} {},{}",
+            width.round(),
+            height.round(),
+            address_selector(handle.inner)
+        )])
+    }
+
+    fn activate_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.client
+            .dispatch(&[format!("focuswindow {}", address_selector(handle.inner))])
+    }
+
+    fn is_maximized(&self, handle: &WindowHandle) -> bool {
+        self.find_client(handle).is_some_and(|c| c.fullscreen == 1)
+    }
+
+    fn restore_window(&self, handle: &WindowHandle) -> Result<()> {
+        // `fullscreen 1` 是切换操作，调用方只在已最大化时还原
+        self.dispatch_focused(handle, "fullscreen 1".to_string())
+    }
+
+    fn maximize_window(&self, handle: &WindowHandle) -> Result<()> {
+        if self.is_maximized(handle) {
+            return Ok(());
+        }
+        self.dispatch_focused(handle, "fullscreen 1".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_address;
+    use super::super::testing::MockHyprland;
+    use super::*;
+
+    fn handle(address: &str) -> WindowHandle {
+        WindowHandle {
+            inner: parse_address(address).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_get_window_at() {
+        let mock = MockHyprland::start();
+        let wm = HyprlandWindowManager::new(mock.client());
+
+        // 浮动窗口盖在平铺窗口之上
+        let hit = wm.get_window_at(Point { x: 300.0, y: 300.0 }).unwrap();
+        assert_eq!(hit.id(), handle("0x55d4c4f1b200").id());
+        // 工作区 2 不可见
+        let hit = wm.get_window_at(Point { x: 800.0, y: 300.0 });
+        assert!(hit.is_none());
+        let hit = wm.get_window_at(Point { x: 100.0, y: 800.0 }).unwrap();
+        assert_eq!(hit.id(), handle("0x55d4c4e0a8f0").id());
+    }
+
+    #[test]
+    fn test_get_focused_window() {
+        let mock = MockHyprland::start();
+        let wm = HyprlandWindowManager::new(mock.client());
+
+        let focused = wm.get_focused_window().unwrap();
+        assert_eq!(focused.id(), handle("0x55d4c4f1b200").id());
+    }
+
+    #[test]
+    fn test_get_window_info() {
+        let mock = MockHyprland::start();
+        let wm = HyprlandWindowManager::new(mock.client());
+
+        let info = wm.get_window_info(&handle("0x55d4c4f1b200"));
+        assert_eq!(info.class.as_deref(), Some("pavucontrol"));
+        assert_eq!(info.title.as_deref(), Some("Volume Control"));
+        assert_eq!(info.bundle_id, None);
+    }
+
+    #[test]
+    fn test_move_tiled_window_to_monitor() {
+        let mock = MockHyprland::start();
+        let wm = HyprlandWindowManager::new(mock.client());
+        let window = handle("0x55d4c4e0a8f0");
+
+        wm.set_window_position(&window, Point { x: 1500.0, y: 40.0 })
+            .unwrap();
+        wm.set_window_size(&window, 1440.0, 2560.0).unwrap();
+
+        assert_eq!(
+            mock.commands(),
+            vec![
+                "[[BATCH]]dispatch focuswindow address:0x55d4c4e0a8f0;\
+                 dispatch movewindow mon:DP-2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_move_floating_window() {
+        let mock = MockHyprland::start();
+        let wm = HyprlandWindowManager::new(mock.client());
+        let window = handle("0x55d4c4f1b200");
+
+        wm.set_window_position(
+            &window,
+            Point {
+                x: 1910.0,
+                y: 1080.4,
+            },
+        )
+        .unwrap();
+        wm.set_window_size(&window, 500.0, 400.0).unwrap();
+
+        assert_eq!(
+            mock.commands(),
+            vec![
+                "dispatch movewindowpixel exact 1910 1080,address:0x55d4c4f1b200",
+                "dispatch resizewindowpixel exact 500 400,address:0x55d4c4f1b200",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maximized_state() {
+        let mock = MockHyprland::start();
+        let wm = HyprlandWindowManager::new(mock.client());
+
+        assert!(wm.is_maximized(&handle("0x55d4c5123450")));
+        assert!(!wm.is_maximized(&handle("0x55d4c4e0a8f0")));
+
+        // 已最大化时不再切换
+        wm.maximize_window(&handle("0x55d4c5123450")).unwrap();
+        assert!(mock.commands().is_empty());
+    }
+}
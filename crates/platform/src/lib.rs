@@ -1,15 +1,23 @@
 #![allow(unexpected_cfgs)]
 
 use anyhow::Result;
+use screenhop_core::hotkey::KeyCombo;
+use screenhop_core::rules::WindowInfo;
+use screenhop_core::trigger::Modifiers;
 use screenhop_core::{MonitorInfo, Point, Rect};
+use std::sync::{Arc, Mutex};
 
 /// 鼠标事件
 #[derive(Debug, Clone)]
 pub struct MouseEvent {
     /// 鼠标点击位置
     pub point: Point,
-    /// 鼠标按键编号（2 = 中键）
+    /// 鼠标按键编号（2 = 中键，3 = 侧键 X1，4 = 侧键 X2，滚轮事件为 0）
     pub button: u32,
+    /// 滚轮滚动量：正数向上，负数向下，按键事件为 0（各平台单位不同，只使用符号）
+    pub wheel_delta: i32,
+    /// 点击时按下的修饰键
+    pub modifiers: Modifiers,
 }
 
 /// 窗口句柄（平台无关的包装）
@@ -19,6 +27,63 @@ pub struct WindowHandle {
     pub(crate) inner: macos::MacWindowHandle,
     #[cfg(target_os = "windows")]
     pub(crate) inner: windows::WinWindowHandle,
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    pub(crate) inner: u64,
+}
+
+impl WindowHandle {
+    /// 窗口标识（窗口存活期间保持不变，用于记忆窗口 frame）
+    pub fn id(&self) -> u64 {
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        return self.inner.id();
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        return self.inner;
+    }
+
+    /// 多次查询同一窗口都不变的标识，用于窗口 frame 记忆
+    ///
+    /// macOS 取不到 CGWindowID 时 [`Self::id`] 退化为 AXUIElement 指针，
+    /// 每次查询窗口都会得到新的元素，此时返回 `None`。
+    pub fn stable_id(&self) -> Option<u64> {
+        #[cfg(target_os = "macos")]
+        return (self.inner.window_id != 0).then_some(self.inner.window_id as u64);
+
+        #[cfg(not(target_os = "macos"))]
+        return Some(self.id());
+    }
+
+    /// 用原始标识构造句柄（仅供假平台使用，不能传给真实的平台 API）
+    #[cfg(any(test, feature = "fake"))]
+    pub(crate) fn from_raw(id: u64) -> Self {
+        #[cfg(target_os = "macos")]
+        return Self {
+            inner: macos::MacWindowHandle {
+                ax_element: std::ptr::null(),
+                pid: 0,
+                window_id: id as u32,
+            },
+        };
+
+        #[cfg(target_os = "windows")]
+        return Self {
+            inner: windows::WinWindowHandle { hwnd: id as isize },
+        };
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        return Self { inner: id };
+    }
+}
+
+/// 鼠标钩子的运行状况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HookHealth {
+    /// 钩子是否已安装
+    pub active: bool,
+    /// 钩子当前是否被系统禁用（尚未恢复）
+    pub disabled: bool,
+    /// 安装以来被系统禁用的次数（macOS 回调超时等）
+    pub disable_count: u64,
 }
 
 /// 鼠标钩子 trait
@@ -33,6 +98,30 @@ pub trait MouseHook {
 
     /// 钩子是否处于活跃状态
     fn is_active(&self) -> bool;
+
+    /// 钩子的运行状况（默认只反映是否活跃）
+    fn health(&self) -> HookHealth {
+        HookHealth {
+            active: self.is_active(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 全局快捷键 trait
+pub trait KeyboardHook {
+    /// 注册快捷键，按下 `hotkeys[i]` 时以 `i` 调用回调
+    ///
+    /// 被其他程序占用的组合键会记录警告并跳过，不影响其余快捷键。
+    fn start<F>(&mut self, hotkeys: &[KeyCombo], callback: F) -> Result<()>
+    where
+        F: Fn(usize) + Send + 'static;
+
+    /// 注销所有快捷键
+    fn stop(&mut self) -> Result<()>;
+
+    /// 快捷键是否处于注册状态
+    fn is_active(&self) -> bool;
 }
 
 /// 窗口管理 trait
@@ -40,9 +129,15 @@ pub trait WindowManager {
     /// 获取指定位置的窗口
     fn get_window_at(&self, point: Point) -> Option<WindowHandle>;
 
+    /// 获取当前拥有键盘焦点的窗口
+    fn get_focused_window(&self) -> Option<WindowHandle>;
+
     /// 获取窗口的 frame（位置 + 尺寸）
     fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect>;
 
+    /// 获取窗口的进程名、bundle id、窗口类和标题（用于匹配应用规则）
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo;
+
     /// 设置窗口位置
     fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()>;
 
@@ -67,17 +162,79 @@ pub trait HitTester {
     /// 判断点击位置是否在窗口标题栏上
     fn is_title_bar_hit(&self, handle: &WindowHandle, point: Point) -> bool;
 
-    /// 判断点击位置是否在交互式标签页上（如浏览器标签、资源管理器标签）
-    fn is_interactive_tab(&self, handle: &WindowHandle, point: Point) -> bool;
+    /// 按指定的标题栏高度判断（应用规则覆盖了高度时使用）
+    ///
+    /// - macOS、Hyprland：`height` 替换配置的标题栏高度
+    /// - X11：`height` 替换配置的标题栏高度（客户端装饰和没有装饰信息的窗口），
+    ///   窗口管理器绘制的标题栏仍以 `_NET_FRAME_EXTENTS` 为准
+    /// - Windows：WM_NCHITTEST 判定为标题栏的位置之外，窗口顶部 `height` 内的点击也算标题栏，
+    ///   因此只能扩大标题栏区域，不能缩小
+    /// - i3：标题栏位置来自布局树，忽略 `height`
+    fn is_title_bar_hit_with_height(
+        &self,
+        handle: &WindowHandle,
+        point: Point,
+        height: f64,
+    ) -> bool {
+        let _ = height;
+        self.is_title_bar_hit(handle, point)
+    }
+
+    /// 窗口上交互式标签页（如浏览器标签、资源管理器标签）所占的屏幕区域
+    ///
+    /// 查询可能很慢（macOS 需要遍历 AX 树），引擎按窗口缓存结果，
+    /// 钩子回调中只和缓存的区域比较。
+    fn interactive_tab_regions(&self, handle: &WindowHandle) -> Vec<Rect>;
 }
 
+/// 显示器布局变化的回调（可能在任意线程触发）
+pub type MonitorChangeCallback = Box<dyn Fn() + Send + Sync>;
+
 /// 显示器管理 trait
 pub trait MonitorManager {
-    /// 获取所有显示器信息
+    /// 获取所有显示器信息（按 `monitor::sort_monitors` 的规范顺序排列）
     fn get_monitors(&self) -> Vec<MonitorInfo>;
 
     /// 获取窗口所在的显示器
     fn get_monitor_for_window(&self, handle: &WindowHandle) -> Option<MonitorInfo>;
+
+    /// 订阅显示器布局变化（热插拔、分辨率、排列或工作区域变化）
+    ///
+    /// 返回 false 表示当前无法收到变化通知，调用方需要自行定期刷新。
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        let _ = callback;
+        false
+    }
+}
+
+/// 显示器变化回调列表，各平台的 `subscribe_changes` 共用
+#[derive(Clone, Default)]
+pub(crate) struct ChangeListeners {
+    callbacks: Arc<Mutex<Vec<MonitorChangeCallback>>>,
+}
+
+impl ChangeListeners {
+    /// 添加回调，返回是否为第一个回调（此时需要开始监听系统通知）
+    pub(crate) fn add(&self, callback: MonitorChangeCallback) -> bool {
+        let mut callbacks = self.callbacks.lock().unwrap();
+        callbacks.push(callback);
+        callbacks.len() == 1
+    }
+
+    /// 通知所有订阅者
+    pub(crate) fn notify(&self) {
+        for callback in self.callbacks.lock().unwrap().iter() {
+            callback();
+        }
+    }
+}
+
+impl std::fmt::Debug for ChangeListeners {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeListeners")
+            .field("count", &self.callbacks.lock().unwrap().len())
+            .finish()
+    }
 }
 
 /// 开机自启动 trait
@@ -98,6 +255,20 @@ pub trait PermissionChecker {
     fn request_permissions(&self) -> Result<()>;
 }
 
+// 跳转引擎
+pub mod engine;
+
+// 异步跳转工作线程
+pub mod worker;
+
+// 内存中的假平台实现（测试用）
+#[cfg(any(test, feature = "fake"))]
+pub mod fake;
+
+// 测试共用的辅助函数
+#[cfg(test)]
+pub(crate) mod testing;
+
 // 平台实现模块
 #[cfg(target_os = "macos")]
 pub mod macos;
@@ -105,6 +276,21 @@ pub mod macos;
 #[cfg(target_os = "windows")]
 pub mod windows;
 
+#[cfg(target_os = "linux")]
+pub mod hyprland;
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+#[cfg(target_os = "linux")]
+pub mod sway;
+
+#[cfg(target_os = "linux")]
+pub mod wayland;
+
+#[cfg(target_os = "linux")]
+pub mod x11;
+
 // 平台工厂函数
 #[cfg(target_os = "macos")]
 pub fn create_platform() -> macos::MacPlatform {
@@ -115,3 +301,21 @@ pub fn create_platform() -> macos::MacPlatform {
 pub fn create_platform() -> windows::WinPlatform {
     windows::WinPlatform::new()
 }
+
+/// Linux 需要先连接 X server，连接失败时返回错误
+#[cfg(target_os = "linux")]
+pub fn create_platform() -> Result<x11::X11Platform> {
+    x11::X11Platform::new()
+}
+
+/// i3 会话使用 X11 钩子 + i3 IPC（不支持 sway，sway 会话返回错误）
+#[cfg(target_os = "linux")]
+pub fn create_i3_platform() -> Result<sway::SwayPlatform> {
+    sway::SwayPlatform::new()
+}
+
+/// Wayland 会话使用 evdev 钩子 + 合成器 IPC（目前仅支持 Hyprland）
+#[cfg(target_os = "linux")]
+pub fn create_wayland_platform(forward_wheel: bool) -> Result<wayland::WaylandPlatform> {
+    wayland::WaylandPlatform::new(forward_wheel)
+}
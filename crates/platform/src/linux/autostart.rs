@@ -0,0 +1,425 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::AutoStart;
+
+const DESKTOP_FILE: &str = "screenhop.desktop";
+const SERVICE_NAME: &str = "screenhop.service";
+/// systemd 用户服务挂载的目标
+const SERVICE_TARGET: &str = "graphical-session.target";
+
+/// Linux 自启动管理器
+///
+/// 默认写入 XDG autostart 条目 `~/.config/autostart/screenhop.desktop`；
+/// 启用 systemd 模式时改为安装 `systemd --user` 服务，异常退出后自动重启。
+pub struct LinuxAutoStart {
+    /// 用户配置目录，无法确定时（没有 `$HOME`）为 None，启用自启动会返回错误
+    config_dir: Option<PathBuf>,
+    use_systemd: bool,
+    /// 修改服务后是否调用 `systemctl --user daemon-reload`
+    reload_systemd: bool,
+}
+
+impl Default for LinuxAutoStart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 按桌面条目规范引用 Exec 中的参数
+///
+/// 规范先对字符串值做转义、再解析参数引号，所以加引号后的反斜杠需要再写一次
+/// （字面的 `$` 写作 `\\$`）；`%` 是字段代码的前缀，字面的 `%` 写作 `%%`。
+pub(crate) fn quote_desktop_exec(arg: &str) -> String {
+    const RESERVED: &[char] = &[
+        ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(',
+        ')', '`',
+    ];
+
+    let quoted = if arg.contains(RESERVED) {
+        let mut quoted = String::from('"');
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    } else {
+        arg.to_string()
+    };
+
+    quoted.replace('\\', "\\\\").replace('%', "%%")
+}
+
+/// 按 systemd 单元文件规范引用 ExecStart 中的参数
+///
+/// 字面的 `%`（说明符前缀）写作 `%%`，`$`（环境变量展开）写作 `$$`；
+/// 含空白的参数加双引号，引号内只需转义 `"` 和 `\`。
+pub(crate) fn quote_systemd_exec(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%").replace('$', "$$");
+    if !escaped.contains([' ', '\t', '\n', '"', '\'', '\\']) {
+        return escaped;
+    }
+
+    let mut quoted = String::from('"');
+    for c in escaped.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// 桌面条目是否处于启用状态
+///
+/// 只看 `[Desktop Entry]` 组：`Hidden=true` 表示用户删除了该条目，
+/// `X-GNOME-Autostart-enabled=false` 是 GNOME 设置中关闭开关的写法。
+pub(crate) fn desktop_entry_enabled(contents: &str) -> bool {
+    let mut in_main_group = false;
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match (key.trim(), value.trim()) {
+            ("Hidden", "true") => return false,
+            ("X-GNOME-Autostart-enabled", "false") => return false,
+            _ => {}
+        }
+    }
+
+    true
+}
+
+fn desktop_entry(exe: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=ScreenHop\n\
+         Comment=鼠标中键在多显示器之间移动窗口\n\
+         Exec={}\n\
+         Icon=screenhop\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n",
+        quote_desktop_exec(&exe.to_string_lossy())
+    )
+}
+
+fn service_unit(exe: &Path) -> String {
+    format!(
+        "[Unit]\n\
+         Description=ScreenHop\n\
+         PartOf={target}\n\
+         After={target}\n\
+         \n\
+         [Service]\n\
+         ExecStart={exe}\n\
+         Restart=on-failure\n\
+         RestartSec=3\n\
+         \n\
+         [Install]\n\
+         WantedBy={target}\n",
+        target = SERVICE_TARGET,
+        exe = quote_systemd_exec(&exe.to_string_lossy())
+    )
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("无法删除 {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl LinuxAutoStart {
+    pub fn new() -> Self {
+        Self {
+            config_dir: dirs::config_dir(),
+            use_systemd: false,
+            reload_systemd: true,
+        }
+    }
+
+    /// 使用指定的配置目录（测试用，不会调用 systemctl）
+    pub fn with_config_dir(config_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config_dir: Some(config_dir.into()),
+            use_systemd: false,
+            reload_systemd: false,
+        }
+    }
+
+    /// 是否使用 systemd 用户服务代替 XDG autostart
+    pub fn with_systemd(mut self, use_systemd: bool) -> Self {
+        self.use_systemd = use_systemd;
+        self
+    }
+
+    fn config_dir(&self) -> Result<&Path> {
+        self.config_dir
+            .as_deref()
+            .context("无法确定用户配置目录（未设置 HOME）")
+    }
+
+    fn desktop_path(&self) -> Result<PathBuf> {
+        Ok(self.config_dir()?.join("autostart").join(DESKTOP_FILE))
+    }
+
+    fn unit_dir(&self) -> Result<PathBuf> {
+        Ok(self.config_dir()?.join("systemd").join("user"))
+    }
+
+    fn unit_path(&self) -> Result<PathBuf> {
+        Ok(self.unit_dir()?.join(SERVICE_NAME))
+    }
+
+    /// `systemctl --user enable` 创建的链接
+    fn wants_link(&self) -> Result<PathBuf> {
+        Ok(self
+            .unit_dir()?
+            .join(format!("{}.wants", SERVICE_TARGET))
+            .join(SERVICE_NAME))
+    }
+
+    fn desktop_enabled(&self) -> bool {
+        self.desktop_path()
+            .and_then(|path| Ok(fs::read_to_string(path)?))
+            .is_ok_and(|contents| desktop_entry_enabled(&contents))
+    }
+
+    fn systemd_enabled(&self) -> bool {
+        match (self.unit_path(), self.wants_link()) {
+            (Ok(unit), Ok(link)) => unit.exists() && link.exists(),
+            _ => false,
+        }
+    }
+
+    fn install_desktop_entry(&self, exe: &Path) -> Result<()> {
+        let path = self.desktop_path()?;
+        fs::create_dir_all(path.parent().unwrap()).context("无法创建 autostart 目录")?;
+        fs::write(&path, desktop_entry(exe))
+            .with_context(|| format!("无法写入 {}", path.display()))?;
+
+        log::info!("已创建 XDG 自启动条目: {}", path.display());
+        Ok(())
+    }
+
+    fn install_service(&self, exe: &Path) -> Result<()> {
+        let unit = self.unit_path()?;
+        let link = self.wants_link()?;
+        fs::create_dir_all(link.parent().unwrap()).context("无法创建 systemd 用户目录")?;
+        fs::write(&unit, service_unit(exe))
+            .with_context(|| format!("无法写入 {}", unit.display()))?;
+
+        // 与 `systemctl --user enable` 等效，不依赖 systemd 正在运行
+        remove_if_exists(&link)?;
+        std::os::unix::fs::symlink(&unit, &link)
+            .with_context(|| format!("无法创建 {}", link.display()))?;
+
+        self.daemon_reload();
+        log::info!("已安装 systemd 用户服务: {}", unit.display());
+        Ok(())
+    }
+
+    fn uninstall_service(&self) -> Result<()> {
+        let unit = self.unit_path()?;
+        let link = self.wants_link()?;
+        if !unit.exists() && !link.exists() {
+            return Ok(());
+        }
+
+        remove_if_exists(&link)?;
+        remove_if_exists(&unit)?;
+        self.daemon_reload();
+        log::info!("已移除 systemd 用户服务");
+        Ok(())
+    }
+
+    fn daemon_reload(&self) {
+        if !self.reload_systemd {
+            return;
+        }
+
+        match Command::new("systemctl")
+            .args(["--user", "daemon-reload"])
+            .output()
+        {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => log::warn!(
+                "systemctl daemon-reload 失败: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            Err(e) => log::warn!("无法执行 systemctl: {}", e),
+        }
+    }
+}
+
+impl AutoStart for LinuxAutoStart {
+    fn is_enabled(&self) -> bool {
+        self.desktop_enabled() || self.systemd_enabled()
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<()> {
+        if !enabled {
+            remove_if_exists(&self.desktop_path()?)?;
+            self.uninstall_service()?;
+            log::info!("已关闭开机自启动");
+            return Ok(());
+        }
+
+        let exe = std::env::current_exe().context("获取当前程序路径失败")?;
+
+        // 两种方式只保留一种，避免启动两个实例
+        if self.use_systemd {
+            remove_if_exists(&self.desktop_path()?)?;
+            self.install_service(&exe)
+        } else {
+            self.uninstall_service()?;
+            self.install_desktop_entry(&exe)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn test_desktop_entry_roundtrip() {
+        let dir = temp_path("screenhop_autostart");
+        let auto = LinuxAutoStart::with_config_dir(&dir);
+        assert!(!auto.is_enabled());
+
+        auto.set_enabled(true).unwrap();
+        assert!(auto.is_enabled());
+        let contents = fs::read_to_string(dir.join("autostart/screenhop.desktop")).unwrap();
+        assert!(contents.contains("Exec="));
+
+        auto.set_enabled(false).unwrap();
+        assert!(!auto.is_enabled());
+        assert!(!dir.join("autostart/screenhop.desktop").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_config_dir() {
+        let auto = LinuxAutoStart {
+            config_dir: None,
+            use_systemd: false,
+            reload_systemd: false,
+        };
+        assert!(!auto.is_enabled());
+        assert!(auto.set_enabled(true).is_err());
+    }
+
+    #[test]
+    fn test_hidden_entry_is_disabled() {
+        let dir = temp_path("screenhop_autostart");
+        let auto = LinuxAutoStart::with_config_dir(&dir);
+        auto.set_enabled(true).unwrap();
+
+        // 用户手动加上 Hidden=true
+        let path = dir.join("autostart/screenhop.desktop");
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(&path, format!("{}Hidden=true\n", contents)).unwrap();
+        assert!(!auto.is_enabled());
+
+        // 重新启用会覆盖用户的修改
+        auto.set_enabled(true).unwrap();
+        assert!(auto.is_enabled());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_desktop_entry_enabled() {
+        assert!(desktop_entry_enabled("[Desktop Entry]\nHidden=false\n"));
+        assert!(!desktop_entry_enabled("[Desktop Entry]\nHidden = true\n"));
+        assert!(!desktop_entry_enabled(
+            "[Desktop Entry]\nX-GNOME-Autostart-enabled=false\n"
+        ));
+        // 其他组里的同名键不算
+        assert!(desktop_entry_enabled(
+            "[Desktop Entry]\nName=ScreenHop\n[Desktop Action quit]\nHidden=true\n"
+        ));
+    }
+
+    #[test]
+    fn test_systemd_service() {
+        let dir = temp_path("screenhop_autostart");
+        let desktop = LinuxAutoStart::with_config_dir(&dir);
+        desktop.set_enabled(true).unwrap();
+
+        let auto = LinuxAutoStart::with_config_dir(&dir).with_systemd(true);
+        auto.set_enabled(true).unwrap();
+        assert!(auto.is_enabled());
+        // 切换到 systemd 后移除 XDG 条目
+        assert!(!dir.join("autostart/screenhop.desktop").exists());
+
+        let unit = fs::read_to_string(dir.join("systemd/user/screenhop.service")).unwrap();
+        assert!(unit.contains("Restart=on-failure"));
+        assert!(dir
+            .join("systemd/user/graphical-session.target.wants/screenhop.service")
+            .exists());
+
+        auto.set_enabled(false).unwrap();
+        assert!(!auto.is_enabled());
+        assert!(!dir.join("systemd/user/screenhop.service").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_quote_desktop_exec() {
+        assert_eq!(
+            quote_desktop_exec("/usr/bin/screenhop"),
+            "/usr/bin/screenhop"
+        );
+        assert_eq!(
+            quote_desktop_exec("/home/a b/screen$hop"),
+            r#""/home/a b/screen\\$hop""#
+        );
+        assert_eq!(
+            quote_desktop_exec(r"/home/a b/x\y"),
+            r#""/home/a b/x\\\\y""#
+        );
+        assert_eq!(
+            quote_desktop_exec("/opt/100%/screenhop"),
+            "/opt/100%%/screenhop"
+        );
+    }
+
+    #[test]
+    fn test_quote_systemd_exec() {
+        assert_eq!(
+            quote_systemd_exec("/usr/bin/screenhop"),
+            "/usr/bin/screenhop"
+        );
+        assert_eq!(
+            quote_systemd_exec("/home/a b/screen$hop"),
+            r#""/home/a b/screen$$hop""#
+        );
+        assert_eq!(
+            quote_systemd_exec("/opt/100%/screenhop"),
+            "/opt/100%%/screenhop"
+        );
+    }
+}
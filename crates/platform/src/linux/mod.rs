@@ -0,0 +1,8 @@
+pub mod autostart;
+
+/// 按 pid 读取进程名（`/proc/<pid>/comm`），进程不存在时返回 `None`
+pub(crate) fn process_name(pid: u32) -> Option<String> {
+    let comm = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    let name = comm.trim_end();
+    (!name.is_empty()).then(|| name.to_string())
+}
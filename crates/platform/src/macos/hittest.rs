@@ -1,7 +1,10 @@
-use screenhop_core::Point;
+use screenhop_core::{Point, Rect};
 
 use crate::{HitTester, WindowHandle};
 
+/// 向下查找标签组的最大 AX 层级（标签栏位于窗口的前几层）
+const TAB_SEARCH_DEPTH: usize = 4;
+
 /// macOS 命中检测器（基于 AXUIElement role 检测）
 pub struct MacHitTester {
     title_bar_height: f64,
@@ -49,8 +52,12 @@ impl MacHitTester {
         }
     }
 
-    /// 获取父元素
-    fn get_parent(&self, element: *const std::ffi::c_void) -> Option<*const std::ffi::c_void> {
+    /// 读取 AX 元素的属性值，返回的引用由调用方释放
+    fn copy_attribute(
+        &self,
+        element: *const std::ffi::c_void,
+        name: &str,
+    ) -> Option<*const std::ffi::c_void> {
         unsafe {
             extern "C" {
                 fn AXUIElementCopyAttributeValue(
@@ -62,85 +69,122 @@ impl MacHitTester {
 
             use core_foundation::base::TCFType;
 
-            let parent_attr = core_foundation::string::CFString::new("AXParent");
-            let mut parent_ref: *const std::ffi::c_void = std::ptr::null();
+            let attr = core_foundation::string::CFString::new(name);
+            let mut value: *const std::ffi::c_void = std::ptr::null();
 
-            let result = AXUIElementCopyAttributeValue(
-                element,
-                parent_attr.as_concrete_TypeRef() as _,
-                &mut parent_ref,
-            );
+            let result =
+                AXUIElementCopyAttributeValue(element, attr.as_concrete_TypeRef() as _, &mut value);
 
-            if result == 0 && !parent_ref.is_null() {
-                Some(parent_ref)
+            if result == 0 && !value.is_null() {
+                Some(value)
             } else {
                 None
             }
         }
     }
 
-    /// 检查元素是否是交互式标签元素（等同 Swift 版的 isInteractiveTabElement）
-    fn check_interactive_tab(&self, start_element: *const std::ffi::c_void) -> bool {
-        let mut current = start_element;
-        let mut depth = 0;
-        let max_depth = 10;
-
-        loop {
-            if depth >= max_depth {
-                break;
+    /// 获取 AX 元素的屏幕区域
+    fn get_element_frame(&self, element: *const std::ffi::c_void) -> Option<Rect> {
+        unsafe {
+            extern "C" {
+                fn AXValueGetValue(
+                    value: *const std::ffi::c_void,
+                    value_type: u32,
+                    value_ptr: *mut std::ffi::c_void,
+                ) -> bool;
+                fn CFRelease(cf: *const std::ffi::c_void);
             }
 
-            let role = match self.get_element_role(current) {
-                Some(r) => r,
-                None => break,
+            let pos_ref = self.copy_attribute(element, "AXPosition")?;
+            let Some(size_ref) = self.copy_attribute(element, "AXSize") else {
+                CFRelease(pos_ref);
+                return None;
             };
 
-            log::debug!("Depth: {} | Role: {}", depth, role);
+            // AXValueType: kAXValueCGPointType = 1, kAXValueCGSizeType = 2
+            let mut point = core_graphics::geometry::CGPoint::new(0.0, 0.0);
+            let mut size = core_graphics::geometry::CGSize::new(0.0, 0.0);
+
+            AXValueGetValue(pos_ref, 1, &mut point as *mut _ as *mut _);
+            AXValueGetValue(size_ref, 2, &mut size as *mut _ as *mut _);
+            CFRelease(pos_ref);
+            CFRelease(size_ref);
+
+            Some(Rect::new(point.x, point.y, size.width, size.height))
+        }
+    }
 
-            // 在 AXTabGroup 内使用深度判定
-            if role == "AXTabGroup" {
-                return depth != 1; // depth==1 → TabGroup 空白区, 允许移动
+    /// 在 `element` 的子树中查找 AXTabGroup，收集其中每个标签的区域
+    ///
+    /// 标签组的空白处不算标签页（可以移动窗口）；AXToolbar 内的元素也允许移动，不再向下查找。
+    fn collect_tab_regions(
+        &self,
+        element: *const std::ffi::c_void,
+        depth: usize,
+        regions: &mut Vec<Rect>,
+    ) {
+        unsafe {
+            extern "C" {
+                fn CFArrayGetCount(array: *const std::ffi::c_void) -> isize;
+                fn CFArrayGetValueAtIndex(
+                    array: *const std::ffi::c_void,
+                    index: isize,
+                ) -> *const std::ffi::c_void;
+                fn CFRelease(cf: *const std::ffi::c_void);
             }
 
-            // 在 AXToolbar 内 → 允许移动
-            if role == "AXToolbar" {
-                return false;
+            let role = self.get_element_role(element);
+            log::debug!("Depth: {} | Role: {:?}", depth, role);
+
+            let is_tab_group = match role.as_deref() {
+                Some("AXTabGroup") => true,
+                Some("AXToolbar") => return,
+                _ => false,
+            };
+            if !is_tab_group && depth >= TAB_SEARCH_DEPTH {
+                return;
             }
 
-            // 向上查找
-            match self.get_parent(current) {
-                Some(parent) => {
-                    current = parent;
-                    depth += 1;
+            let Some(children) = self.copy_attribute(element, "AXChildren") else {
+                return;
+            };
+            for index in 0..CFArrayGetCount(children) {
+                let child = CFArrayGetValueAtIndex(children, index);
+                if is_tab_group {
+                    regions.extend(self.get_element_frame(child));
+                } else {
+                    self.collect_tab_regions(child, depth + 1, regions);
                 }
-                None => break,
             }
+            CFRelease(children);
         }
-
-        // 没有找到 AXTabGroup 祖先，说明不在 Tab 区域内，允许移动
-        false
     }
 }
 
 impl HitTester for MacHitTester {
     fn is_title_bar_hit(&self, handle: &WindowHandle, point: Point) -> bool {
+        self.is_title_bar_hit_with_height(handle, point, self.title_bar_height)
+    }
+
+    fn is_title_bar_hit_with_height(
+        &self,
+        handle: &WindowHandle,
+        point: Point,
+        height: f64,
+    ) -> bool {
         use crate::WindowManager;
         let wm = super::window::MacWindowManager::new();
 
         if let Some(frame) = wm.get_window_frame(handle) {
-            screenhop_core::monitor::is_in_title_bar(point, &frame, self.title_bar_height)
+            screenhop_core::monitor::is_in_title_bar(point, &frame, height)
         } else {
             false
         }
     }
 
-    fn is_interactive_tab(&self, _handle: &WindowHandle, point: Point) -> bool {
-        // 获取点击位置的 UI 元素
-        let wm = super::window::MacWindowManager::new();
-        if let Some(element) = wm.get_element_at_position(point) {
-            self.check_interactive_tab(element)
-        } else {
-            false
-        }
+    fn interactive_tab_regions(&self, handle: &WindowHandle) -> Vec<Rect> {
+        let mut regions = Vec::new();
+        self.collect_tab_regions(handle.inner.ax_element, 0, &mut regions);
+        regions
     }
 }
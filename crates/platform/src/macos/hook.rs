@@ -1,83 +1,186 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::ffi::c_void;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicPtr, AtomicU64, Ordering},
+    mpsc::{self, RecvTimeoutError},
     Arc,
 };
+use std::thread::JoinHandle;
+use std::time::Duration;
 
-use crate::MouseEvent;
+use crate::{HookHealth, MouseEvent, MouseHook};
+use screenhop_core::trigger::Modifiers;
 use screenhop_core::Point;
 
+// CGEventFlags 中的修饰键掩码
+const FLAG_MASK_SHIFT: u64 = 0x0002_0000;
+const FLAG_MASK_CONTROL: u64 = 0x0004_0000;
+const FLAG_MASK_ALTERNATE: u64 = 0x0008_0000;
+const FLAG_MASK_COMMAND: u64 = 0x0010_0000;
+
+/// 从 CGEventFlags 读取修饰键
+pub(super) fn modifiers_from_flags(flags: u64) -> Modifiers {
+    Modifiers {
+        ctrl: flags & FLAG_MASK_CONTROL != 0,
+        alt: flags & FLAG_MASK_ALTERNATE != 0,
+        shift: flags & FLAG_MASK_SHIFT != 0,
+        cmd: flags & FLAG_MASK_COMMAND != 0,
+    }
+}
+
 /// macOS 鼠标钩子实现（基于 CGEventTap）
 ///
-/// 使用 CGEventTapCreate 创建一个拦截 otherMouseDown 事件的 tap，
+/// 使用 CGEventTapCreate 创建一个拦截 otherMouseDown 和 scrollWheel 事件的 tap，
 /// 绑定到当前线程的 CFRunLoop 中运行。
+/// `start` / `stop` 必须在同一个运行 RunLoop 的线程（主线程）调用。
+///
+/// 系统在回调超时等情况下会禁用 tap：回调收到禁用通知后立即重新启用，
+/// 另有看门狗线程定期检查 `CGEventTapIsEnabled`，防止通知丢失时 tap 一直失效。
 pub struct MacMouseHook {
     active: Arc<AtomicBool>,
+    tap: Option<InstalledTap>,
+}
+
+/// 已安装到 RunLoop 的 tap
+struct InstalledTap {
+    /// CFMachPortRef
+    port: *const c_void,
+    /// CFRunLoopSourceRef
+    source: *const c_void,
+    /// CFRunLoopRef
+    run_loop: *const c_void,
+    watchdog: Watchdog,
+}
+
+/// 看门狗检查间隔
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 定期检查 tap 是否被禁用的线程
+struct Watchdog {
+    /// 丢弃后看门狗线程退出
+    stop: mpsc::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl Watchdog {
+    fn spawn(port: *const c_void) -> Result<Self> {
+        // 原生指针不能跨线程传递，按地址传入；tap 在看门狗退出之后才会释放
+        let port = port as usize;
+        let (stop, stop_rx) = mpsc::channel::<()>();
+
+        let handle = std::thread::Builder::new()
+            .name("event-tap-watchdog".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(WATCHDOG_INTERVAL) {
+                    let port = port as *const c_void;
+                    unsafe {
+                        // 禁用次数由 tap 回调统计，这里只负责兜底重新启用，避免同一次禁用计两次
+                        if !CGEventTapIsEnabled(port) {
+                            log::debug!("看门狗发现 CGEventTap 已被禁用，正在重新启用");
+                            CGEventTapEnable(port, true);
+                        }
+                    }
+                }
+            })
+            .context("无法创建 CGEventTap 看门狗线程")?;
+
+        Ok(Self { stop, handle })
+    }
+
+    fn join(self) {
+        drop(self.stop);
+        if self.handle.join().is_err() {
+            log::error!("CGEventTap 看门狗线程异常退出");
+        }
+    }
 }
 
 // 全局回调存储，因为 CGEventTap 的 C 回调不支持闭包捕获
 static mut GLOBAL_CALLBACK: Option<Box<dyn Fn(MouseEvent) -> bool + Send>> = None;
 
+// 当前 tap 的 CFMachPort，供 C 回调在收到禁用通知时重新启用
+static TAP_PORT: AtomicPtr<c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+// 本次安装以来 tap 被系统禁用的次数（只在 tap 回调收到禁用通知时累加）
+static DISABLE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+// CGEventTap 类型定义
+pub(super) type CGEventTapCallBack = unsafe extern "C" fn(
+    proxy: *const c_void,
+    event_type: u32,
+    event: *const c_void,
+    user_info: *mut c_void,
+) -> *const c_void;
+
+extern "C" {
+    pub(super) fn CGEventTapCreate(
+        tap: u32,                // CGEventTapLocation
+        place: u32,              // CGEventTapPlacement
+        options: u32,            // CGEventTapOptions
+        events_of_interest: u64, // CGEventMask
+        callback: CGEventTapCallBack,
+        user_info: *mut c_void,
+    ) -> *const c_void; // CFMachPortRef
+
+    pub(super) fn CGEventTapEnable(tap: *const c_void, enable: bool);
+
+    pub(super) fn CGEventTapIsEnabled(tap: *const c_void) -> bool;
+
+    pub(super) fn CFMachPortCreateRunLoopSource(
+        allocator: *const c_void,
+        port: *const c_void,
+        order: i64,
+    ) -> *const c_void;
+
+    pub(super) fn CFMachPortInvalidate(port: *const c_void);
+
+    pub(super) fn CFRunLoopGetCurrent() -> *const c_void;
+
+    pub(super) fn CFRunLoopAddSource(rl: *const c_void, source: *const c_void, mode: *const c_void);
+
+    pub(super) fn CFRunLoopRemoveSource(
+        rl: *const c_void,
+        source: *const c_void,
+        mode: *const c_void,
+    );
+
+    pub(super) fn CFRelease(cf: *const c_void);
+
+    pub(super) static kCFRunLoopCommonModes: *const c_void;
+}
+
+impl Default for MacMouseHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MacMouseHook {
     pub fn new() -> Self {
         Self {
             active: Arc::new(AtomicBool::new(false)),
+            tap: None,
         }
     }
+}
 
+impl MouseHook for MacMouseHook {
     /// 安装 CGEventTap 到当前线程的 RunLoop
-    /// 必须在主线程调用（NSApp.run() 之前）
-    pub fn install_event_tap<F>(&mut self, callback: F) -> Result<()>
+    /// 必须在主线程调用（首次安装在 NSApp.run() 之前）
+    fn start<F>(&mut self, callback: F) -> Result<()>
     where
         F: Fn(MouseEvent) -> bool + Send + 'static,
     {
-        unsafe {
-            GLOBAL_CALLBACK = Some(Box::new(callback));
+        if self.tap.is_some() {
+            self.stop()?;
         }
 
-        self.active.store(true, Ordering::SeqCst);
-
         unsafe {
-            // CGEventTap 类型定义
-            type CGEventTapCallBack = unsafe extern "C" fn(
-                proxy: *const std::ffi::c_void,
-                event_type: u32,
-                event: *const std::ffi::c_void,
-                user_info: *mut std::ffi::c_void,
-            ) -> *const std::ffi::c_void;
-
-            extern "C" {
-                fn CGEventTapCreate(
-                    tap: u32,       // CGEventTapLocation
-                    place: u32,     // CGEventTapPlacement
-                    options: u32,   // CGEventTapOptions
-                    events_of_interest: u64, // CGEventMask
-                    callback: CGEventTapCallBack,
-                    user_info: *mut std::ffi::c_void,
-                ) -> *const std::ffi::c_void; // CFMachPortRef
-
-                fn CFMachPortCreateRunLoopSource(
-                    allocator: *const std::ffi::c_void,
-                    port: *const std::ffi::c_void,
-                    order: i64,
-                ) -> *const std::ffi::c_void;
-
-                fn CFRunLoopGetCurrent() -> *const std::ffi::c_void;
-
-                fn CFRunLoopAddSource(
-                    rl: *const std::ffi::c_void,
-                    source: *const std::ffi::c_void,
-                    mode: *const std::ffi::c_void,
-                );
-
-                fn CGEventTapEnable(tap: *const std::ffi::c_void, enable: bool);
-            }
+            // kCGEventOtherMouseDown = 25, kCGEventOtherMouseUp = 26, kCGEventScrollWheel = 22
+            // 事件掩码: (1 << 25) | (1 << 26) | (1 << 22)
+            let event_mask: u64 = (1 << 25) | (1 << 26) | (1 << 22);
 
-            // kCGEventOtherMouseDown = 25, kCGEventOtherMouseUp = 26
-            // 事件掩码: (1 << 25) | (1 << 26)
-            let event_mask: u64 = (1 << 25) | (1 << 26);
-
-            let tap = CGEventTapCreate(
+            let port = CGEventTapCreate(
                 0, // kCGHIDEventTap
                 0, // kCGHeadInsertEventTap
                 0, // kCGEventTapOptionDefault (active tap, can modify/consume)
@@ -86,67 +189,157 @@ impl MacMouseHook {
                 std::ptr::null_mut(),
             );
 
-            if tap.is_null() {
+            if port.is_null() {
                 anyhow::bail!("CGEventTapCreate 失败 — 请确认已授予辅助功能权限");
             }
 
             // 创建 RunLoop Source
-            let source = CFMachPortCreateRunLoopSource(std::ptr::null(), tap, 0);
+            let source = CFMachPortCreateRunLoopSource(std::ptr::null(), port, 0);
             if source.is_null() {
+                CFMachPortInvalidate(port);
+                CFRelease(port);
                 anyhow::bail!("CFMachPortCreateRunLoopSource 失败");
             }
 
+            GLOBAL_CALLBACK = Some(Box::new(callback));
+            TAP_PORT.store(port as *mut c_void, Ordering::SeqCst);
+            DISABLE_COUNT.store(0, Ordering::SeqCst);
+
             // 添加到当前 RunLoop
             let run_loop = CFRunLoopGetCurrent();
+            CFRunLoopAddSource(run_loop, source, kCFRunLoopCommonModes);
+            CGEventTapEnable(port, true);
 
-            // kCFRunLoopCommonModes
-            extern "C" {
-                static kCFRunLoopCommonModes: *const std::ffi::c_void;
-            }
+            let watchdog = match Watchdog::spawn(port) {
+                Ok(watchdog) => watchdog,
+                Err(e) => {
+                    release_tap(port, source, run_loop);
+                    return Err(e);
+                }
+            };
 
-            CFRunLoopAddSource(run_loop, source, kCFRunLoopCommonModes);
-            CGEventTapEnable(tap, true);
+            self.tap = Some(InstalledTap {
+                port,
+                source,
+                run_loop,
+                watchdog,
+            });
+        }
 
-            log::info!("CGEventTap 已安装到 RunLoop");
+        self.active.store(true, Ordering::SeqCst);
+        log::info!("CGEventTap 已安装到 RunLoop");
+        Ok(())
+    }
+
+    /// 从 RunLoop 移除并销毁 CGEventTap
+    fn stop(&mut self) -> Result<()> {
+        let Some(tap) = self.tap.take() else {
+            return Ok(());
+        };
+
+        // 先停止看门狗，之后才能释放它引用的 tap
+        tap.watchdog.join();
+        unsafe {
+            release_tap(tap.port, tap.source, tap.run_loop);
         }
 
+        self.active.store(false, Ordering::SeqCst);
+        log::info!("CGEventTap 已卸载");
         Ok(())
     }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    fn health(&self) -> HookHealth {
+        HookHealth {
+            active: self.is_active(),
+            disabled: self
+                .tap
+                .as_ref()
+                .is_some_and(|tap| unsafe { !CGEventTapIsEnabled(tap.port) }),
+            disable_count: DISABLE_COUNT.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// 从 RunLoop 移除 tap 并释放相关对象，同时清空全局回调
+unsafe fn release_tap(port: *const c_void, source: *const c_void, run_loop: *const c_void) {
+    TAP_PORT.store(std::ptr::null_mut(), Ordering::SeqCst);
+    CGEventTapEnable(port, false);
+    CFRunLoopRemoveSource(run_loop, source, kCFRunLoopCommonModes);
+    CFMachPortInvalidate(port);
+    CFRelease(source);
+    CFRelease(port);
+
+    // 回调只会在同一个 RunLoop 线程上执行，此时不会有回调正在运行
+    GLOBAL_CALLBACK = None;
+}
+
+impl Drop for MacMouseHook {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
 }
 
 /// CGEventTap 的 C 回调函数
 unsafe extern "C" fn event_tap_callback(
-    _proxy: *const std::ffi::c_void,
+    _proxy: *const c_void,
     event_type: u32,
-    event: *const std::ffi::c_void,
-    _user_info: *mut std::ffi::c_void,
-) -> *const std::ffi::c_void {
+    event: *const c_void,
+    _user_info: *mut c_void,
+) -> *const c_void {
     // 处理 tap 被系统禁用的情况（比如系统过于繁忙）
     // kCGEventTapDisabledByTimeout = 0xFFFFFFFE
     // kCGEventTapDisabledByUserInput = 0xFFFFFFFF
+    // 立即重新启用，否则 tap 会一直失效直到重启应用
     if event_type == 0xFFFFFFFE || event_type == 0xFFFFFFFF {
-        log::warn!("CGEventTap 被系统禁用, 类型={}", event_type);
-        return event;
-    }
-
-    // 只处理 otherMouseDown (25)
-    if event_type != 25 {
+        DISABLE_COUNT.fetch_add(1, Ordering::SeqCst);
+        let port = TAP_PORT.load(Ordering::SeqCst);
+        if !port.is_null() {
+            CGEventTapEnable(port, true);
+        }
+        log::warn!("CGEventTap 被系统禁用（类型={}），已重新启用", event_type);
         return event;
     }
 
-    // 从 CGEvent 读取按键编号和位置
+    // 从 CGEvent 读取按键编号、滚动量、位置和修饰键
     extern "C" {
-        fn CGEventGetIntegerValueField(event: *const std::ffi::c_void, field: u32) -> i64;
-        fn CGEventGetLocation(event: *const std::ffi::c_void) -> core_graphics::geometry::CGPoint;
+        fn CGEventGetIntegerValueField(event: *const c_void, field: u32) -> i64;
+        fn CGEventGetLocation(event: *const c_void) -> core_graphics::geometry::CGPoint;
+        fn CGEventGetFlags(event: *const c_void) -> u64;
     }
 
-    // kCGMouseEventButtonNumber = 3
-    let button_number = CGEventGetIntegerValueField(event, 3);
+    let (button, wheel_delta) = match event_type {
+        // otherMouseDown
+        25 => {
+            // kCGMouseEventButtonNumber = 3
+            let button_number = CGEventGetIntegerValueField(event, 3);
 
-    // 只处理中键 (button 2)
-    if button_number != 2 {
-        return event;
-    }
+            // 只处理中键 (2) 和侧键 (3 = 后退, 4 = 前进)
+            if !(2..=4).contains(&button_number) {
+                return event;
+            }
+            (button_number as u32, 0)
+        }
+        // scrollWheel
+        22 => {
+            // kCGScrollWheelEventDeltaAxis1 = 11（行数，正数向上）；
+            // 触控板的细微滚动行数为 0，改用 kCGScrollWheelEventPointDeltaAxis1 = 96
+            let lines = CGEventGetIntegerValueField(event, 11);
+            let delta = if lines != 0 {
+                lines
+            } else {
+                CGEventGetIntegerValueField(event, 96)
+            };
+            if delta == 0 {
+                return event;
+            }
+            (0, delta.signum() as i32)
+        }
+        _ => return event,
+    };
 
     let location = CGEventGetLocation(event);
 
@@ -155,7 +348,9 @@ unsafe extern "C" fn event_tap_callback(
             x: location.x,
             y: location.y,
         },
-        button: 2,
+        button,
+        wheel_delta,
+        modifiers: modifiers_from_flags(CGEventGetFlags(event)),
     };
 
     // 调用全局回调
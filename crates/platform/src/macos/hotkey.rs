@@ -0,0 +1,268 @@
+use anyhow::Result;
+use screenhop_core::hotkey::{Key, KeyCombo};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::sync::Arc;
+
+use super::hook::{
+    kCFRunLoopCommonModes, modifiers_from_flags, CFMachPortCreateRunLoopSource,
+    CFMachPortInvalidate, CFRelease, CFRunLoopAddSource, CFRunLoopGetCurrent,
+    CFRunLoopRemoveSource, CGEventTapCreate, CGEventTapEnable,
+};
+use crate::KeyboardHook;
+
+// kCGEventKeyDown
+const EVENT_KEY_DOWN: u32 = 10;
+
+// CGEventField：kCGKeyboardEventAutorepeat / kCGKeyboardEventKeycode
+const FIELD_AUTOREPEAT: u32 = 8;
+const FIELD_KEYCODE: u32 = 9;
+
+/// 主键对应的 kVK 虚拟键码（按 ANSI 键盘布局的物理位置）
+pub(crate) fn key_code(key: Key) -> u16 {
+    match key {
+        Key::Letter(c) => match c {
+            'A' => 0x00,
+            'S' => 0x01,
+            'D' => 0x02,
+            'F' => 0x03,
+            'H' => 0x04,
+            'G' => 0x05,
+            'Z' => 0x06,
+            'X' => 0x07,
+            'C' => 0x08,
+            'V' => 0x09,
+            'B' => 0x0B,
+            'Q' => 0x0C,
+            'W' => 0x0D,
+            'E' => 0x0E,
+            'R' => 0x0F,
+            'Y' => 0x10,
+            'T' => 0x11,
+            'O' => 0x1F,
+            'U' => 0x20,
+            'I' => 0x22,
+            'P' => 0x23,
+            'L' => 0x25,
+            'J' => 0x26,
+            'K' => 0x28,
+            'N' => 0x2D,
+            'M' => 0x2E,
+            // Key::parse 只会产生 A-Z，其他字符不匹配任何按键
+            _ => u16::MAX,
+        },
+        Key::Digit(d) => [0x1D, 0x12, 0x13, 0x14, 0x15, 0x17, 0x16, 0x1A, 0x1C, 0x19][d as usize],
+        Key::F(n) => [
+            0x7A, 0x78, 0x63, 0x76, 0x60, 0x61, 0x62, 0x64, 0x65, 0x6D, 0x67, 0x6F,
+        ][n as usize - 1],
+        Key::Left => 0x7B,
+        Key::Right => 0x7C,
+        Key::Down => 0x7D,
+        Key::Up => 0x7E,
+        Key::Home => 0x73,
+        Key::End => 0x77,
+        Key::PageUp => 0x74,
+        Key::PageDown => 0x79,
+        Key::Space => 0x31,
+        Key::Tab => 0x30,
+        Key::Enter => 0x24,
+        Key::Escape => 0x35,
+    }
+}
+
+/// 已注册的快捷键与回调，因为 CGEventTap 的 C 回调不支持闭包捕获
+struct Bindings {
+    hotkeys: Vec<KeyCombo>,
+    callback: Box<dyn Fn(usize) + Send>,
+}
+
+static mut GLOBAL_BINDINGS: Option<Bindings> = None;
+
+// 当前 tap 的 CFMachPort，供 C 回调在收到禁用通知时重新启用
+static TAP_PORT: AtomicPtr<c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+/// macOS 全局快捷键（基于监听 keyDown 的 CGEventTap）
+///
+/// 与 [`super::hook::MacMouseHook`] 一样绑定到当前线程的 CFRunLoop，
+/// `start` / `stop` 必须在主线程调用。匹配到的按键（包括自动重复）会被吞掉。
+pub struct MacKeyboardHook {
+    active: Arc<AtomicBool>,
+    tap: Option<InstalledTap>,
+}
+
+/// 已安装到 RunLoop 的 tap
+struct InstalledTap {
+    /// CFMachPortRef
+    port: *const c_void,
+    /// CFRunLoopSourceRef
+    source: *const c_void,
+    /// CFRunLoopRef
+    run_loop: *const c_void,
+}
+
+impl Default for MacKeyboardHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MacKeyboardHook {
+    pub fn new() -> Self {
+        Self {
+            active: Arc::new(AtomicBool::new(false)),
+            tap: None,
+        }
+    }
+}
+
+impl KeyboardHook for MacKeyboardHook {
+    fn start<F>(&mut self, hotkeys: &[KeyCombo], callback: F) -> Result<()>
+    where
+        F: Fn(usize) + Send + 'static,
+    {
+        if self.tap.is_some() {
+            self.stop()?;
+        }
+
+        unsafe {
+            let port = CGEventTapCreate(
+                0, // kCGHIDEventTap
+                0, // kCGHeadInsertEventTap
+                0, // kCGEventTapOptionDefault
+                1 << EVENT_KEY_DOWN,
+                key_tap_callback,
+                std::ptr::null_mut(),
+            );
+            if port.is_null() {
+                anyhow::bail!("CGEventTapCreate 失败 — 请确认已授予辅助功能权限");
+            }
+
+            let source = CFMachPortCreateRunLoopSource(std::ptr::null(), port, 0);
+            if source.is_null() {
+                CFMachPortInvalidate(port);
+                CFRelease(port);
+                anyhow::bail!("CFMachPortCreateRunLoopSource 失败");
+            }
+
+            GLOBAL_BINDINGS = Some(Bindings {
+                hotkeys: hotkeys.to_vec(),
+                callback: Box::new(callback),
+            });
+            TAP_PORT.store(port as *mut c_void, Ordering::SeqCst);
+
+            let run_loop = CFRunLoopGetCurrent();
+            CFRunLoopAddSource(run_loop, source, kCFRunLoopCommonModes);
+            CGEventTapEnable(port, true);
+
+            self.tap = Some(InstalledTap {
+                port,
+                source,
+                run_loop,
+            });
+        }
+
+        for combo in hotkeys {
+            log::info!("已注册快捷键 {}", combo);
+        }
+        self.active.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let Some(tap) = self.tap.take() else {
+            return Ok(());
+        };
+
+        unsafe {
+            TAP_PORT.store(std::ptr::null_mut(), Ordering::SeqCst);
+            CGEventTapEnable(tap.port, false);
+            CFRunLoopRemoveSource(tap.run_loop, tap.source, kCFRunLoopCommonModes);
+            CFMachPortInvalidate(tap.port);
+            CFRelease(tap.source);
+            CFRelease(tap.port);
+
+            // 回调只会在同一个 RunLoop 线程上执行，此时不会有回调正在运行
+            GLOBAL_BINDINGS = None;
+        }
+
+        self.active.store(false, Ordering::SeqCst);
+        log::info!("全局快捷键已注销");
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MacKeyboardHook {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+/// 快捷键 tap 的 C 回调函数
+unsafe extern "C" fn key_tap_callback(
+    _proxy: *const c_void,
+    event_type: u32,
+    event: *const c_void,
+    _user_info: *mut c_void,
+) -> *const c_void {
+    // tap 被系统禁用（超时或用户输入），立即重新启用
+    if event_type == 0xFFFFFFFE || event_type == 0xFFFFFFFF {
+        let port = TAP_PORT.load(Ordering::SeqCst);
+        if !port.is_null() {
+            CGEventTapEnable(port, true);
+        }
+        log::warn!(
+            "快捷键 CGEventTap 被系统禁用（类型={}），已重新启用",
+            event_type
+        );
+        return event;
+    }
+
+    if event_type != EVENT_KEY_DOWN {
+        return event;
+    }
+
+    extern "C" {
+        fn CGEventGetIntegerValueField(event: *const c_void, field: u32) -> i64;
+        fn CGEventGetFlags(event: *const c_void) -> u64;
+    }
+
+    let Some(ref bindings) = GLOBAL_BINDINGS else {
+        return event;
+    };
+
+    let code = CGEventGetIntegerValueField(event, FIELD_KEYCODE) as u16;
+    let modifiers = modifiers_from_flags(CGEventGetFlags(event));
+    let Some(index) = bindings
+        .hotkeys
+        .iter()
+        .position(|combo| key_code(combo.key) == code && combo.modifiers == modifiers)
+    else {
+        return event;
+    };
+
+    // 按住不放产生的自动重复只吞掉，不重复触发
+    if CGEventGetIntegerValueField(event, FIELD_AUTOREPEAT) == 0 {
+        (bindings.callback)(index);
+    }
+    std::ptr::null()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_code() {
+        assert_eq!(key_code(Key::Letter('A')), 0x00);
+        assert_eq!(key_code(Key::Letter('M')), 0x2E);
+        assert_eq!(key_code(Key::Digit(0)), 0x1D);
+        assert_eq!(key_code(Key::Digit(5)), 0x17);
+        assert_eq!(key_code(Key::F(1)), 0x7A);
+        assert_eq!(key_code(Key::F(12)), 0x6F);
+        assert_eq!(key_code(Key::Right), 0x7C);
+    }
+}
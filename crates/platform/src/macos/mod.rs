@@ -1,4 +1,5 @@
 pub mod hook;
+pub mod hotkey;
 pub mod window;
 pub mod monitor;
 pub mod hittest;
@@ -12,6 +13,21 @@ pub struct MacWindowHandle {
     pub(crate) ax_element: *const std::ffi::c_void,
     /// 窗口所属进程 PID
     pub(crate) pid: i32,
+    /// CGWindowID（获取失败时为 0）
+    pub(crate) window_id: u32,
+}
+
+impl MacWindowHandle {
+    /// 窗口标识：优先使用 CGWindowID，获取失败时退化为 AXUIElement 指针
+    ///
+    /// 指针只在这个句柄的生命周期内有意义，不能用作跨查询的键（见 `WindowHandle::stable_id`）。
+    pub fn id(&self) -> u64 {
+        if self.window_id != 0 {
+            self.window_id as u64
+        } else {
+            self.ax_element as usize as u64
+        }
+    }
 }
 
 // AXUIElement 是线程安全的
@@ -21,6 +37,7 @@ unsafe impl Sync for MacWindowHandle {}
 /// macOS 平台实现集合
 pub struct MacPlatform {
     pub hook: hook::MacMouseHook,
+    pub keyboard: hotkey::MacKeyboardHook,
     pub window_manager: window::MacWindowManager,
     pub hit_tester: hittest::MacHitTester,
     pub monitor_manager: monitor::MacMonitorManager,
@@ -31,6 +48,7 @@ impl MacPlatform {
     pub fn new() -> Self {
         Self {
             hook: hook::MacMouseHook::new(),
+            keyboard: hotkey::MacKeyboardHook::new(),
             window_manager: window::MacWindowManager::new(),
             hit_tester: hittest::MacHitTester::new(),
             monitor_manager: monitor::MacMonitorManager::new(),
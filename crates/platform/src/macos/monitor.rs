@@ -1,9 +1,14 @@
 #![allow(deprecated)] // cocoa crate fields are deprecated in favor of objc2-foundation
 
+use screenhop_core::edid::decode_manufacturer_id;
 use screenhop_core::MonitorInfo;
 use screenhop_core::Rect;
 
-use crate::{MonitorManager, WindowHandle};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use crate::{ChangeListeners, MonitorChangeCallback, MonitorManager, WindowHandle};
 
 /// macOS 显示器管理器（基于 NSScreen）
 pub struct MacMonitorManager;
@@ -57,13 +62,27 @@ impl MonitorManager for MacMonitorManager {
                     visible_frame.size.height,
                 );
 
+                let display_id = screen_display_id(screen);
+                let vendor = CGDisplayVendorNumber(display_id);
+                let model = CGDisplayModelNumber(display_id);
+                let serial = CGDisplaySerialNumber(display_id);
+
                 monitors.push(MonitorInfo {
-                    id: i as u64,
+                    id: display_id as u64,
                     bounds,
                     work_area,
+                    name: screen_localized_name(screen).unwrap_or_else(|| format!("Display {}", i)),
+                    manufacturer: decode_manufacturer_id(vendor as u16),
+                    product_code: (model != 0).then_some(model as u16),
+                    serial: (serial != 0).then(|| serial.to_string()),
+                    connector: CGDisplayIsBuiltin(display_id).then(|| "Built-in".to_string()),
+                    is_primary: CGDisplayIsMain(display_id),
+                    // NSScreen / AX 坐标均为逻辑点，无需换算
+                    scale_factor: 1.0,
                 });
             }
 
+            screenhop_core::monitor::sort_monitors(&mut monitors);
             monitors
         }
     }
@@ -72,13 +91,155 @@ impl MonitorManager for MacMonitorManager {
         use crate::WindowManager;
         let wm = super::window::MacWindowManager::new();
         let frame = wm.get_window_frame(handle)?;
-        let center = screenhop_core::Point {
-            x: frame.mid_x(),
-            y: frame.mid_y(),
-        };
 
         let monitors = self.get_monitors();
-        screenhop_core::monitor::find_monitor_for_point(center, &monitors)
+        screenhop_core::monitor::find_monitor_for_rect(&frame, &monitors)
             .map(|idx| monitors[idx].clone())
     }
+
+    /// 通过 CGDisplayRegisterReconfigurationCallback 接收显示器变化，
+    /// 并观察 NSApplicationDidChangeScreenParametersNotification：
+    /// Dock 和菜单栏的变化只改变 visibleFrame，不会触发显示器重新配置的回调。
+    /// 回调在主线程的 RunLoop 中触发，需要在主线程订阅
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        if display_listeners().add(callback) {
+            let err = unsafe {
+                CGDisplayRegisterReconfigurationCallback(display_reconfigured, std::ptr::null_mut())
+            };
+            if err == 0 {
+                DISPLAY_CALLBACK_REGISTERED.store(true, Ordering::SeqCst);
+            } else {
+                log::error!("CGDisplayRegisterReconfigurationCallback 失败: {}", err);
+            }
+
+            unsafe { observe_screen_parameters() };
+        }
+        DISPLAY_CALLBACK_REGISTERED.load(Ordering::SeqCst)
+    }
+}
+
+/// AppKit 在屏幕参数（包括 visibleFrame）变化时发出的通知
+const SCREEN_PARAMETERS_NOTIFICATION: &str = "NSApplicationDidChangeScreenParametersNotification";
+
+/// CFNotificationSuspensionBehaviorDeliverImmediately
+const DELIVER_IMMEDIATELY: isize = 4;
+
+/// 在本地通知中心（即 NSNotificationCenter.defaultCenter）观察屏幕参数变化
+unsafe fn observe_screen_parameters() {
+    use core_foundation::base::TCFType;
+
+    let name = core_foundation::string::CFString::new(SCREEN_PARAMETERS_NOTIFICATION);
+    // 观察者只用于标识这次订阅，取一个静态变量的地址
+    let observer = &DISPLAY_CALLBACK_REGISTERED as *const AtomicBool as *const c_void;
+    CFNotificationCenterAddObserver(
+        CFNotificationCenterGetLocalCenter(),
+        observer,
+        screen_parameters_changed,
+        name.as_concrete_TypeRef() as _,
+        std::ptr::null(),
+        DELIVER_IMMEDIATELY,
+    );
+}
+
+/// 屏幕参数变化回调（Dock 位置 / 大小、菜单栏自动隐藏等）
+unsafe extern "C" fn screen_parameters_changed(
+    _center: *const c_void,
+    _observer: *const c_void,
+    _name: *const c_void,
+    _object: *const c_void,
+    _user_info: *const c_void,
+) {
+    log::info!("屏幕参数变化（Dock 或菜单栏）");
+    display_listeners().notify();
+}
+
+/// kCGDisplayBeginConfigurationFlag：变化开始前的通知，此时布局还未更新
+const BEGIN_CONFIGURATION_FLAG: u32 = 1 << 0;
+
+// 显示器变化的订阅者，CGDisplay 的 C 回调不支持闭包捕获
+fn display_listeners() -> &'static ChangeListeners {
+    static LISTENERS: OnceLock<ChangeListeners> = OnceLock::new();
+    LISTENERS.get_or_init(ChangeListeners::default)
+}
+
+static DISPLAY_CALLBACK_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+type CGDisplayReconfigurationCallBack =
+    unsafe extern "C" fn(display: u32, flags: u32, user_info: *mut c_void);
+
+type CFNotificationCallback = unsafe extern "C" fn(
+    center: *const c_void,
+    observer: *const c_void,
+    name: *const c_void,
+    object: *const c_void,
+    user_info: *const c_void,
+);
+
+/// 显示器配置变化回调（每块受影响的显示器各触发一次）
+unsafe extern "C" fn display_reconfigured(display: u32, flags: u32, _user_info: *mut c_void) {
+    if flags & BEGIN_CONFIGURATION_FLAG != 0 {
+        return;
+    }
+
+    log::info!("显示器布局变化: display={} flags={:#x}", display, flags);
+    display_listeners().notify();
+}
+
+extern "C" {
+    fn CGDisplayRegisterReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        user_info: *mut c_void,
+    ) -> i32;
+    fn CGDisplayVendorNumber(display: u32) -> u32;
+    fn CGDisplayModelNumber(display: u32) -> u32;
+    fn CGDisplaySerialNumber(display: u32) -> u32;
+    fn CGDisplayIsMain(display: u32) -> bool;
+    fn CGDisplayIsBuiltin(display: u32) -> bool;
+    fn CFNotificationCenterGetLocalCenter() -> *const c_void;
+    fn CFNotificationCenterAddObserver(
+        center: *const c_void,
+        observer: *const c_void,
+        callback: CFNotificationCallback,
+        name: *const c_void,
+        object: *const c_void,
+        suspension_behavior: isize,
+    );
+}
+
+/// 读取 NSScreen 对应的 CGDirectDisplayID（deviceDescription 中的 NSScreenNumber）
+unsafe fn screen_display_id(screen: *mut objc::runtime::Object) -> u32 {
+    use core_foundation::base::TCFType;
+    use objc::*;
+
+    let description: *mut objc::runtime::Object = msg_send![screen, deviceDescription];
+    // CFString 与 NSString 免费桥接
+    let key = core_foundation::string::CFString::new("NSScreenNumber");
+    let key_ref = key.as_concrete_TypeRef() as *const std::ffi::c_void;
+    let number: *mut objc::runtime::Object = msg_send![description, objectForKey: key_ref];
+
+    if number.is_null() {
+        return 0;
+    }
+    msg_send![number, unsignedIntValue]
+}
+
+/// 读取 NSScreen 的本地化名称（macOS 10.15+）
+unsafe fn screen_localized_name(screen: *mut objc::runtime::Object) -> Option<String> {
+    use objc::*;
+
+    let responds: bool = msg_send![screen, respondsToSelector: sel!(localizedName)];
+    if !responds {
+        return None;
+    }
+
+    let name: *mut objc::runtime::Object = msg_send![screen, localizedName];
+    if name.is_null() {
+        return None;
+    }
+
+    let utf8: *const std::os::raw::c_char = msg_send![name, UTF8String];
+    if utf8.is_null() {
+        return None;
+    }
+    Some(std::ffi::CStr::from_ptr(utf8).to_string_lossy().into_owned())
 }
@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 use anyhow::Result;
+use screenhop_core::rules::WindowInfo;
 use screenhop_core::{Point, Rect};
 
 use crate::{WindowHandle, WindowManager};
@@ -61,6 +62,11 @@ impl MacWindowManager {
                     element: *const std::ffi::c_void,
                     pid: *mut i32,
                 ) -> i32;
+                // 私有 API：从 AXUIElement 取得 CGWindowID
+                fn _AXUIElementGetWindow(
+                    element: *const std::ffi::c_void,
+                    window_id: *mut u32,
+                ) -> i32;
             }
 
             use core_foundation::base::TCFType;
@@ -85,13 +91,88 @@ impl MacWindowManager {
             let mut pid: i32 = 0;
             AXUIElementGetPid(window_element, &mut pid);
 
+            let mut window_id: u32 = 0;
+            if _AXUIElementGetWindow(window_element, &mut window_id) != 0 {
+                window_id = 0;
+            }
+
             Some(MacWindowHandle {
                 ax_element: window_element,
                 pid,
+                window_id,
             })
         }
     }
 
+    /// 读取 AX 元素的字符串属性
+    fn string_attribute(&self, element: *const std::ffi::c_void, name: &str) -> Option<String> {
+        unsafe {
+            extern "C" {
+                fn AXUIElementCopyAttributeValue(
+                    element: *const std::ffi::c_void,
+                    attribute: *const std::ffi::c_void,
+                    value: *mut *const std::ffi::c_void,
+                ) -> i32;
+                fn CFGetTypeID(cf: *const std::ffi::c_void) -> usize;
+                fn CFRelease(cf: *const std::ffi::c_void);
+            }
+
+            use core_foundation::base::TCFType;
+            use core_foundation::string::CFString;
+
+            let attr = CFString::new(name);
+            let mut value: *const std::ffi::c_void = std::ptr::null();
+            let r =
+                AXUIElementCopyAttributeValue(element, attr.as_concrete_TypeRef() as _, &mut value);
+            if r != 0 || value.is_null() {
+                return None;
+            }
+            if CFGetTypeID(value) != CFString::type_id() {
+                CFRelease(value);
+                return None;
+            }
+
+            let string = CFString::wrap_under_create_rule(value as _).to_string();
+            (!string.is_empty()).then_some(string)
+        }
+    }
+
+    /// 通过 NSRunningApplication 获取进程的 bundle id 和可执行文件名
+    fn application_info(&self, pid: i32) -> (Option<String>, Option<String>) {
+        unsafe {
+            use objc::runtime::Object;
+            use objc::*;
+
+            unsafe fn to_string(string: *mut Object) -> Option<String> {
+                if string.is_null() {
+                    return None;
+                }
+                let utf8: *const std::ffi::c_char = msg_send![string, UTF8String];
+                if utf8.is_null() {
+                    return None;
+                }
+                let string = std::ffi::CStr::from_ptr(utf8).to_string_lossy();
+                Some(string.into_owned())
+            }
+
+            let cls = class!(NSRunningApplication);
+            let app: *mut Object = msg_send![cls, runningApplicationWithProcessIdentifier: pid];
+            if app.is_null() {
+                return (None, None);
+            }
+
+            let bundle_id: *mut Object = msg_send![app, bundleIdentifier];
+            let url: *mut Object = msg_send![app, executableURL];
+            let executable: *mut Object = if url.is_null() {
+                std::ptr::null_mut()
+            } else {
+                msg_send![url, lastPathComponent]
+            };
+
+            (to_string(bundle_id), to_string(executable))
+        }
+    }
+
     /// 向上遍历 UI 层级找到窗口
     fn walk_up_to_window(&self, start: *const std::ffi::c_void) -> Option<*const std::ffi::c_void> {
         unsafe {
@@ -152,6 +233,72 @@ impl WindowManager for MacWindowManager {
         Some(WindowHandle { inner: mac_handle })
     }
 
+    fn get_focused_window(&self) -> Option<WindowHandle> {
+        unsafe {
+            extern "C" {
+                fn AXUIElementCreateSystemWide() -> *const std::ffi::c_void;
+                fn AXUIElementCopyAttributeValue(
+                    element: *const std::ffi::c_void,
+                    attribute: *const std::ffi::c_void,
+                    value: *mut *const std::ffi::c_void,
+                ) -> i32;
+                fn AXUIElementGetPid(
+                    element: *const std::ffi::c_void,
+                    pid: *mut i32,
+                ) -> i32;
+                fn _AXUIElementGetWindow(
+                    element: *const std::ffi::c_void,
+                    window_id: *mut u32,
+                ) -> i32;
+                fn CFRelease(cf: *const std::ffi::c_void);
+            }
+
+            use core_foundation::base::TCFType;
+
+            let app_attr = core_foundation::string::CFString::new("AXFocusedApplication");
+            let window_attr = core_foundation::string::CFString::new("AXFocusedWindow");
+
+            let system_wide = AXUIElementCreateSystemWide();
+            let mut app: *const std::ffi::c_void = std::ptr::null();
+            let r = AXUIElementCopyAttributeValue(
+                system_wide,
+                app_attr.as_concrete_TypeRef() as _,
+                &mut app,
+            );
+            CFRelease(system_wide);
+            if r != 0 || app.is_null() {
+                return None;
+            }
+
+            let mut window: *const std::ffi::c_void = std::ptr::null();
+            let r = AXUIElementCopyAttributeValue(
+                app,
+                window_attr.as_concrete_TypeRef() as _,
+                &mut window,
+            );
+            CFRelease(app);
+            if r != 0 || window.is_null() {
+                return None;
+            }
+
+            let mut pid: i32 = 0;
+            AXUIElementGetPid(window, &mut pid);
+
+            let mut window_id: u32 = 0;
+            if _AXUIElementGetWindow(window, &mut window_id) != 0 {
+                window_id = 0;
+            }
+
+            Some(WindowHandle {
+                inner: MacWindowHandle {
+                    ax_element: window,
+                    pid,
+                    window_id,
+                },
+            })
+        }
+    }
+
     fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect> {
         unsafe {
             extern "C" {
@@ -207,6 +354,18 @@ impl WindowManager for MacWindowManager {
         }
     }
 
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo {
+        let (bundle_id, process_name) = self.application_info(handle.inner.pid);
+
+        WindowInfo {
+            process_name,
+            bundle_id,
+            // macOS 没有窗口类，按 bundle id 匹配应用
+            class: None,
+            title: self.string_attribute(handle.inner.ax_element, "AXTitle"),
+        }
+    }
+
     fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()> {
         unsafe {
             extern "C" {
@@ -0,0 +1,81 @@
+use screenhop_core::{Point, Rect};
+use std::sync::Arc;
+
+use super::IpcClient;
+use crate::{HitTester, WindowHandle};
+
+/// i3 命中检测器（标题栏高度取自布局树，不需要配置）
+pub struct SwayHitTester {
+    client: Arc<IpcClient>,
+}
+
+impl SwayHitTester {
+    pub fn new(client: Arc<IpcClient>) -> Self {
+        Self { client }
+    }
+}
+
+impl HitTester for SwayHitTester {
+    fn is_title_bar_hit(&self, handle: &WindowHandle, point: Point) -> bool {
+        let Ok(tree) = self.client.get_tree() else {
+            return false;
+        };
+
+        let Some((node, _)) = tree.find_window(handle.inner) else {
+            return false;
+        };
+        tree.find_parent(handle.inner)
+            .and_then(|parent| node.title_bar(parent))
+            .is_some_and(|title_bar| title_bar.contains(point))
+    }
+
+    fn interactive_tab_regions(&self, _handle: &WindowHandle) -> Vec<Rect> {
+        // 标签由窗口管理器绘制，点击可见窗口自己的标签即移动它；
+        // 其他标签不属于可见窗口，点击会直接交给窗口管理器
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{self, FakeServer};
+    use super::*;
+
+    #[test]
+    fn test_title_bar_hit() {
+        let server = FakeServer::start();
+        let hit_tester = SwayHitTester::new(server.client());
+        let handle = WindowHandle { inner: 8 };
+
+        assert!(hit_tester.is_title_bar_hit(&handle, Point { x: 400.0, y: 40.0 }));
+        assert!(!hit_tester.is_title_bar_hit(&handle, Point { x: 400.0, y: 60.0 }));
+        // 全屏窗口没有装饰
+        assert!(
+            !hit_tester.is_title_bar_hit(&WindowHandle { inner: 20 }, Point { x: 2000.0, y: 5.0 })
+        );
+
+        // 标签布局中只有窗口自己的标签算作标题栏
+        let tab = WindowHandle { inner: 10 };
+        assert!(hit_tester.is_title_bar_hit(&tab, Point { x: 1000.0, y: 40.0 }));
+        assert!(!hit_tester.is_title_bar_hit(&tab, Point { x: 1500.0, y: 40.0 }));
+    }
+
+    #[test]
+    fn test_i3_title_bar_hit() {
+        let server = FakeServer::with_tree(testing::I3_TREE);
+        let hit_tester = SwayHitTester::new(server.client());
+
+        let xterm = WindowHandle {
+            inner: 94413212543488,
+        };
+        assert!(hit_tester.is_title_bar_hit(&xterm, Point { x: 400.0, y: 10.0 }));
+        assert!(!hit_tester.is_title_bar_hit(&xterm, Point { x: 400.0, y: 30.0 }));
+
+        // 堆叠布局中可见窗口的标题在第二行
+        let stacked = WindowHandle {
+            inner: 94413212547104,
+        };
+        assert!(hit_tester.is_title_bar_hit(&stacked, Point { x: 1500.0, y: 30.0 }));
+        assert!(!hit_tester.is_title_bar_hit(&stacked, Point { x: 1500.0, y: 10.0 }));
+    }
+}
@@ -0,0 +1,678 @@
+pub mod hittest;
+pub mod monitor;
+pub mod window;
+
+use anyhow::{bail, Context, Result};
+use screenhop_core::Rect;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// i3-ipc 消息头魔数
+const MAGIC: &[u8; 6] = b"i3-ipc";
+
+/// i3-ipc 消息类型
+pub(crate) const RUN_COMMAND: u32 = 0;
+pub(crate) const GET_WORKSPACES: u32 = 1;
+pub(crate) const SUBSCRIBE: u32 = 2;
+pub(crate) const GET_OUTPUTS: u32 = 3;
+pub(crate) const GET_TREE: u32 = 4;
+
+/// 事件消息类型（最高位为 1）
+pub(crate) const EVENT_OUTPUT: u32 = 0x8000_0001;
+
+/// 写入一条 i3-ipc 消息（魔数 + 长度 + 类型 + 负载，整数为本机字节序）
+pub(crate) fn write_message(writer: &mut impl Write, msg_type: u32, payload: &[u8]) -> Result<()> {
+    let mut message = Vec::with_capacity(MAGIC.len() + 8 + payload.len());
+    message.extend_from_slice(MAGIC);
+    message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+    message.extend_from_slice(&msg_type.to_ne_bytes());
+    message.extend_from_slice(payload);
+
+    writer.write_all(&message)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// 读取一条 i3-ipc 消息，返回（类型, 负载）
+pub(crate) fn read_message(reader: &mut impl Read) -> Result<(u32, Vec<u8>)> {
+    let mut header = [0u8; 14];
+    reader.read_exact(&mut header)?;
+    if &header[..6] != MAGIC {
+        bail!("i3-ipc 消息头无效");
+    }
+
+    let len = u32::from_ne_bytes(header[6..10].try_into()?) as usize;
+    let msg_type = u32::from_ne_bytes(header[10..14].try_into()?);
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+
+    Ok((msg_type, payload))
+}
+
+/// i3 / sway 中的矩形（整数坐标）
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub(crate) struct IpcRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<IpcRect> for Rect {
+    fn from(rect: IpcRect) -> Self {
+        Self::new(
+            rect.x as f64,
+            rect.y as f64,
+            rect.width as f64,
+            rect.height as f64,
+        )
+    }
+}
+
+/// `GET_OUTPUTS` 的单项
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Output {
+    /// sway 独有的节点 ID
+    pub id: Option<u64>,
+    pub name: String,
+    pub active: bool,
+    #[serde(default)]
+    pub primary: bool,
+    pub rect: IpcRect,
+    /// 以下字段仅 sway 提供
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub serial: Option<String>,
+}
+
+/// `GET_WORKSPACES` 的单项
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Workspace {
+    pub name: String,
+    pub visible: bool,
+    pub output: String,
+    /// 工作区矩形（已扣除 bar 等保留区域）
+    pub rect: IpcRect,
+}
+
+/// `GET_TREE` 中的节点
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Node {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub layout: String,
+    /// 容器矩形（不含标题栏，标签 / 堆叠布局中也不含标签栏）
+    pub rect: IpcRect,
+    /// 标题栏（或标签）相对于父容器的位置，无装饰时为全零
+    #[serde(default)]
+    pub deco_rect: IpcRect,
+    #[serde(default)]
+    pub fullscreen_mode: u8,
+    /// 是否拥有键盘焦点
+    #[serde(default)]
+    pub focused: bool,
+    /// 子节点按焦点顺序排列的 ID，第一个是标签 / 堆叠布局中可见的那个
+    #[serde(default)]
+    pub focus: Vec<u64>,
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+    #[serde(default)]
+    pub floating_nodes: Vec<Node>,
+    /// X11 窗口 ID（i3 和 sway 的 Xwayland 窗口）
+    pub window: Option<u32>,
+    /// Wayland 应用 ID（仅 sway）
+    pub app_id: Option<String>,
+    /// X11 窗口属性（i3 和 sway 的 Xwayland 窗口）
+    pub window_properties: Option<WindowProperties>,
+    /// 进程 ID（仅 sway）
+    pub pid: Option<u32>,
+}
+
+/// X11 窗口的 `WM_CLASS` 等属性
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct WindowProperties {
+    pub class: Option<String>,
+}
+
+impl Node {
+    /// 是否为承载实际窗口的叶子容器
+    pub(crate) fn is_window(&self) -> bool {
+        self.nodes.is_empty()
+            && self.floating_nodes.is_empty()
+            && (self.window.is_some() || self.app_id.is_some())
+    }
+
+    /// 按 ID 查找窗口，同时返回它是否处于浮动状态
+    pub(crate) fn find_window(&self, id: u64) -> Option<(&Node, bool)> {
+        self.find_window_inner(id, false)
+    }
+
+    /// 拥有键盘焦点的窗口
+    pub(crate) fn find_focused_window(&self) -> Option<&Node> {
+        if self.focused && self.is_window() {
+            return Some(self);
+        }
+
+        self.nodes
+            .iter()
+            .chain(&self.floating_nodes)
+            .find_map(Node::find_focused_window)
+    }
+
+    fn find_window_inner(&self, id: u64, floating: bool) -> Option<(&Node, bool)> {
+        if self.id == id && self.is_window() {
+            return Some((self, floating));
+        }
+
+        self.nodes
+            .iter()
+            .find_map(|node| node.find_window_inner(id, floating))
+            .or_else(|| {
+                self.floating_nodes
+                    .iter()
+                    .find_map(|node| node.find_window_inner(id, true))
+            })
+    }
+
+    /// 直接包含指定节点的父容器
+    pub(crate) fn find_parent(&self, id: u64) -> Option<&Node> {
+        let mut children = self.nodes.iter().chain(&self.floating_nodes);
+        if children.any(|node| node.id == id) {
+            return Some(self);
+        }
+
+        self.nodes
+            .iter()
+            .chain(&self.floating_nodes)
+            .find_map(|node| node.find_parent(id))
+    }
+
+    /// 标题栏（或标签）的绝对位置，无装饰或全屏时返回 `None`
+    ///
+    /// sway 和 i3 的 `rect` 都不含标题栏，`deco_rect` 相对于父容器：
+    /// 拆分布局和浮动窗口的标题栏紧贴在 `rect` 上方；标签布局的标签横向排列、
+    /// 堆叠布局的标题纵向排列在父容器顶部，窗口的 `rect` 下移整个标签栏的高度。
+    pub(crate) fn title_bar(&self, parent: &Node) -> Option<Rect> {
+        let deco = self.deco_rect;
+        if self.fullscreen_mode != 0 || deco.width <= 0 || deco.height <= 0 {
+            return None;
+        }
+
+        let rect = Rect::from(self.rect);
+        let height = deco.height as f64;
+        let (x, y) = match parent.layout.as_str() {
+            "tabbed" | "stacked" => (
+                (parent.rect.x + deco.x) as f64,
+                rect.y - height * title_rows(parent) + deco.y as f64,
+            ),
+            _ => (rect.x, rect.y - height),
+        };
+        Some(Rect::new(x, y, deco.width as f64, height))
+    }
+
+    /// 包含标题栏的窗口外框（标签 / 堆叠布局中包含整个标签栏）
+    ///
+    /// 浮动窗口的 `move position` 以外框为准。
+    pub(crate) fn frame(&self, parent: &Node) -> Rect {
+        let rect = Rect::from(self.rect);
+        let Some(title_bar) = self.title_bar(parent) else {
+            return rect;
+        };
+
+        let top = rect.y - title_bar.height * title_rows(parent);
+        Rect::new(rect.x, top, rect.width, rect.y + rect.height - top)
+    }
+
+    /// 当前可见的窗口及其父容器，按从上到下的顺序
+    ///
+    /// 只遍历 `visible_workspaces` 中的工作区；标签 / 堆叠布局只取可见的那个子节点。
+    pub(crate) fn visible_windows_top_down(
+        &self,
+        visible_workspaces: &[String],
+    ) -> Vec<(&Node, &Node)> {
+        let mut windows = Vec::new();
+
+        let mut workspaces = Vec::new();
+        for output in self.nodes.iter().filter(|n| n.node_type == "output") {
+            output.collect_workspaces(&mut workspaces);
+        }
+
+        for workspace in workspaces.into_iter().filter(|n| {
+            n.name
+                .as_ref()
+                .is_some_and(|name| visible_workspaces.contains(name))
+        }) {
+            let mut tiled = Vec::new();
+            for node in &workspace.nodes {
+                node.collect_visible(workspace, &mut tiled);
+            }
+
+            // 浮动窗口越靠后越在上层
+            let mut floating = Vec::new();
+            for node in workspace.floating_nodes.iter().rev() {
+                node.collect_visible(workspace, &mut floating);
+            }
+
+            // 全屏窗口盖住同一工作区的其他窗口
+            windows.extend(tiled.iter().filter(|(n, _)| n.fullscreen_mode != 0));
+            windows.extend(floating);
+            windows.extend(tiled.into_iter().filter(|(n, _)| n.fullscreen_mode == 0));
+        }
+
+        windows
+    }
+
+    /// 输出下的工作区（i3 的工作区位于输出的 `content` 容器中，sway 直接挂在输出下）
+    fn collect_workspaces<'a>(&'a self, workspaces: &mut Vec<&'a Node>) {
+        for node in &self.nodes {
+            if node.node_type == "workspace" {
+                workspaces.push(node);
+            } else {
+                node.collect_workspaces(workspaces);
+            }
+        }
+    }
+
+    fn collect_visible<'a>(&'a self, parent: &'a Node, windows: &mut Vec<(&'a Node, &'a Node)>) {
+        if self.is_window() {
+            windows.push((self, parent));
+            return;
+        }
+
+        if matches!(self.layout.as_str(), "tabbed" | "stacked") {
+            let visible = self
+                .focus
+                .first()
+                .and_then(|id| self.nodes.iter().find(|n| n.id == *id))
+                .or_else(|| self.nodes.first());
+            if let Some(node) = visible {
+                node.collect_visible(self, windows);
+            }
+        } else {
+            for node in &self.nodes {
+                node.collect_visible(self, windows);
+            }
+        }
+    }
+}
+
+/// 父容器顶部的标题行数：堆叠布局每个子节点一行，其他布局一行
+fn title_rows(parent: &Node) -> f64 {
+    if parent.layout == "stacked" {
+        parent.nodes.len() as f64
+    } else {
+        1.0
+    }
+}
+
+/// `RUN_COMMAND` 的单条执行结果
+#[derive(Debug, Deserialize)]
+struct CommandResult {
+    success: bool,
+    error: Option<String>,
+}
+
+/// i3-ipc 客户端（协议与 sway 兼容，回复中 sway 独有的字段为可选）
+///
+/// 每次请求使用一条新连接，不需要在多个线程之间同步。
+pub struct IpcClient {
+    socket_path: PathBuf,
+}
+
+impl IpcClient {
+    pub fn new(socket_path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+        }
+    }
+
+    /// 从 `$I3SOCK` 获取套接字路径
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var_os("I3SOCK").context("未设置 I3SOCK，当前不是 i3 会话")?;
+
+        Ok(Self::new(path))
+    }
+
+    fn request<T: DeserializeOwned>(&self, msg_type: u32, payload: &str) -> Result<T> {
+        let mut stream = UnixStream::connect(&self.socket_path)
+            .with_context(|| format!("无法连接 IPC 套接字: {}", self.socket_path.display()))?;
+
+        write_message(&mut stream, msg_type, payload.as_bytes())?;
+        let (reply_type, reply) = read_message(&mut stream)?;
+        if reply_type != msg_type {
+            bail!("IPC 回复类型不匹配: {} != {}", reply_type, msg_type);
+        }
+
+        serde_json::from_slice(&reply).context("无法解析 IPC 回复")
+    }
+
+    pub(crate) fn get_outputs(&self) -> Result<Vec<Output>> {
+        self.request(GET_OUTPUTS, "")
+    }
+
+    pub(crate) fn get_workspaces(&self) -> Result<Vec<Workspace>> {
+        self.request(GET_WORKSPACES, "")
+    }
+
+    pub(crate) fn get_tree(&self) -> Result<Node> {
+        self.request(GET_TREE, "")
+    }
+
+    /// 执行命令，任意一条失败即返回错误
+    pub(crate) fn run_command(&self, command: &str) -> Result<()> {
+        let results: Vec<CommandResult> = self.request(RUN_COMMAND, command)?;
+
+        for result in results {
+            if !result.success {
+                bail!(
+                    "命令执行失败 `{}`: {}",
+                    command,
+                    result.error.unwrap_or_default()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 订阅事件，返回之后只用于接收事件的连接
+    pub(crate) fn subscribe(&self, events: &[&str]) -> Result<UnixStream> {
+        let mut stream = UnixStream::connect(&self.socket_path)
+            .with_context(|| format!("无法连接 IPC 套接字: {}", self.socket_path.display()))?;
+
+        write_message(&mut stream, SUBSCRIBE, &serde_json::to_vec(events)?)?;
+        let (reply_type, reply) = read_message(&mut stream)?;
+        if reply_type != SUBSCRIBE {
+            bail!("IPC 回复类型不匹配: {} != {}", reply_type, SUBSCRIBE);
+        }
+
+        let result: CommandResult = serde_json::from_slice(&reply).context("无法解析 IPC 回复")?;
+        if !result.success {
+            bail!("订阅事件失败: {}", result.error.unwrap_or_default());
+        }
+        Ok(stream)
+    }
+
+    /// 当前可见工作区的名称
+    pub(crate) fn visible_workspaces(&self) -> Result<Vec<String>> {
+        Ok(self
+            .get_workspaces()?
+            .into_iter()
+            .filter(|ws| ws.visible)
+            .map(|ws| ws.name)
+            .collect())
+    }
+}
+
+/// i3 平台实现集合（X11 鼠标钩子与快捷键 + i3 IPC 窗口管理）
+///
+/// i3 的平铺窗口不响应 EWMH 的移动请求，因此窗口操作改走 IPC。
+/// 只支持 i3：sway 原生窗口的输入不经过 X server，X11 钩子收不到点击和按键，
+/// 而 sway 的 IPC 又不提供光标位置，evdev 钩子也无法定位点击。
+pub struct SwayPlatform {
+    pub hook: crate::x11::hook::X11MouseHook,
+    pub keyboard: crate::x11::hotkey::X11KeyboardHook,
+    pub window_manager: window::SwayWindowManager,
+    pub hit_tester: hittest::SwayHitTester,
+    pub monitor_manager: monitor::SwayMonitorManager,
+}
+
+impl SwayPlatform {
+    /// sway 会话（同样设置了 `I3SOCK`）返回错误
+    pub fn new() -> Result<Self> {
+        if std::env::var_os("SWAYSOCK").is_some() {
+            bail!("当前是 sway 会话，i3 后端依赖的 X11 钩子收不到 sway 原生窗口的输入");
+        }
+        let client = Arc::new(IpcClient::from_env()?);
+
+        Ok(Self {
+            hook: crate::x11::hook::X11MouseHook::new(),
+            keyboard: crate::x11::hotkey::X11KeyboardHook::new(),
+            window_manager: window::SwayWindowManager::new(client.clone()),
+            hit_tester: hittest::SwayHitTester::new(client.clone()),
+            monitor_manager: monitor::SwayMonitorManager::new(client),
+        })
+    }
+}
+
+/// 回放录制好的回复的假 IPC 服务器
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use std::collections::HashMap;
+    use std::os::unix::net::UnixListener;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    pub(crate) const OUTPUTS: &str = include_str!("testdata/outputs.json");
+    pub(crate) const WORKSPACES: &str = include_str!("testdata/workspaces.json");
+    /// sway 的布局树：拆分、标签布局、浮动窗口和全屏的 Xwayland 窗口
+    pub(crate) const TREE: &str = include_str!("testdata/sway_tree.json");
+    /// i3 的布局树：工作区位于输出的 `content` 容器中，包含堆叠布局、浮动容器和像素边框窗口
+    pub(crate) const I3_TREE: &str = include_str!("testdata/i3_tree.json");
+
+    pub(crate) struct FakeServer {
+        path: PathBuf,
+        commands: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FakeServer {
+        /// 启动服务器；`RUN_COMMAND` 总是回复成功并记录命令，`SUBSCRIBE` 之后推送一条 output 事件
+        pub(crate) fn start() -> Self {
+            Self::with_tree(TREE)
+        }
+
+        /// 启动回复指定布局树的服务器
+        pub(crate) fn with_tree(tree: &'static str) -> Self {
+            let path = crate::testing::temp_path("screenhop_sway");
+
+            let replies: HashMap<u32, &'static str> = HashMap::from([
+                (GET_OUTPUTS, OUTPUTS),
+                (GET_WORKSPACES, WORKSPACES),
+                (GET_TREE, tree),
+            ]);
+            let commands = Arc::new(Mutex::new(Vec::new()));
+
+            let listener = UnixListener::bind(&path).unwrap();
+            let recorded = commands.clone();
+            std::thread::spawn(move || {
+                for mut stream in listener.incoming().map_while(|s| s.ok()) {
+                    while let Ok((msg_type, payload)) = read_message(&mut stream) {
+                        if msg_type == SUBSCRIBE {
+                            // 订阅成功后立即推送一条 output 事件
+                            write_message(&mut stream, SUBSCRIBE, br#"{"success": true}"#).unwrap();
+                            write_message(
+                                &mut stream,
+                                EVENT_OUTPUT,
+                                br#"{"change": "unspecified"}"#,
+                            )
+                            .unwrap();
+                            continue;
+                        }
+
+                        let reply = if msg_type == RUN_COMMAND {
+                            recorded
+                                .lock()
+                                .unwrap()
+                                .push(String::from_utf8(payload).unwrap());
+                            r#"[{"success": true}]"#
+                        } else {
+                            replies[&msg_type]
+                        };
+                        write_message(&mut stream, msg_type, reply.as_bytes()).unwrap();
+                    }
+                }
+            });
+
+            Self { path, commands }
+        }
+
+        pub(crate) fn client(&self) -> Arc<IpcClient> {
+            Arc::new(IpcClient::new(&self.path))
+        }
+
+        pub(crate) fn commands(&self) -> Vec<String> {
+            self.commands.lock().unwrap().clone()
+        }
+
+        pub(crate) fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl Drop for FakeServer {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::FakeServer;
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let mut buf = Vec::new();
+        write_message(&mut buf, RUN_COMMAND, b"focus left").unwrap();
+
+        assert_eq!(&buf[..6], b"i3-ipc");
+        let (msg_type, payload) = read_message(&mut buf.as_slice()).unwrap();
+        assert_eq!(msg_type, RUN_COMMAND);
+        assert_eq!(payload, b"focus left");
+    }
+
+    #[test]
+    fn test_read_message_rejects_bad_magic() {
+        let buf = b"x3-ipc\0\0\0\0\0\0\0\0";
+        assert!(read_message(&mut buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_client_against_fake_server() {
+        let server = FakeServer::start();
+        let client = IpcClient::new(server.path());
+
+        assert_eq!(client.get_outputs().unwrap().len(), 3);
+        assert_eq!(client.visible_workspaces().unwrap(), vec!["1", "3"]);
+
+        client.run_command("[con_id=7] focus").unwrap();
+        assert_eq!(server.commands(), vec!["[con_id=7] focus"]);
+    }
+
+    #[test]
+    fn test_visible_windows_top_down() {
+        let tree: Node = serde_json::from_str(testing::TREE).unwrap();
+        let visible = vec!["1".to_string(), "3".to_string()];
+        let ids: Vec<u64> = tree
+            .visible_windows_top_down(&visible)
+            .iter()
+            .map(|(n, _)| n.id)
+            .collect();
+
+        // 浮动窗口在前；标签布局只保留获得焦点的标签；工作区 2 不可见
+        assert_eq!(ids, vec![12, 8, 10, 20]);
+    }
+
+    #[test]
+    fn test_i3_visible_windows_top_down() {
+        let tree: Node = serde_json::from_str(testing::I3_TREE).unwrap();
+        let visible = vec!["1".to_string(), "3".to_string()];
+        let ids: Vec<u64> = tree
+            .visible_windows_top_down(&visible)
+            .iter()
+            .map(|(n, _)| n.id)
+            .collect();
+
+        // 工作区在 content 容器中；浮动容器里的窗口在前；堆叠布局只保留获得焦点的窗口；
+        // dock 区域中的 i3bar 不算窗口
+        assert_eq!(
+            ids,
+            vec![
+                94413212549216,
+                94413212543488,
+                94413212547104,
+                94413212553312
+            ]
+        );
+    }
+
+    #[test]
+    fn test_title_bar_and_frame() {
+        let tree: Node = serde_json::from_str(testing::TREE).unwrap();
+        let title_bar = |id| {
+            let (node, _) = tree.find_window(id).unwrap();
+            node.title_bar(tree.find_parent(id).unwrap())
+        };
+
+        // sway 的 rect 不含标题栏，deco_rect 相对于父容器
+        assert_eq!(title_bar(8), Some(Rect::new(0.0, 30.0, 960.0, 24.0)));
+        assert_eq!(title_bar(12), Some(Rect::new(600.0, 300.0, 600.0, 24.0)));
+        // 标签横向排列在标签容器顶部
+        assert_eq!(title_bar(10), Some(Rect::new(960.0, 30.0, 480.0, 24.0)));
+        assert_eq!(title_bar(11), Some(Rect::new(1440.0, 30.0, 480.0, 24.0)));
+        // 全屏窗口没有装饰
+        assert_eq!(title_bar(20), None);
+
+        let (node, _) = tree.find_window(12).unwrap();
+        assert_eq!(
+            node.frame(tree.find_parent(12).unwrap()),
+            Rect::new(600.0, 300.0, 600.0, 400.0)
+        );
+    }
+
+    #[test]
+    fn test_i3_title_bar_and_frame() {
+        let tree: Node = serde_json::from_str(testing::I3_TREE).unwrap();
+        let title_bar = |id| {
+            let (node, _) = tree.find_window(id).unwrap();
+            node.title_bar(tree.find_parent(id).unwrap())
+        };
+
+        assert_eq!(
+            title_bar(94413212543488),
+            Some(Rect::new(0.0, 0.0, 960.0, 22.0))
+        );
+        // 堆叠布局的标题按顺序纵向排列
+        assert_eq!(
+            title_bar(94413212545712),
+            Some(Rect::new(960.0, 0.0, 960.0, 22.0))
+        );
+        assert_eq!(
+            title_bar(94413212547104),
+            Some(Rect::new(960.0, 22.0, 960.0, 22.0))
+        );
+        // 浮动窗口的 deco_rect 相对于外层的 floating_con
+        assert_eq!(
+            title_bar(94413212549216),
+            Some(Rect::new(700.0, 300.0, 500.0, 22.0))
+        );
+        // 像素边框没有标题栏
+        assert_eq!(title_bar(94413212553312), None);
+
+        let (node, floating) = tree.find_window(94413212547104).unwrap();
+        assert!(!floating);
+        assert_eq!(
+            node.frame(tree.find_parent(94413212547104).unwrap()),
+            Rect::new(960.0, 0.0, 960.0, 1060.0)
+        );
+        assert!(tree.find_window(94413212549216).unwrap().1);
+    }
+
+    #[test]
+    fn test_find_window() {
+        let tree: Node = serde_json::from_str(testing::TREE).unwrap();
+
+        assert!(!tree.find_window(8).unwrap().1);
+        assert!(tree.find_window(12).unwrap().1);
+        // 工作区本身不是窗口
+        assert!(tree.find_window(4).is_none());
+    }
+}
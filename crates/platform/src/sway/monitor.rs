@@ -0,0 +1,184 @@
+use anyhow::{Context, Result};
+use screenhop_core::{monitor, MonitorInfo, Rect};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::window::SwayWindowManager;
+use super::{read_message, IpcClient, Output, Workspace, EVENT_OUTPUT};
+use crate::{ChangeListeners, MonitorChangeCallback, MonitorManager, WindowHandle, WindowManager};
+
+/// i3 显示器管理器（基于 `GET_OUTPUTS` 与 `GET_WORKSPACES`）
+///
+/// 订阅变化后通过 `SUBSCRIBE ["output"]` 接收输出变化事件。
+pub struct SwayMonitorManager {
+    client: Arc<IpcClient>,
+    listeners: ChangeListeners,
+    watching: Arc<AtomicBool>,
+}
+
+/// sway 对未知字段填 "Unknown"
+fn known(value: &Option<String>) -> Option<String> {
+    value.clone().filter(|v| !v.is_empty() && v != "Unknown")
+}
+
+/// 把输出转换为显示器信息
+///
+/// 工作区矩形已经扣除了 bar 等保留区域，因此取该输出上可见工作区的矩形作为工作区域。
+pub(crate) fn output_to_monitor(
+    index: usize,
+    output: &Output,
+    workspaces: &[Workspace],
+) -> MonitorInfo {
+    let bounds = Rect::from(output.rect);
+    let work_area = workspaces
+        .iter()
+        .find(|ws| ws.visible && ws.output == output.name)
+        .and_then(|ws| bounds.intersection(&Rect::from(ws.rect)))
+        .unwrap_or(bounds);
+
+    MonitorInfo {
+        id: output.id.unwrap_or(index as u64),
+        bounds,
+        work_area,
+        name: known(&output.model).unwrap_or_else(|| output.name.clone()),
+        manufacturer: known(&output.make),
+        product_code: None,
+        serial: known(&output.serial),
+        connector: Some(output.name.clone()),
+        is_primary: output.primary,
+        // 输出矩形已是逻辑坐标
+        scale_factor: 1.0,
+    }
+}
+
+impl SwayMonitorManager {
+    pub fn new(client: Arc<IpcClient>) -> Self {
+        Self {
+            client,
+            listeners: ChangeListeners::default(),
+            watching: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 在后台线程中接收 output 事件
+    fn watch_changes(&self) -> Result<()> {
+        let mut stream = self.client.subscribe(&["output"])?;
+        let listeners = self.listeners.clone();
+        let watching = self.watching.clone();
+        watching.store(true, Ordering::SeqCst);
+
+        std::thread::Builder::new()
+            .name("i3-output-events".to_string())
+            .spawn(move || {
+                loop {
+                    match read_message(&mut stream) {
+                        Ok((EVENT_OUTPUT, _)) => {
+                            log::info!("显示器布局变化（i3 output 事件）");
+                            listeners.notify();
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::warn!("i3 事件连接断开: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                watching.store(false, Ordering::SeqCst);
+                listeners.notify();
+            })
+            .context("无法创建 i3 事件线程")?;
+
+        Ok(())
+    }
+}
+
+impl MonitorManager for SwayMonitorManager {
+    fn get_monitors(&self) -> Vec<MonitorInfo> {
+        let (outputs, workspaces) = match self
+            .client
+            .get_outputs()
+            .and_then(|outputs| Ok((outputs, self.client.get_workspaces()?)))
+        {
+            Ok(result) => result,
+            Err(e) => {
+                log::error!("获取 i3 输出失败: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut monitors: Vec<MonitorInfo> = outputs
+            .iter()
+            .enumerate()
+            .filter(|(_, output)| output.active)
+            .map(|(index, output)| output_to_monitor(index, output, &workspaces))
+            .collect();
+
+        monitor::sort_monitors(&mut monitors);
+        monitors
+    }
+
+    fn get_monitor_for_window(&self, handle: &WindowHandle) -> Option<MonitorInfo> {
+        let wm = SwayWindowManager::new(self.client.clone());
+        let frame = wm.get_window_frame(handle)?;
+        let monitors = self.get_monitors();
+
+        monitor::find_monitor_for_rect(&frame, &monitors).map(|idx| monitors[idx].clone())
+    }
+
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        if self.listeners.add(callback) {
+            if let Err(e) = self.watch_changes() {
+                log::error!("无法订阅 i3 输出事件: {}", e);
+            }
+        }
+        self.watching.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::FakeServer;
+    use super::*;
+
+    #[test]
+    fn test_get_monitors() {
+        let server = FakeServer::start();
+        let manager = SwayMonitorManager::new(server.client());
+        let monitors = manager.get_monitors();
+
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0].connector.as_deref(), Some("eDP-1"));
+        // 顶部 bar 占 30px
+        assert_eq!(monitors[0].work_area, Rect::new(0.0, 30.0, 1920.0, 1050.0));
+        assert_eq!(monitors[0].serial, None);
+
+        assert_eq!(monitors[1].name, "DELL U2720Q");
+        assert_eq!(monitors[1].manufacturer.as_deref(), Some("Dell Inc."));
+        assert_eq!(monitors[1].work_area, monitors[1].bounds);
+        assert_eq!(monitors[1].scale_factor, 1.0);
+    }
+
+    #[test]
+    fn test_get_monitor_for_window() {
+        let server = FakeServer::start();
+        let manager = SwayMonitorManager::new(server.client());
+
+        let monitor = manager
+            .get_monitor_for_window(&WindowHandle { inner: 20 })
+            .unwrap();
+        assert_eq!(monitor.connector.as_deref(), Some("HDMI-A-1"));
+    }
+
+    #[test]
+    fn test_output_event_notifies_subscribers() {
+        let server = FakeServer::start();
+        let manager = SwayMonitorManager::new(server.client());
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        assert!(manager.subscribe_changes(Box::new(move || {
+            let _ = tx.send(());
+        })));
+        rx.recv_timeout(std::time::Duration::from_secs(2)).unwrap();
+    }
+}
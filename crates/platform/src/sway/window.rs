@@ -0,0 +1,303 @@
+use anyhow::{Context, Result};
+use screenhop_core::rules::WindowInfo;
+use screenhop_core::{Point, Rect};
+use std::sync::Arc;
+
+use super::{IpcClient, Node};
+use crate::{WindowHandle, WindowManager};
+
+/// i3 窗口管理器（通过 IPC 命令操作容器）
+///
+/// 平铺窗口的位置和尺寸由布局决定，因此移动平铺窗口时只把它送到目标位置所在的输出，
+/// 设置尺寸则只对浮动窗口生效。“最大化”对应输出内全屏（`fullscreen_mode == 1`）。
+pub struct SwayWindowManager {
+    client: Arc<IpcClient>,
+}
+
+impl SwayWindowManager {
+    pub fn new(client: Arc<IpcClient>) -> Self {
+        Self { client }
+    }
+
+    /// 在最新的布局树中查找窗口，返回（窗口节点, 父容器, 是否浮动）
+    fn with_window<T>(
+        &self,
+        handle: &WindowHandle,
+        f: impl FnOnce(&Node, &Node, bool) -> T,
+    ) -> Option<T> {
+        let tree = match self.client.get_tree() {
+            Ok(tree) => tree,
+            Err(e) => {
+                log::error!("获取 i3 布局树失败: {}", e);
+                return None;
+            }
+        };
+
+        let (node, floating) = tree.find_window(handle.inner)?;
+        let parent = tree.find_parent(handle.inner)?;
+        Some(f(node, parent, floating))
+    }
+
+    fn run_for(&self, handle: &WindowHandle, command: &str) -> Result<()> {
+        self.client
+            .run_command(&format!("[con_id={}] {}", handle.inner, command))
+    }
+
+    /// 包含指定点的输出名称
+    fn output_at(&self, point: Point) -> Result<String> {
+        self.client
+            .get_outputs()?
+            .into_iter()
+            .find(|output| output.active && Rect::from(output.rect).contains(point))
+            .map(|output| output.name)
+            .with_context(|| format!("({}, {}) 不在任何输出上", point.x, point.y))
+    }
+}
+
+impl WindowManager for SwayWindowManager {
+    fn get_window_at(&self, point: Point) -> Option<WindowHandle> {
+        let visible = match self.client.visible_workspaces() {
+            Ok(visible) => visible,
+            Err(e) => {
+                log::error!("获取 i3 工作区失败: {}", e);
+                return None;
+            }
+        };
+        let tree = self.client.get_tree().ok()?;
+
+        tree.visible_windows_top_down(&visible)
+            .into_iter()
+            .find(|(node, parent)| {
+                Rect::from(node.rect).contains(point)
+                    || node
+                        .title_bar(parent)
+                        .is_some_and(|title_bar| title_bar.contains(point))
+            })
+            .map(|(node, _)| WindowHandle { inner: node.id })
+    }
+
+    fn get_focused_window(&self) -> Option<WindowHandle> {
+        let tree = match self.client.get_tree() {
+            Ok(tree) => tree,
+            Err(e) => {
+                log::error!("获取 i3 布局树失败: {}", e);
+                return None;
+            }
+        };
+
+        tree.find_focused_window()
+            .map(|node| WindowHandle { inner: node.id })
+    }
+
+    fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect> {
+        self.with_window(handle, |node, parent, _| node.frame(parent))
+    }
+
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo {
+        self.with_window(handle, |node, _, _| WindowInfo {
+            process_name: node.pid.and_then(crate::linux::process_name),
+            bundle_id: None,
+            class: node.app_id.clone().or_else(|| {
+                node.window_properties
+                    .as_ref()
+                    .and_then(|props| props.class.clone())
+            }),
+            title: node.name.clone(),
+        })
+        .unwrap_or_default()
+    }
+
+    fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()> {
+        let floating = self
+            .with_window(handle, |_, _, floating| floating)
+            .context("窗口已不存在")?;
+
+        if floating {
+            self.run_for(
+                handle,
+                &format!(
+                    "move absolute position {} px {} px",
+                    pos.x.round(),
+                    pos.y.round()
+                ),
+            )
+        } else {
+            let output = self.output_at(pos)?;
+            self.run_for(handle, &format!("move container to output \"{}\"", output))
+        }
+    }
+
+    fn set_window_size(&self, handle: &WindowHandle, width: f64, height: f64) -> Result<()> {
+        let floating = self
+            .with_window(handle, |_, _, floating| floating)
+            .context("窗口已不存在")?;
+
+        if !floating {
+            // 平铺窗口的尺寸由布局决定
+            return Ok(());
+        }
+
+        self.run_for(
+            handle,
+            &format!(
+                "resize set width {} px height {} px",
+                width.round(),
+                height.round()
+            ),
+        )
+    }
+
+    fn activate_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.run_for(handle, "focus")
+    }
+
+    fn is_maximized(&self, handle: &WindowHandle) -> bool {
+        self.with_window(handle, |node, _, _| node.fullscreen_mode == 1)
+            .unwrap_or(false)
+    }
+
+    fn restore_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.run_for(handle, "fullscreen disable")
+    }
+
+    fn maximize_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.run_for(handle, "fullscreen enable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{self, FakeServer};
+    use super::*;
+
+    #[test]
+    fn test_get_window_at() {
+        let server = FakeServer::start();
+        let wm = SwayWindowManager::new(server.client());
+
+        // 浮动窗口盖在平铺窗口之上
+        let handle = wm.get_window_at(Point { x: 700.0, y: 400.0 }).unwrap();
+        assert_eq!(handle.id(), 12);
+        // 标签布局中只有获得焦点的标签可见
+        let handle = wm
+            .get_window_at(Point {
+                x: 1500.0,
+                y: 100.0,
+            })
+            .unwrap();
+        assert_eq!(handle.id(), 10);
+        // 外框包含标签栏
+        assert_eq!(
+            wm.get_window_frame(&handle),
+            Some(Rect::new(960.0, 30.0, 960.0, 1050.0))
+        );
+
+        // 标题栏不在 rect 中，但属于窗口
+        let handle = wm.get_window_at(Point { x: 400.0, y: 40.0 }).unwrap();
+        assert_eq!(handle.id(), 8);
+        // 其他标签交给窗口管理器处理
+        assert!(wm.get_window_at(Point { x: 1500.0, y: 40.0 }).is_none());
+    }
+
+    #[test]
+    fn test_i3_get_window_at() {
+        let server = FakeServer::with_tree(testing::I3_TREE);
+        let wm = SwayWindowManager::new(server.client());
+
+        let handle = wm.get_window_at(Point { x: 900.0, y: 310.0 }).unwrap();
+        assert_eq!(handle.id(), 94413212549216);
+        assert_eq!(
+            wm.get_window_frame(&handle),
+            Some(Rect::new(700.0, 300.0, 500.0, 400.0))
+        );
+
+        let handle = wm.get_window_at(Point { x: 1500.0, y: 30.0 }).unwrap();
+        assert_eq!(handle.id(), 94413212547104);
+        // i3bar 所在的 dock 区域没有窗口
+        assert!(wm
+            .get_window_at(Point {
+                x: 400.0,
+                y: 1070.0
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_focused_window() {
+        let server = FakeServer::start();
+        let wm = SwayWindowManager::new(server.client());
+
+        assert_eq!(wm.get_focused_window().unwrap().id(), 10);
+    }
+
+    #[test]
+    fn test_get_window_info() {
+        let server = FakeServer::start();
+        let wm = SwayWindowManager::new(server.client());
+
+        let info = wm.get_window_info(&WindowHandle { inner: 12 });
+        assert_eq!(info.class.as_deref(), Some("pavucontrol"));
+        assert_eq!(info.title.as_deref(), Some("Volume Control"));
+
+        // Xwayland 窗口没有 app_id，使用 WM_CLASS
+        let info = wm.get_window_info(&WindowHandle { inner: 20 });
+        assert_eq!(info.class.as_deref(), Some("Gimp-2.10"));
+    }
+
+    #[test]
+    fn test_move_tiled_window_to_output() {
+        let server = FakeServer::start();
+        let wm = SwayWindowManager::new(server.client());
+        let handle = WindowHandle { inner: 8 };
+
+        wm.set_window_position(
+            &handle,
+            Point {
+                x: 2000.0,
+                y: 100.0,
+            },
+        )
+        .unwrap();
+        wm.set_window_size(&handle, 1280.0, 1440.0).unwrap();
+
+        assert_eq!(
+            server.commands(),
+            vec!["[con_id=8] move container to output \"HDMI-A-1\""]
+        );
+    }
+
+    #[test]
+    fn test_move_floating_window() {
+        let server = FakeServer::start();
+        let wm = SwayWindowManager::new(server.client());
+        let handle = WindowHandle { inner: 12 };
+
+        wm.set_window_position(
+            &handle,
+            Point {
+                x: 2560.0,
+                y: 520.4,
+            },
+        )
+        .unwrap();
+        wm.set_window_size(&handle, 800.0, 533.3).unwrap();
+
+        assert_eq!(
+            server.commands(),
+            vec![
+                "[con_id=12] move absolute position 2560 px 520 px",
+                "[con_id=12] resize set width 800 px height 533 px",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fullscreen_as_maximized() {
+        let server = FakeServer::start();
+        let wm = SwayWindowManager::new(server.client());
+
+        assert!(wm.is_maximized(&WindowHandle { inner: 20 }));
+        assert!(!wm.is_maximized(&WindowHandle { inner: 8 }));
+        assert!(wm.get_window_frame(&WindowHandle { inner: 99 }).is_none());
+    }
+}
@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 临时目录下本进程内唯一的路径（不创建文件，先清除上次运行留下的同名文件或目录）
+///
+/// 测试并行运行，路径中带上进程号和递增序号避免互相干扰。
+pub(crate) fn temp_path(prefix: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "{}_{}_{}",
+        prefix,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = std::fs::remove_dir_all(&path);
+    let _ = std::fs::remove_file(&path);
+    path
+}
@@ -0,0 +1,512 @@
+use anyhow::{bail, Context, Result};
+use evdev::{AttributeSet, Device, EventSummary, InputEvent, KeyCode, RelativeAxisCode};
+use inotify::{Inotify, WatchMask};
+use screenhop_core::trigger::Modifiers;
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::os::fd::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use super::CursorLocator;
+use crate::{MouseEvent, MouseHook};
+
+/// 输入设备目录
+const INPUT_DIR: &str = "/dev/input";
+
+/// 正在运行的监听线程
+struct HookThread {
+    /// 写入任意字节即可唤醒监听线程并让它退出
+    wakeup: UnixStream,
+    handle: JoinHandle<()>,
+}
+
+/// 基于 evdev 的鼠标钩子（Wayland 会话使用）
+///
+/// 直接读取 `/dev/input/event*`，需要用户在 `input` 组中。
+/// 读取不会独占设备，点击仍会送达合成器，因此回调的返回值会被忽略。
+/// 通过 inotify 监听 `/dev/input`，新插入的鼠标会自动加入。
+/// 键盘设备只在点击时查询修饰键状态，不读取按键事件。
+/// 每个事件都要经合成器 IPC 查询一次光标位置，因此只在启用滚轮触发时转发滚轮事件。
+pub struct EvdevMouseHook {
+    locator: Arc<dyn CursorLocator>,
+    /// 是否转发滚轮事件
+    forward_wheel: bool,
+    active: Arc<AtomicBool>,
+    thread: Option<HookThread>,
+}
+
+/// 可作为触发键的按键及其 `MouseEvent::button` 编号
+const TRIGGER_BUTTONS: [(KeyCode, u32); 5] = [
+    (KeyCode::BTN_MIDDLE, 2),
+    (KeyCode::BTN_SIDE, 3),
+    (KeyCode::BTN_BACK, 3),
+    (KeyCode::BTN_EXTRA, 4),
+    (KeyCode::BTN_FORWARD, 4),
+];
+
+/// 设备是否带有中键或侧键
+fn has_trigger_button(device: &Device) -> bool {
+    device
+        .supported_keys()
+        .is_some_and(|keys| TRIGGER_BUTTONS.iter().any(|(code, _)| keys.contains(*code)))
+}
+
+/// 设备是否为键盘（用来查询修饰键状态）
+fn is_keyboard(device: &Device) -> bool {
+    device
+        .supported_keys()
+        .is_some_and(|keys| keys.contains(KeyCode::KEY_LEFTCTRL) && keys.contains(KeyCode::KEY_A))
+}
+
+/// 中键或侧键按下事件对应的 `MouseEvent::button` 编号
+pub(crate) fn pressed_button(event: InputEvent) -> Option<u32> {
+    let EventSummary::Key(_, code, 1) = event.destructure() else {
+        return None;
+    };
+    TRIGGER_BUTTONS
+        .iter()
+        .find(|(button, _)| *button == code)
+        .map(|(_, number)| *number)
+}
+
+/// 滚轮事件对应的 `MouseEvent::wheel_delta`（REL_WHEEL 的格数，正数向上），其他事件返回 0
+pub(crate) fn wheel_delta(event: InputEvent) -> i32 {
+    match event.destructure() {
+        EventSummary::RelativeAxis(_, RelativeAxisCode::REL_WHEEL, value) => value,
+        _ => 0,
+    }
+}
+
+/// 从键盘的按键状态读取修饰键
+pub(crate) fn modifiers_from_keys(keys: &AttributeSet<KeyCode>) -> Modifiers {
+    let any = |codes: [KeyCode; 2]| codes.iter().any(|code| keys.contains(*code));
+    Modifiers {
+        ctrl: any([KeyCode::KEY_LEFTCTRL, KeyCode::KEY_RIGHTCTRL]),
+        alt: any([KeyCode::KEY_LEFTALT, KeyCode::KEY_RIGHTALT]),
+        shift: any([KeyCode::KEY_LEFTSHIFT, KeyCode::KEY_RIGHTSHIFT]),
+        cmd: any([KeyCode::KEY_LEFTMETA, KeyCode::KEY_RIGHTMETA]),
+    }
+}
+
+/// 打开的输入设备
+enum OpenedDevice {
+    /// 带中键或侧键，读取其按键事件
+    Mouse(Device),
+    /// 键盘，只查询修饰键状态
+    Keyboard(Device),
+}
+
+/// 打开鼠标（非阻塞）或键盘，其他设备返回 `Ok(None)`
+fn open_device(path: &Path) -> std::io::Result<Option<OpenedDevice>> {
+    let device = Device::open(path)?;
+    let name = device.name().unwrap_or("未知设备").to_string();
+
+    if has_trigger_button(&device) {
+        device.set_nonblocking(true)?;
+        log::info!("监听输入设备: {} ({})", path.display(), name);
+        return Ok(Some(OpenedDevice::Mouse(device)));
+    }
+    if is_keyboard(&device) {
+        log::debug!("键盘设备: {} ({})", path.display(), name);
+        return Ok(Some(OpenedDevice::Keyboard(device)));
+    }
+    Ok(None)
+}
+
+fn is_event_node(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("event"))
+}
+
+/// 检查当前用户能否读取输入设备
+///
+/// 有设备节点但全部没有读权限时（用户不在 `input` 组中）返回错误；
+/// 还没有任何设备时返回 `Ok`，之后插入的设备由热插拔监听处理。
+pub(crate) fn check_device_access() -> Result<()> {
+    let entries =
+        std::fs::read_dir(INPUT_DIR).with_context(|| format!("无法读取 {}", INPUT_DIR))?;
+
+    let mut denied = false;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if !is_event_node(&path) {
+            continue;
+        }
+        match std::fs::File::open(&path) {
+            Ok(_) => return Ok(()),
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => denied = true,
+            Err(_) => {}
+        }
+    }
+
+    if denied {
+        bail!("没有读取 {} 的权限，请把当前用户加入 input 组", INPUT_DIR);
+    }
+    Ok(())
+}
+
+/// 监听线程的状态
+struct Listener {
+    devices: HashMap<PathBuf, Device>,
+    keyboards: HashMap<PathBuf, Device>,
+    inotify: Inotify,
+    forward_wheel: bool,
+}
+
+impl Listener {
+    fn new(forward_wheel: bool) -> Result<Self> {
+        let inotify = Inotify::init().context("无法初始化 inotify")?;
+        inotify
+            .watches()
+            .add(INPUT_DIR, WatchMask::CREATE | WatchMask::ATTRIB)
+            .with_context(|| format!("无法监听 {}", INPUT_DIR))?;
+
+        let mut listener = Self {
+            devices: HashMap::new(),
+            keyboards: HashMap::new(),
+            inotify,
+            forward_wheel,
+        };
+
+        let mut denied = 0;
+        for entry in std::fs::read_dir(INPUT_DIR)?.flatten() {
+            match listener.try_add(entry.path()) {
+                Err(e) if e.kind() == ErrorKind::PermissionDenied => denied += 1,
+                _ => {}
+            }
+        }
+
+        if listener.devices.is_empty() && denied > 0 {
+            bail!("没有读取 {} 的权限，请把当前用户加入 input 组", INPUT_DIR);
+        }
+        Ok(listener)
+    }
+
+    fn try_add(&mut self, path: PathBuf) -> std::io::Result<()> {
+        if !is_event_node(&path)
+            || self.devices.contains_key(&path)
+            || self.keyboards.contains_key(&path)
+        {
+            return Ok(());
+        }
+        match open_device(&path)? {
+            Some(OpenedDevice::Mouse(device)) => {
+                self.devices.insert(path, device);
+            }
+            Some(OpenedDevice::Keyboard(device)) => {
+                self.keyboards.insert(path, device);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// 处理 inotify 事件：新设备节点出现或权限被 udev 修改后尝试打开
+    fn handle_hotplug(&mut self) {
+        let mut buffer = [0u8; 4096];
+        let names: Vec<PathBuf> = match self.inotify.read_events(&mut buffer) {
+            Ok(events) => events
+                .filter_map(|event| event.name.map(|name| Path::new(INPUT_DIR).join(name)))
+                .collect(),
+            Err(_) => return,
+        };
+
+        for path in names {
+            if let Err(e) = self.try_add(path.clone()) {
+                // udev 可能还没设置好权限，等待随后的 ATTRIB 事件
+                log::debug!("暂时无法打开 {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// 所有键盘上当前按下的修饰键；已拔出的键盘会被移除
+    fn modifiers(&mut self) -> Modifiers {
+        let mut modifiers = Modifiers::default();
+        self.keyboards
+            .retain(|path, device| match device.get_key_state() {
+                Ok(keys) => {
+                    let pressed = modifiers_from_keys(&keys);
+                    modifiers.ctrl |= pressed.ctrl;
+                    modifiers.alt |= pressed.alt;
+                    modifiers.shift |= pressed.shift;
+                    modifiers.cmd |= pressed.cmd;
+                    true
+                }
+                Err(e) => {
+                    log::debug!("键盘设备已移除: {} ({})", path.display(), e);
+                    false
+                }
+            });
+        modifiers
+    }
+
+    /// 读取可读设备的事件，返回按下的中键、侧键（按键编号, 0）和滚轮滚动（0, 滚动量）；
+    /// 未启用滚轮转发时忽略滚轮事件，已拔出的设备会被移除
+    fn read_devices(&mut self, ready: &[PathBuf]) -> Vec<(u32, i32)> {
+        let mut pressed = Vec::new();
+        let mut removed = Vec::new();
+
+        for path in ready {
+            let Some(device) = self.devices.get_mut(path) else {
+                continue;
+            };
+            match device.fetch_events() {
+                Ok(events) => {
+                    for event in events {
+                        if let Some(button) = pressed_button(event) {
+                            pressed.push((button, 0));
+                        }
+                        if !self.forward_wheel {
+                            continue;
+                        }
+                        let delta = wheel_delta(event);
+                        if delta != 0 {
+                            pressed.push((0, delta));
+                        }
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+                Err(e) => {
+                    log::info!("输入设备已移除: {} ({})", path.display(), e);
+                    removed.push(path);
+                }
+            }
+        }
+
+        for path in removed {
+            self.devices.remove(path);
+        }
+        pressed
+    }
+}
+
+impl EvdevMouseHook {
+    /// `forward_wheel` 为 false 时不转发滚轮事件，滚动不会触发 IPC 查询
+    pub fn new(locator: Arc<dyn CursorLocator>, forward_wheel: bool) -> Self {
+        Self {
+            locator,
+            forward_wheel,
+            active: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+}
+
+impl MouseHook for EvdevMouseHook {
+    fn start<F>(&mut self, callback: F) -> Result<()>
+    where
+        F: Fn(MouseEvent) -> bool + Send + 'static,
+    {
+        if self.thread.is_some() {
+            self.stop()?;
+        }
+
+        let mut listener = Listener::new(self.forward_wheel)?;
+        let (wakeup, mut wakeup_rx) = UnixStream::pair().context("无法创建唤醒管道")?;
+        let locator = self.locator.clone();
+        let active = self.active.clone();
+        active.store(true, Ordering::SeqCst);
+
+        let handle = std::thread::Builder::new()
+            .name("evdev-mouse-hook".to_string())
+            .spawn(move || {
+                loop {
+                    let paths: Vec<PathBuf> = listener.devices.keys().cloned().collect();
+                    let mut fds: Vec<libc::pollfd> =
+                        [wakeup_rx.as_raw_fd(), listener.inotify.as_raw_fd()]
+                            .into_iter()
+                            .chain(paths.iter().map(|p| listener.devices[p].as_raw_fd()))
+                            .map(|fd| libc::pollfd {
+                                fd,
+                                events: libc::POLLIN,
+                                revents: 0,
+                            })
+                            .collect();
+
+                    let ret =
+                        unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1) };
+                    if ret < 0 {
+                        let err = std::io::Error::last_os_error();
+                        if err.kind() == ErrorKind::Interrupted {
+                            continue;
+                        }
+                        log::error!("poll 输入设备失败: {}", err);
+                        break;
+                    }
+
+                    if fds[0].revents != 0 {
+                        let _ = wakeup_rx.read(&mut [0u8; 1]);
+                        break;
+                    }
+                    if fds[1].revents != 0 {
+                        listener.handle_hotplug();
+                    }
+
+                    let ready: Vec<PathBuf> = paths
+                        .into_iter()
+                        .zip(&fds[2..])
+                        .filter(|(_, fd)| fd.revents != 0)
+                        .map(|(path, _)| path)
+                        .collect();
+                    let pressed = listener.read_devices(&ready);
+                    if pressed.is_empty() {
+                        continue;
+                    }
+
+                    let Some(point) = locator.cursor_position() else {
+                        log::warn!("无法获取光标位置，忽略本次点击");
+                        continue;
+                    };
+                    let modifiers = listener.modifiers();
+                    for (button, wheel_delta) in pressed {
+                        callback(MouseEvent {
+                            point,
+                            button,
+                            wheel_delta,
+                            modifiers,
+                        });
+                    }
+                }
+
+                active.store(false, Ordering::SeqCst);
+            })
+            .context("无法创建鼠标钩子线程")?;
+
+        self.thread = Some(HookThread { wakeup, handle });
+        log::info!("evdev 鼠标钩子已启动");
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let Some(mut thread) = self.thread.take() else {
+            return Ok(());
+        };
+
+        thread.wakeup.write_all(&[1])?;
+        if thread.handle.join().is_err() {
+            log::error!("鼠标钩子线程异常退出");
+        }
+
+        self.active.store(false, Ordering::SeqCst);
+        log::info!("evdev 鼠标钩子已停止");
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evdev::uinput::VirtualDevice;
+    use evdev::EventType;
+    use screenhop_core::Point;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    struct FixedLocator(Point);
+
+    impl CursorLocator for FixedLocator {
+        fn cursor_position(&self) -> Option<Point> {
+            Some(self.0)
+        }
+    }
+
+    #[test]
+    fn test_pressed_button() {
+        let press = |code: KeyCode, value| InputEvent::new(EventType::KEY.0, code.code(), value);
+
+        assert_eq!(pressed_button(press(KeyCode::BTN_MIDDLE, 1)), Some(2));
+        assert_eq!(pressed_button(press(KeyCode::BTN_SIDE, 1)), Some(3));
+        assert_eq!(pressed_button(press(KeyCode::BTN_EXTRA, 1)), Some(4));
+        // 松开
+        assert_eq!(pressed_button(press(KeyCode::BTN_MIDDLE, 0)), None);
+        assert_eq!(pressed_button(press(KeyCode::BTN_LEFT, 1)), None);
+    }
+
+    #[test]
+    fn test_wheel_delta() {
+        let relative =
+            |code: RelativeAxisCode, value| InputEvent::new(EventType::RELATIVE.0, code.0, value);
+
+        assert_eq!(wheel_delta(relative(RelativeAxisCode::REL_WHEEL, 1)), 1);
+        assert_eq!(wheel_delta(relative(RelativeAxisCode::REL_WHEEL, -2)), -2);
+        // 高精度滚轮和水平滚动不处理
+        assert_eq!(
+            wheel_delta(relative(RelativeAxisCode::REL_WHEEL_HI_RES, 120)),
+            0
+        );
+        assert_eq!(wheel_delta(relative(RelativeAxisCode::REL_HWHEEL, 1)), 0);
+    }
+
+    #[test]
+    fn test_modifiers_from_keys() {
+        let keys: AttributeSet<KeyCode> = [
+            KeyCode::KEY_RIGHTCTRL,
+            KeyCode::KEY_LEFTMETA,
+            KeyCode::KEY_A,
+        ]
+        .into_iter()
+        .collect();
+        let modifiers = modifiers_from_keys(&keys);
+        assert!(modifiers.ctrl && modifiers.cmd);
+        assert!(!modifiers.alt && !modifiers.shift);
+    }
+
+    #[test]
+    fn test_is_event_node() {
+        assert!(is_event_node(Path::new("/dev/input/event12")));
+        assert!(!is_event_node(Path::new("/dev/input/mice")));
+        assert!(!is_event_node(Path::new("/dev/input/by-id")));
+    }
+
+    #[test]
+    #[ignore = "需要 /dev/uinput 的写权限和 /dev/input 的读权限"]
+    fn test_hotplugged_virtual_mouse() {
+        let point = Point { x: 120.0, y: 45.0 };
+        let (tx, rx) = mpsc::channel();
+        let mut hook = EvdevMouseHook::new(Arc::new(FixedLocator(point)), false);
+        hook.start(move |event| {
+            tx.send(event.point).unwrap();
+            false
+        })
+        .unwrap();
+
+        // 在钩子启动之后创建设备，验证热插拔
+        let keys: AttributeSet<KeyCode> = [KeyCode::BTN_LEFT, KeyCode::BTN_MIDDLE]
+            .into_iter()
+            .collect();
+        let axes: AttributeSet<RelativeAxisCode> =
+            [RelativeAxisCode::REL_X, RelativeAxisCode::REL_Y]
+                .into_iter()
+                .collect();
+        let mut device = VirtualDevice::builder()
+            .unwrap()
+            .name("screenhop test mouse")
+            .with_keys(&keys)
+            .unwrap()
+            .with_relative_axes(&axes)
+            .unwrap()
+            .build()
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+
+        let middle = KeyCode::BTN_MIDDLE.code();
+        device
+            .emit(&[InputEvent::new(EventType::KEY.0, middle, 1)])
+            .unwrap();
+        device
+            .emit(&[InputEvent::new(EventType::KEY.0, middle, 0)])
+            .unwrap();
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(2)).unwrap(), point);
+        hook.stop().unwrap();
+        assert!(!hook.is_active());
+    }
+}
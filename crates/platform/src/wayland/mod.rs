@@ -0,0 +1,51 @@
+pub mod hook;
+
+use anyhow::{bail, Result};
+use screenhop_core::Point;
+use std::sync::Arc;
+
+use crate::hyprland::{self, HyprlandClient};
+
+/// 光标位置查询
+///
+/// Wayland 下无法全局获取指针坐标，evdev 也只提供相对移动，
+/// 因此需要由合成器的 IPC 告知光标当前位置。
+pub trait CursorLocator: Send + Sync {
+    /// 光标在全局逻辑坐标系中的位置
+    fn cursor_position(&self) -> Option<Point>;
+}
+
+/// Wayland 平台实现集合（evdev 鼠标钩子 + 合成器 IPC）
+///
+/// 目前只有 Hyprland 能通过 IPC 提供光标位置；sway 的 IPC 没有对应的请求。
+pub struct WaylandPlatform {
+    pub hook: hook::EvdevMouseHook,
+    pub window_manager: hyprland::window::HyprlandWindowManager,
+    pub hit_tester: hyprland::hittest::HyprlandHitTester,
+    pub monitor_manager: hyprland::monitor::HyprlandMonitorManager,
+}
+
+impl WaylandPlatform {
+    /// `forward_wheel` 对应配置中的滚轮触发开关
+    ///
+    /// 没有输入设备的读权限时返回错误，调用方可以退回 X11（XWayland）。
+    /// sway 会话也返回错误，此时退回 X11 没有意义（见 [`crate::sway::SwayPlatform`]）。
+    pub fn new(forward_wheel: bool) -> Result<Self> {
+        if std::env::var_os("SWAYSOCK").is_some() {
+            bail!(
+                "暂不支持 sway：sway 的 IPC 无法提供光标位置，无法判断点击的是哪个窗口，\
+                 XWayland 下的 X11 钩子也收不到 sway 原生窗口的输入"
+            );
+        }
+        hook::check_device_access()?;
+        let platform = hyprland::HyprlandPlatform::new()?;
+        let locator = Arc::new(HyprlandClient::from_env()?);
+
+        Ok(Self {
+            hook: hook::EvdevMouseHook::new(locator, forward_wheel),
+            window_manager: platform.window_manager,
+            hit_tester: platform.hit_tester,
+            monitor_manager: platform.monitor_manager,
+        })
+    }
+}
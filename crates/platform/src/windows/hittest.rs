@@ -1,4 +1,4 @@
-use screenhop_core::Point;
+use screenhop_core::{Point, Rect};
 
 use crate::{HitTester, WindowHandle};
 
@@ -8,6 +8,14 @@ use windows::Win32::UI::WindowsAndMessaging::*;
 const WM_NCHITTEST: u32 = 0x0084;
 const HTCAPTION: i32 = 2;
 
+/// 点击是否落在窗口顶部 `height` 像素内
+unsafe fn is_within_top(hwnd: HWND, point: Point, height: f64) -> bool {
+    let mut rect = windows::Win32::Foundation::RECT::default();
+    GetWindowRect(hwnd, &mut rect).is_ok()
+        && point.y >= rect.top as f64
+        && point.y <= rect.top as f64 + height
+}
+
 /// Windows 命中检测器（基于 WM_NCHITTEST + UI Automation）
 pub struct WinHitTester;
 
@@ -46,20 +54,26 @@ impl HitTester for WinHitTester {
             let class = String::from_utf16_lossy(&class_name[..len as usize]);
 
             if class == "CabinetWClass" {
-                let mut rect = windows::Win32::Foundation::RECT::default();
-                if GetWindowRect(hwnd, &mut rect).is_ok() {
-                    let top_margin = 60; // Approximated title bar height for WinUI 3 tabs
-                    if point.y >= rect.top as f64 && point.y <= (rect.top + top_margin) as f64 {
-                        return true;
-                    }
-                }
+                // Approximated title bar height for WinUI 3 tabs
+                return is_within_top(hwnd, point, 60.0);
             }
 
             false
         }
     }
 
-    fn is_interactive_tab(&self, handle: &WindowHandle, _point: Point) -> bool {
+    fn is_title_bar_hit_with_height(
+        &self,
+        handle: &WindowHandle,
+        point: Point,
+        height: f64,
+    ) -> bool {
+        // 规则指定了高度时，自绘标题栏（WM_NCHITTEST 返回 HTCLIENT）按窗口顶部的高度判断
+        self.is_title_bar_hit(handle, point)
+            || unsafe { is_within_top(HWND(handle.inner.hwnd as *mut _), point, height) }
+    }
+
+    fn interactive_tab_regions(&self, handle: &WindowHandle) -> Vec<Rect> {
         // 检查是否在 Explorer 标签页上
         // 使用 UI Automation 检测（需要 IUIAutomation 接口）
         unsafe {
@@ -69,19 +83,19 @@ impl HitTester for WinHitTester {
             let mut class_name = [0u16; 256];
             let len = GetClassNameW(hwnd, &mut class_name);
             if len == 0 {
-                return false;
+                return Vec::new();
             }
 
             let class = String::from_utf16_lossy(&class_name[..len as usize]);
 
             // 仅对 Explorer 窗口检查标签页
             if class != "CabinetWClass" {
-                return false;
+                return Vec::new();
             }
 
             // TODO: 完整的 UI Automation 标签页检测
-            // 需要 IUIAutomation::ElementFromPoint 检查 ControlType
-            false
+            // 需要 IUIAutomation::ElementFromHandle 找到 TabItem 控件并读取 BoundingRectangle
+            Vec::new()
         }
     }
 }
@@ -1,12 +1,16 @@
 use anyhow::Result;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 
-use crate::MouseEvent;
+use crate::{MouseEvent, MouseHook};
+use screenhop_core::trigger::Modifiers;
 use screenhop_core::Point;
 
 use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+};
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 /// Windows 鼠标钩子实现（基于 WH_MOUSE_LL）
@@ -14,7 +18,7 @@ pub struct WinMouseHook {
     active: Arc<AtomicBool>,
 }
 
-type CallbackType = Box<dyn Fn(MouseEvent) -> bool + Send + Sync>;
+type CallbackType = Box<dyn Fn(MouseEvent) -> bool + Send>;
 
 // 由于 HHOOK 底层包含原生指针，没有实现 Send / Sync，我们需要用一个 Wrapper 来包裹它
 #[derive(Clone, Copy)]
@@ -48,12 +52,20 @@ impl WinMouseHook {
             active: Arc::new(AtomicBool::new(false)),
         }
     }
+}
 
+impl MouseHook for WinMouseHook {
     /// 安装 WH_MOUSE_LL 钩子
-    pub fn install_hook<F>(&mut self, callback: F) -> Result<()>
+    ///
+    /// 底层钩子的回调由安装线程的消息循环分发，必须在主线程调用。
+    fn start<F>(&mut self, callback: F) -> Result<()>
     where
-        F: Fn(MouseEvent) -> bool + Send + Sync + 'static,
+        F: Fn(MouseEvent) -> bool + Send + 'static,
     {
+        if self.is_active() {
+            self.stop()?;
+        }
+
         if let Ok(mut cb) = global_callback().lock() {
             *cb = Some(Box::new(callback));
         }
@@ -73,11 +85,13 @@ impl WinMouseHook {
     }
 
     /// 卸载钩子
-    pub fn uninstall_hook(&mut self) -> Result<()> {
+    fn stop(&mut self) -> Result<()> {
         unsafe {
             if let Ok(mut handle) = hook_handle().lock() {
                 if let Some(hook) = handle.take() {
-                    let _ = UnhookWindowsHookEx(hook.0);
+                    if let Err(e) = UnhookWindowsHookEx(hook.0) {
+                        log::warn!("卸载 WH_MOUSE_LL 钩子失败: {}", e);
+                    }
                 }
             }
         }
@@ -88,6 +102,53 @@ impl WinMouseHook {
         log::info!("WH_MOUSE_LL 鼠标钩子已卸载");
         Ok(())
     }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for WinMouseHook {
+    fn drop(&mut self) {
+        if self.is_active() {
+            let _ = self.stop();
+        }
+    }
+}
+
+const WM_MBUTTONDOWN: u32 = 0x0207;
+const WM_MOUSEWHEEL: u32 = 0x020A;
+const WM_XBUTTONDOWN: u32 = 0x020B;
+const WM_XBUTTONUP: u32 = 0x020C;
+const XBUTTON1: u32 = 0x0001;
+
+/// 已消费按下事件的侧键编号（0 表示没有）
+///
+/// 浏览器等程序在侧键松开时才执行“后退 / 前进”，因此松开事件也要一起吞掉。
+static SWALLOWED_XBUTTON: AtomicU32 = AtomicU32::new(0);
+
+/// 当前按下的修饰键
+unsafe fn current_modifiers() -> Modifiers {
+    let pressed = |key: VIRTUAL_KEY| (GetAsyncKeyState(key.0 as i32) as u16 & 0x8000) != 0;
+    Modifiers {
+        ctrl: pressed(VK_CONTROL),
+        alt: pressed(VK_MENU),
+        shift: pressed(VK_SHIFT),
+        cmd: pressed(VK_LWIN) || pressed(VK_RWIN),
+    }
+}
+
+/// 钩子消息对应的 `MouseEvent::button` 编号和 `wheel_delta`，不关心的消息返回 `None`
+fn mouse_input(msg: u32, mouse_data: u32) -> Option<(u32, i32)> {
+    match msg {
+        WM_MBUTTONDOWN => Some((2, 0)),
+        // mouseData 的高位字是 XBUTTON1 / XBUTTON2
+        WM_XBUTTONDOWN if (mouse_data >> 16) == XBUTTON1 => Some((3, 0)),
+        WM_XBUTTONDOWN => Some((4, 0)),
+        // 滚轮消息的高位字是有符号的滚动量（WHEEL_DELTA 的倍数，正数向上）
+        WM_MOUSEWHEEL => Some((0, (mouse_data >> 16) as u16 as i16 as i32)),
+        _ => None,
+    }
 }
 
 /// WH_MOUSE_LL 回调函数
@@ -100,9 +161,18 @@ unsafe extern "system" fn low_level_mouse_proc(
 
     if n_code >= 0 {
         let msg = w_param.0 as u32;
-        // WM_MBUTTONDOWN = 0x0207
-        if msg == 0x0207 {
-            let mouse_struct = &*(l_param.0 as *const MSLLHOOKSTRUCT);
+        let mouse_struct = &*(l_param.0 as *const MSLLHOOKSTRUCT);
+
+        if msg == WM_XBUTTONUP {
+            let button = if (mouse_struct.mouseData >> 16) == XBUTTON1 {
+                3
+            } else {
+                4
+            };
+            handled = SWALLOWED_XBUTTON
+                .compare_exchange(button, 0, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok();
+        } else if let Some((button, wheel_delta)) = mouse_input(msg, mouse_struct.mouseData) {
             let point = Point {
                 x: mouse_struct.pt.x as f64,
                 y: mouse_struct.pt.y as f64,
@@ -110,13 +180,18 @@ unsafe extern "system" fn low_level_mouse_proc(
 
             let event = MouseEvent {
                 point,
-                button: 2, // 中键
+                button,
+                wheel_delta,
+                modifiers: current_modifiers(),
             };
 
             if let Ok(cb_guard) = global_callback().lock() {
                 if let Some(ref callback) = *cb_guard {
                     if callback(event) {
                         handled = true;
+                        if button == 3 || button == 4 {
+                            SWALLOWED_XBUTTON.store(button, Ordering::SeqCst);
+                        }
                     }
                 }
             }
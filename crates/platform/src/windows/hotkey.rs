@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use screenhop_core::hotkey::{Key, KeyCombo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+
+use crate::KeyboardHook;
+
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT,
+    MOD_SHIFT, MOD_WIN,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetMessageW, PeekMessageW, PostThreadMessageW, MSG, PM_NOREMOVE, WM_HOTKEY, WM_QUIT,
+};
+
+/// 主键对应的虚拟键码
+pub(crate) fn virtual_key(key: Key) -> u32 {
+    match key {
+        // 字母和数字的虚拟键码与大写 ASCII 相同
+        Key::Letter(c) => c as u32,
+        Key::Digit(d) => b'0' as u32 + d as u32,
+        Key::F(n) => 0x70 + (n as u32 - 1),
+        Key::Left => 0x25,
+        Key::Up => 0x26,
+        Key::Right => 0x27,
+        Key::Down => 0x28,
+        Key::Home => 0x24,
+        Key::End => 0x23,
+        Key::PageUp => 0x21,
+        Key::PageDown => 0x22,
+        Key::Space => 0x20,
+        Key::Tab => 0x09,
+        Key::Enter => 0x0D,
+        Key::Escape => 0x1B,
+    }
+}
+
+fn hotkey_modifiers(combo: &KeyCombo) -> HOT_KEY_MODIFIERS {
+    let mut modifiers = MOD_NOREPEAT;
+    if combo.modifiers.ctrl {
+        modifiers |= MOD_CONTROL;
+    }
+    if combo.modifiers.alt {
+        modifiers |= MOD_ALT;
+    }
+    if combo.modifiers.shift {
+        modifiers |= MOD_SHIFT;
+    }
+    if combo.modifiers.cmd {
+        modifiers |= MOD_WIN;
+    }
+    modifiers
+}
+
+/// 正在运行的快捷键线程
+struct HotkeyThread {
+    thread_id: u32,
+    handle: JoinHandle<()>,
+}
+
+/// Windows 全局快捷键（基于 RegisterHotKey）
+///
+/// 快捷键注册在独立线程的消息队列上（hwnd 为空），WM_HOTKEY 由该线程的消息循环分发，
+/// 不依赖主线程的事件循环。
+pub struct WinKeyboardHook {
+    active: Arc<AtomicBool>,
+    thread: Option<HotkeyThread>,
+}
+
+impl Default for WinKeyboardHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WinKeyboardHook {
+    pub fn new() -> Self {
+        Self {
+            active: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+}
+
+/// 注册快捷键并运行消息循环，直到收到 WM_QUIT
+fn run_hotkey_loop<F>(hotkeys: Vec<KeyCombo>, callback: F, ready: mpsc::Sender<u32>)
+where
+    F: Fn(usize),
+{
+    unsafe {
+        // 先创建线程的消息队列，确保 PostThreadMessageW 不会丢失 WM_QUIT
+        let mut msg = MSG::default();
+        let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+
+        // 快捷键 ID 从 1 开始，对应 hotkeys 中的索引 + 1
+        let mut registered = Vec::new();
+        for (index, combo) in hotkeys.iter().enumerate() {
+            let id = index as i32 + 1;
+            match RegisterHotKey(None, id, hotkey_modifiers(combo), virtual_key(combo.key)) {
+                Ok(()) => {
+                    registered.push(id);
+                    log::info!("已注册快捷键 {}", combo);
+                }
+                Err(e) => log::warn!("快捷键 {} 已被其他程序占用: {}", combo, e),
+            }
+        }
+
+        let _ = ready.send(GetCurrentThreadId());
+
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            if msg.message == WM_HOTKEY {
+                if let Some(index) = (msg.wParam.0 as usize).checked_sub(1) {
+                    callback(index);
+                }
+            }
+        }
+
+        for id in registered {
+            let _ = UnregisterHotKey(None, id);
+        }
+    }
+}
+
+impl KeyboardHook for WinKeyboardHook {
+    fn start<F>(&mut self, hotkeys: &[KeyCombo], callback: F) -> Result<()>
+    where
+        F: Fn(usize) + Send + 'static,
+    {
+        if self.thread.is_some() {
+            self.stop()?;
+        }
+
+        let hotkeys = hotkeys.to_vec();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let active = self.active.clone();
+        active.store(true, Ordering::SeqCst);
+
+        let handle = std::thread::Builder::new()
+            .name("hotkey-listener".to_string())
+            .spawn(move || {
+                run_hotkey_loop(hotkeys, callback, ready_tx);
+                active.store(false, Ordering::SeqCst);
+            })
+            .context("无法创建快捷键线程")?;
+
+        let thread_id = ready_rx.recv().context("快捷键线程启动失败")?;
+        self.thread = Some(HotkeyThread { thread_id, handle });
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let Some(thread) = self.thread.take() else {
+            return Ok(());
+        };
+
+        unsafe {
+            PostThreadMessageW(thread.thread_id, WM_QUIT, WPARAM(0), LPARAM(0))
+                .context("无法通知快捷键线程退出")?;
+        }
+        if thread.handle.join().is_err() {
+            log::error!("快捷键线程异常退出");
+        }
+
+        self.active.store(false, Ordering::SeqCst);
+        log::info!("全局快捷键已注销");
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for WinKeyboardHook {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
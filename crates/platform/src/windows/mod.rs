@@ -1,4 +1,5 @@
 pub mod hook;
+pub mod hotkey;
 pub mod window;
 pub mod monitor;
 pub mod hittest;
@@ -10,9 +11,17 @@ pub struct WinWindowHandle {
     pub(crate) hwnd: isize, // HWND as raw pointer value
 }
 
+impl WinWindowHandle {
+    /// 窗口标识（HWND 的数值）
+    pub fn id(&self) -> u64 {
+        self.hwnd as u64
+    }
+}
+
 /// Windows 平台实现集合
 pub struct WinPlatform {
     pub hook: hook::WinMouseHook,
+    pub keyboard: hotkey::WinKeyboardHook,
     pub window_manager: window::WinWindowManager,
     pub hit_tester: hittest::WinHitTester,
     pub monitor_manager: monitor::WinMonitorManager,
@@ -29,6 +38,7 @@ impl WinPlatform {
     pub fn new() -> Self {
         Self {
             hook: hook::WinMouseHook::new(),
+            keyboard: hotkey::WinKeyboardHook::new(),
             window_manager: window::WinWindowManager::new(),
             hit_tester: hittest::WinHitTester::new(),
             monitor_manager: monitor::WinMonitorManager::new(),
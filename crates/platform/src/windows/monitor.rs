@@ -1,9 +1,22 @@
+use screenhop_core::edid::Edid;
 use screenhop_core::{MonitorInfo, Rect};
 
-use crate::{MonitorManager, WindowHandle, WindowManager};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
-use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+use crate::{ChangeListeners, MonitorChangeCallback, MonitorManager, WindowHandle, WindowManager};
+
+use windows::core::{w, HSTRING, PCWSTR};
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, LRESULT, RECT, WPARAM};
 use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_BINARY};
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+    TranslateMessage, EDD_GET_DEVICE_INTERFACE_NAME, MONITORINFOF_PRIMARY, MSG, SPI_SETWORKAREA,
+    WINDOW_EX_STYLE, WM_DISPLAYCHANGE, WM_SETTINGCHANGE, WNDCLASSW, WS_OVERLAPPED,
+};
 
 /// Windows 显示器管理器（基于 EnumDisplayMonitors）
 pub struct WinMonitorManager;
@@ -29,21 +42,120 @@ impl MonitorManager for WinMonitorManager {
             let _ = EnumDisplayMonitors(None, None, Some(enum_monitor_proc), data);
         }
 
+        screenhop_core::monitor::sort_monitors(&mut monitors);
         monitors
     }
 
     fn get_monitor_for_window(&self, handle: &WindowHandle) -> Option<MonitorInfo> {
         let wm = super::window::WinWindowManager::new();
         let frame = wm.get_window_frame(handle)?;
-        let center = screenhop_core::Point {
-            x: frame.mid_x(),
-            y: frame.mid_y(),
-        };
 
         let monitors = self.get_monitors();
-        screenhop_core::monitor::find_monitor_for_point(center, &monitors)
+        screenhop_core::monitor::find_monitor_for_rect(&frame, &monitors)
             .map(|idx| monitors[idx].clone())
     }
+
+    /// 通过隐藏窗口接收 WM_DISPLAYCHANGE 与工作区变化的 WM_SETTINGCHANGE
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        if display_listeners().add(callback) {
+            let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+            match std::thread::Builder::new()
+                .name("display-change-window".to_string())
+                .spawn(move || run_display_window(ready_tx))
+            {
+                // 等待窗口创建完成，确保返回值反映真实状态
+                Ok(_) => {
+                    let _ = ready_rx.recv();
+                }
+                Err(e) => log::error!("无法创建显示器变化监听线程: {}", e),
+            }
+        }
+        DISPLAY_WINDOW_RUNNING.load(Ordering::SeqCst)
+    }
+}
+
+// 显示器变化的订阅者，窗口过程不支持闭包捕获
+fn display_listeners() -> &'static ChangeListeners {
+    static LISTENERS: OnceLock<ChangeListeners> = OnceLock::new();
+    LISTENERS.get_or_init(ChangeListeners::default)
+}
+
+static DISPLAY_WINDOW_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 创建接收广播消息的隐藏顶层窗口并运行消息循环
+///
+/// 仅消息窗口（HWND_MESSAGE）收不到广播，因此使用一个从不显示的普通顶层窗口。
+fn run_display_window(ready: std::sync::mpsc::Sender<()>) {
+    // 提前返回（失败）时丢弃 ready，订阅方同样会停止等待
+    unsafe {
+        let instance = match GetModuleHandleW(None) {
+            Ok(instance) => instance,
+            Err(e) => {
+                log::error!("GetModuleHandleW 失败: {}", e);
+                return;
+            }
+        };
+
+        let class_name = w!("ScreenHopDisplayWatcher");
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(display_window_proc),
+            hInstance: instance.into(),
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+        if RegisterClassW(&class) == 0 {
+            log::error!("注册显示器监听窗口类失败");
+            return;
+        }
+
+        if let Err(e) = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            class_name,
+            w!("ScreenHop"),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            instance,
+            None,
+        ) {
+            log::error!("创建显示器监听窗口失败: {}", e);
+            return;
+        }
+
+        DISPLAY_WINDOW_RUNNING.store(true, Ordering::SeqCst);
+        let _ = ready.send(());
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        DISPLAY_WINDOW_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+unsafe extern "system" fn display_window_proc(
+    hwnd: HWND,
+    msg: u32,
+    w_param: WPARAM,
+    l_param: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_DISPLAYCHANGE => {
+            log::info!("显示器布局变化（WM_DISPLAYCHANGE）");
+            display_listeners().notify();
+        }
+        WM_SETTINGCHANGE if w_param.0 as u32 == SPI_SETWORKAREA.0 => {
+            display_listeners().notify();
+        }
+        _ => {}
+    }
+
+    DefWindowProcW(hwnd, msg, w_param, l_param)
 }
 
 unsafe extern "system" fn enum_monitor_proc(
@@ -54,12 +166,16 @@ unsafe extern "system" fn enum_monitor_proc(
 ) -> BOOL {
     let monitors = &mut *(data.0 as *mut Vec<MonitorInfo>);
 
-    let mut info = MONITORINFO {
-        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+    let mut info_ex = MONITORINFOEXW {
+        monitorInfo: MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFOEXW>() as u32,
+            ..Default::default()
+        },
         ..Default::default()
     };
 
-    if GetMonitorInfoW(hmonitor, &mut info).as_bool() {
+    if GetMonitorInfoW(hmonitor, &mut info_ex as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+        let info = info_ex.monitorInfo;
         let bounds = Rect::new(
             info.rcMonitor.left as f64,
             info.rcMonitor.top as f64,
@@ -74,12 +190,99 @@ unsafe extern "system" fn enum_monitor_proc(
             (info.rcWork.bottom - info.rcWork.top) as f64,
         );
 
+        // 适配器设备名（如 \\.\DISPLAY1），作为连接器名称
+        let connector = wide_to_string(&info_ex.szDevice);
+        let device = query_monitor_device(&info_ex.szDevice);
+        let edid = device
+            .as_ref()
+            .and_then(|d| read_edid(&wide_to_string(&d.DeviceID)));
+
+        let name = edid
+            .as_ref()
+            .and_then(|e| e.monitor_name.clone())
+            .or_else(|| device.as_ref().map(|d| wide_to_string(&d.DeviceString)))
+            .unwrap_or_else(|| connector.clone());
+
         monitors.push(MonitorInfo {
             id: hmonitor.0 as u64,
             bounds,
             work_area,
+            name,
+            manufacturer: edid.as_ref().map(|e| e.manufacturer.clone()),
+            product_code: edid.as_ref().map(|e| e.product_code),
+            serial: edid.as_ref().and_then(|e| e.serial()),
+            connector: Some(connector),
+            is_primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+            scale_factor: monitor_scale_factor(hmonitor),
         });
     }
 
     BOOL(1) // 继续枚举
 }
+
+/// 显示器的有效缩放比例（DPI / 96）
+///
+/// 进程在清单中声明了 Per-Monitor V2 DPI 感知，钩子坐标、窗口矩形和显示器矩形均为物理像素。
+unsafe fn monitor_scale_factor(hmonitor: HMONITOR) -> f64 {
+    let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+    match GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) {
+        Ok(()) if dpi_x > 0 => dpi_x as f64 / 96.0,
+        _ => 1.0,
+    }
+}
+
+/// 查询连接在指定适配器上的显示器设备（DeviceID 为设备接口路径）
+unsafe fn query_monitor_device(adapter_name: &[u16]) -> Option<DISPLAY_DEVICEW> {
+    let mut device = DISPLAY_DEVICEW {
+        cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+        ..Default::default()
+    };
+
+    EnumDisplayDevicesW(
+        PCWSTR(adapter_name.as_ptr()),
+        0,
+        &mut device,
+        EDD_GET_DEVICE_INTERFACE_NAME,
+    )
+    .as_bool()
+    .then_some(device)
+}
+
+/// 从注册表读取显示器 EDID
+///
+/// 设备接口路径形如 `\\?\DISPLAY#DEL4109#5&2b5d3c5b&0&UID4353#{e6f07b5f-...}`，
+/// 对应注册表 `HKLM\SYSTEM\CurrentControlSet\Enum\DISPLAY\DEL4109\5&2b5d3c5b&0&UID4353\Device Parameters\EDID`。
+unsafe fn read_edid(device_id: &str) -> Option<Edid> {
+    let mut parts = device_id.split('#');
+    let (_, model, instance) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let subkey = HSTRING::from(format!(
+        "SYSTEM\\CurrentControlSet\\Enum\\DISPLAY\\{}\\{}\\Device Parameters",
+        model, instance
+    ));
+
+    let mut buffer = [0u8; 1024];
+    let mut len = buffer.len() as u32;
+    let status = RegGetValueW(
+        HKEY_LOCAL_MACHINE,
+        PCWSTR(subkey.as_ptr()),
+        w!("EDID"),
+        RRF_RT_REG_BINARY,
+        None,
+        Some(buffer.as_mut_ptr() as *mut _),
+        Some(&mut len),
+    );
+
+    if status.is_err() {
+        log::debug!("读取 EDID 失败: {}", device_id);
+        return None;
+    }
+
+    Edid::parse(&buffer[..len as usize]).ok()
+}
+
+/// 将以 NUL 结尾的 UTF-16 缓冲区转换为字符串
+fn wide_to_string(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
@@ -1,11 +1,16 @@
 use anyhow::Result;
+use screenhop_core::rules::WindowInfo;
 use screenhop_core::{Point, Rect};
 
 use super::WinWindowHandle;
 use crate::{WindowHandle, WindowManager};
 
+use windows::core::PWSTR;
 use windows::Win32::Foundation::POINT as WINPOINT;
-use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Foundation::{CloseHandle, HWND, RECT};
+use windows::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+};
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 /// Windows 窗口管理器（基于 Win32 API）
@@ -23,6 +28,38 @@ impl WinWindowManager {
     }
 }
 
+/// UTF-16 缓冲区中前 `len` 个字符，为空时返回 `None`
+fn utf16_string(buffer: &[u16], len: i32) -> Option<String> {
+    let len = usize::try_from(len).ok()?.min(buffer.len());
+    (len > 0).then(|| String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// 窗口所属进程的可执行文件名（如 `mstsc.exe`）
+///
+/// 只需要 PROCESS_QUERY_LIMITED_INFORMATION 权限，以管理员身份运行的进程也能查询。
+unsafe fn process_name(hwnd: HWND) -> Option<String> {
+    let mut pid = 0u32;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    if pid == 0 {
+        return None;
+    }
+
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+    let mut buffer = [0u16; 1024];
+    let mut len = buffer.len() as u32;
+    let result = QueryFullProcessImageNameW(
+        process,
+        PROCESS_NAME_WIN32,
+        PWSTR(buffer.as_mut_ptr()),
+        &mut len,
+    );
+    let _ = CloseHandle(process);
+    result.ok()?;
+
+    let path = utf16_string(&buffer, len as i32)?;
+    path.rsplit(['\\', '/']).next().map(str::to_string)
+}
+
 impl WindowManager for WinWindowManager {
     fn get_window_at(&self, point: Point) -> Option<WindowHandle> {
         unsafe {
@@ -53,6 +90,24 @@ impl WindowManager for WinWindowManager {
         }
     }
 
+    fn get_focused_window(&self) -> Option<WindowHandle> {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            // 桌面获得焦点时不移动
+            if hwnd.0.is_null() || hwnd == GetShellWindow() {
+                return None;
+            }
+
+            let root = GetAncestor(hwnd, GA_ROOT);
+            let top = if root.0.is_null() { hwnd } else { root };
+            Some(WindowHandle {
+                inner: WinWindowHandle {
+                    hwnd: top.0 as isize,
+                },
+            })
+        }
+    }
+
     fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect> {
         unsafe {
             let hwnd = HWND(handle.inner.hwnd as *mut _);
@@ -70,6 +125,24 @@ impl WindowManager for WinWindowManager {
         }
     }
 
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo {
+        unsafe {
+            let hwnd = HWND(handle.inner.hwnd as *mut _);
+
+            let mut class = [0u16; 256];
+            let class_len = GetClassNameW(hwnd, &mut class);
+            let mut title = [0u16; 512];
+            let title_len = GetWindowTextW(hwnd, &mut title);
+
+            WindowInfo {
+                process_name: process_name(hwnd),
+                bundle_id: None,
+                class: utf16_string(&class, class_len),
+                title: utf16_string(&title, title_len),
+            }
+        }
+    }
+
     fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()> {
         unsafe {
             let hwnd = HWND(handle.inner.hwnd as *mut _);
@@ -0,0 +1,389 @@
+use anyhow::{Context, Result};
+use screenhop_core::hotkey::HotkeyAction;
+use screenhop_core::rules::RuleOverrides;
+use screenhop_core::Point;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::engine::HopEngine;
+use crate::{HitTester, MonitorManager, MouseEvent, WindowHandle, WindowManager};
+
+/// 点击判定超过该耗时时发出警告
+///
+/// Windows 会静默移除超过 `LowLevelHooksTimeout` 的 WH_MOUSE_LL 钩子，
+/// macOS 也会禁用回调超时的 CGEventTap。
+const SLOW_DECISION: Duration = Duration::from_millis(50);
+
+/// 某一阶段的耗时统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageLatency {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+    pub last: Duration,
+}
+
+impl StageLatency {
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        self.last = elapsed;
+    }
+
+    /// 平均耗时
+    pub fn average(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.count as u32
+    }
+}
+
+/// 点击处理的耗时统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// 钩子回调中的点击判定
+    pub decision: StageLatency,
+    /// 请求在队列中等待工作线程的时间
+    pub queue: StageLatency,
+    /// 工作线程移动窗口的时间
+    pub hop: StageLatency,
+}
+
+/// 移动请求的来源
+#[derive(Debug, Clone, Copy)]
+enum HopKind {
+    /// 标题栏点击，移到下一个显示器
+    Click(Point),
+    /// 快捷键，按动作选择目标显示器
+    Hotkey(HotkeyAction),
+    /// 标题栏上的滚轮滚动，移到上一个或下一个显示器
+    Wheel(Point, HotkeyAction),
+}
+
+impl std::fmt::Display for HopKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HopKind::Click(point) => write!(f, "点击 ({:.0},{:.0})", point.x, point.y),
+            HopKind::Hotkey(action) => write!(f, "快捷键 {:?}", action),
+            HopKind::Wheel(point, action) => {
+                write!(f, "滚轮 ({:.0},{:.0}) {:?}", point.x, point.y, action)
+            }
+        }
+    }
+}
+
+/// 交给工作线程的移动请求
+struct HopRequest {
+    handle: WindowHandle,
+    /// 判定时解析的应用规则，工作线程不再查询窗口属性
+    overrides: RuleOverrides,
+    kind: HopKind,
+    queued_at: Instant,
+}
+
+/// 工作线程的任务
+enum Task {
+    Hop(HopRequest),
+    /// 撤销最近一次移动
+    Undo,
+    /// 重做最近一次撤销的移动
+    Redo,
+}
+
+/// 异步跳转：钩子回调中只做快速判定，窗口移动在工作线程中完成
+///
+/// 系统钩子回调必须尽快返回，而移动窗口涉及 AX / Win32 调用和显示器枚举，
+/// 可能耗时数十到数百毫秒。
+pub struct HopWorker<W, H, M> {
+    engine: Arc<HopEngine<W, H, M>>,
+    sender: Option<Sender<Task>>,
+    thread: Option<JoinHandle<()>>,
+    stats: Arc<Mutex<LatencyStats>>,
+}
+
+impl<W, H, M> HopWorker<W, H, M>
+where
+    W: WindowManager + Send + Sync + 'static,
+    H: HitTester + Send + Sync + 'static,
+    M: MonitorManager + Send + Sync + 'static,
+{
+    /// 启动工作线程
+    pub fn spawn(engine: Arc<HopEngine<W, H, M>>) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel::<Task>();
+        let stats = Arc::new(Mutex::new(LatencyStats::default()));
+
+        let worker_engine = engine.clone();
+        let worker_stats = stats.clone();
+        let thread = std::thread::Builder::new()
+            .name("hop-worker".to_string())
+            .spawn(move || {
+                for task in receiver {
+                    let request = match task {
+                        Task::Hop(request) => request,
+                        Task::Undo => {
+                            worker_engine.undo_hop();
+                            continue;
+                        }
+                        Task::Redo => {
+                            worker_engine.redo_hop();
+                            continue;
+                        }
+                    };
+
+                    let started = Instant::now();
+                    let queued = started - request.queued_at;
+                    let moved = match request.kind {
+                        HopKind::Click(_) => {
+                            worker_engine.hop_window(&request.handle, &request.overrides)
+                        }
+                        HopKind::Hotkey(action) | HopKind::Wheel(_, action) => {
+                            worker_engine.hop_window_to(&request.handle, &request.overrides, action)
+                        }
+                    };
+                    let elapsed = started.elapsed();
+
+                    log::debug!(
+                        "{} 处理完成: 排队 {:?}, 移动 {:?}{}",
+                        request.kind,
+                        queued,
+                        elapsed,
+                        if moved { "" } else { "（未移动）" },
+                    );
+
+                    let mut stats = worker_stats.lock().unwrap();
+                    stats.queue.record(queued);
+                    stats.hop.record(elapsed);
+                }
+            })
+            .context("无法创建窗口移动线程")?;
+
+        Ok(Self {
+            engine,
+            sender: Some(sender),
+            thread: Some(thread),
+            stats,
+        })
+    }
+
+    /// 处理钩子上报的鼠标事件：不满足触发条件的事件直接放行
+    pub fn handle_event(&self, event: &MouseEvent) -> bool {
+        if event.wheel_delta != 0 {
+            return match self.engine.wheel_action(event) {
+                Some(action) => self.handle_wheel(event.point, action),
+                None => false,
+            };
+        }
+        self.engine.matches_trigger(event) && self.handle_click(event.point)
+    }
+
+    /// 处理一次点击（在钩子回调中调用）
+    /// 返回 true 表示事件已消费（窗口将在工作线程中移动），返回 false 表示放行事件
+    pub fn handle_click(&self, point: Point) -> bool {
+        match self.decide(|| self.engine.hit_window(point)) {
+            Some((handle, overrides)) => self.enqueue(handle, overrides, HopKind::Click(point)),
+            None => false,
+        }
+    }
+
+    /// 处理一次已通过防抖的滚轮滚动（在钩子回调中调用），只在标题栏上生效
+    pub fn handle_wheel(&self, point: Point, action: HotkeyAction) -> bool {
+        match self.decide(|| self.engine.hit_title_bar(point)) {
+            Some((handle, overrides)) => {
+                self.enqueue(handle, overrides, HopKind::Wheel(point, action))
+            }
+            None => false,
+        }
+    }
+
+    /// 执行命中判定并记录耗时
+    fn decide(
+        &self,
+        hit: impl FnOnce() -> Option<(WindowHandle, RuleOverrides)>,
+    ) -> Option<(WindowHandle, RuleOverrides)> {
+        let started = Instant::now();
+        let target = hit();
+        let elapsed = started.elapsed();
+
+        self.stats.lock().unwrap().decision.record(elapsed);
+        if elapsed > SLOW_DECISION {
+            log::warn!("点击判定耗时 {:?}，系统可能因超时移除鼠标钩子", elapsed);
+        }
+        target
+    }
+
+    /// 处理一次快捷键：取当前焦点窗口，交给工作线程按动作移动
+    pub fn handle_hotkey(&self, action: HotkeyAction) -> bool {
+        match action {
+            HotkeyAction::Undo => return self.undo(),
+            HotkeyAction::Redo => return self.redo(),
+            _ => {}
+        }
+
+        match self.engine.window_manager().get_focused_window() {
+            Some(handle) => {
+                let overrides = self.engine.overrides(&handle);
+                self.enqueue(handle, overrides, HopKind::Hotkey(action))
+            }
+            None => {
+                log::debug!("没有获得焦点的窗口，忽略快捷键 {:?}", action);
+                false
+            }
+        }
+    }
+
+    /// 在工作线程中撤销最近一次移动
+    pub fn undo(&self) -> bool {
+        self.send(Task::Undo)
+    }
+
+    /// 在工作线程中重做最近一次撤销的移动
+    pub fn redo(&self) -> bool {
+        self.send(Task::Redo)
+    }
+
+    fn enqueue(&self, handle: WindowHandle, overrides: RuleOverrides, kind: HopKind) -> bool {
+        self.send(Task::Hop(HopRequest {
+            handle,
+            overrides,
+            kind,
+            queued_at: Instant::now(),
+        }))
+    }
+
+    fn send(&self, task: Task) -> bool {
+        match self.sender.as_ref().map(|sender| sender.send(task)) {
+            Some(Ok(())) => true,
+            _ => {
+                log::error!("窗口移动线程已退出，放行本次事件");
+                false
+            }
+        }
+    }
+
+    /// 目前为止的耗时统计
+    pub fn stats(&self) -> LatencyStats {
+        *self.stats.lock().unwrap()
+    }
+
+    pub fn engine(&self) -> &HopEngine<W, H, M> {
+        &self.engine
+    }
+}
+
+impl<W, H, M> Drop for HopWorker<W, H, M> {
+    /// 处理完队列中剩余的请求后结束工作线程
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::error!("窗口移动线程异常退出");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake::{FakeDesktop, FakeHitTester, FakeMonitorManager, FakeWindowManager};
+    use screenhop_core::config::AppConfig;
+    use screenhop_core::{MonitorInfo, Rect};
+
+    type FakeWorker = HopWorker<FakeWindowManager, FakeHitTester, FakeMonitorManager>;
+
+    fn make_monitor(id: u64, x: f64) -> MonitorInfo {
+        MonitorInfo {
+            id,
+            name: format!("monitor-{}", id),
+            bounds: Rect::new(x, 0.0, 1920.0, 1080.0),
+            work_area: Rect::new(x, 0.0, 1920.0, 1080.0),
+            ..Default::default()
+        }
+    }
+
+    fn worker(desktop: &FakeDesktop) -> FakeWorker {
+        let engine = HopEngine::new(
+            desktop.window_manager(),
+            desktop.hit_tester(),
+            desktop.monitor_manager(),
+            AppConfig::default(),
+        );
+        HopWorker::spawn(Arc::new(engine)).unwrap()
+    }
+
+    #[test]
+    fn test_click_is_moved_on_worker() {
+        let desktop = FakeDesktop::new(vec![make_monitor(1, 0.0), make_monitor(2, 1920.0)]);
+        let id = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        let worker = worker(&desktop);
+
+        assert!(worker.handle_click(Point { x: 500.0, y: 110.0 }));
+        // 内容区的点击直接放行，不进入队列
+        assert!(!worker.handle_click(Point { x: 500.0, y: 400.0 }));
+
+        let stats = worker.stats();
+        assert_eq!(stats.decision.count, 2);
+
+        // 丢弃时会等待队列处理完毕
+        let stats_handle = worker.stats.clone();
+        drop(worker);
+        assert!(desktop.window_frame(id).unwrap().x >= 1920.0);
+        let stats = *stats_handle.lock().unwrap();
+        assert_eq!(stats.hop.count, 1);
+        assert_eq!(stats.queue.count, 1);
+    }
+
+    #[test]
+    fn test_hotkey_is_moved_on_worker() {
+        let desktop = FakeDesktop::new(vec![make_monitor(1, 0.0), make_monitor(2, 1920.0)]);
+        let id = desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        let worker = worker(&desktop);
+
+        // 没有焦点窗口
+        assert!(!worker.handle_hotkey(HotkeyAction::Next));
+
+        desktop.focus(id);
+        assert!(worker.handle_hotkey(HotkeyAction::Next));
+        drop(worker);
+        assert!(desktop.window_frame(id).unwrap().x >= 1920.0);
+    }
+
+    #[test]
+    fn test_undo_runs_on_worker() {
+        let desktop = FakeDesktop::new(vec![make_monitor(1, 0.0), make_monitor(2, 1920.0)]);
+        let frame = Rect::new(100.0, 100.0, 800.0, 600.0);
+        let id = desktop.add_window(frame);
+        let worker = worker(&desktop);
+
+        assert!(worker.handle_click(Point { x: 500.0, y: 110.0 }));
+        // 撤销不需要焦点窗口，按顺序排在移动之后执行
+        assert!(worker.handle_hotkey(HotkeyAction::Undo));
+        drop(worker);
+        assert_eq!(desktop.window_frame(id), Some(frame));
+    }
+
+    #[test]
+    fn test_single_monitor_is_passed_through() {
+        let desktop = FakeDesktop::new(vec![make_monitor(1, 0.0)]);
+        desktop.add_window(Rect::new(100.0, 100.0, 800.0, 600.0));
+        let worker = worker(&desktop);
+
+        assert!(!worker.handle_click(Point { x: 500.0, y: 110.0 }));
+    }
+
+    #[test]
+    fn test_stage_latency_average() {
+        let mut stage = StageLatency::default();
+        assert_eq!(stage.average(), Duration::ZERO);
+
+        stage.record(Duration::from_millis(10));
+        stage.record(Duration::from_millis(30));
+        assert_eq!(stage.average(), Duration::from_millis(20));
+        assert_eq!(stage.max, Duration::from_millis(30));
+        assert_eq!(stage.last, Duration::from_millis(30));
+    }
+}
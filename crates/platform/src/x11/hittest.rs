@@ -0,0 +1,144 @@
+use screenhop_core::{Point, Rect};
+use std::sync::Arc;
+use x11rb::protocol::xproto::Window;
+
+use super::window::{FrameExtents, X11WindowManager};
+use super::X11Connection;
+use crate::{HitTester, WindowHandle};
+
+/// 根据窗口装饰信息计算标题栏区域（根窗口坐标）
+///
+/// - 有 `_NET_FRAME_EXTENTS` 且顶部装饰不为 0：标题栏就是窗口管理器画的顶部装饰带
+/// - 有 `_GTK_FRAME_EXTENTS`（客户端装饰）：扣除阴影后，从可见区域顶部量取 `height`
+///   （GTK 不公布 header bar 的高度，只能使用配置的标题栏高度）
+/// - 只有 `_NET_FRAME_EXTENTS` 且顶部为 0：无装饰窗口，没有标题栏
+/// - 两个属性都没有：从客户区顶部量取 `height`
+pub(crate) fn title_bar_rect(
+    client: &Rect,
+    net: Option<FrameExtents>,
+    gtk: Option<FrameExtents>,
+    height: f64,
+) -> Option<Rect> {
+    if let Some(net) = net.filter(|net| net.top > 0.0) {
+        let outer = net.outer(client);
+        return Some(Rect::new(outer.x, outer.y, outer.width, net.top));
+    }
+
+    let area = match (net, gtk) {
+        (_, Some(gtk)) => gtk.inner(client),
+        (Some(_), None) => return None,
+        (None, None) => *client,
+    };
+
+    Some(Rect::new(
+        area.x,
+        area.y,
+        area.width,
+        height.min(area.height),
+    ))
+}
+
+/// Linux X11 命中检测器
+pub struct X11HitTester {
+    conn: Arc<X11Connection>,
+    /// 客户端装饰窗口和没有装饰信息的窗口使用的标题栏高度
+    title_bar_height: f64,
+}
+
+impl X11HitTester {
+    pub fn new(conn: Arc<X11Connection>) -> Self {
+        Self {
+            conn,
+            title_bar_height: 40.0,
+        }
+    }
+
+    pub fn set_title_bar_height(&mut self, height: f64) {
+        self.title_bar_height = height;
+    }
+
+    fn hit(&self, handle: &WindowHandle, point: Point, height: f64) -> bool {
+        let wm = X11WindowManager::new(self.conn.clone());
+        let window = handle.inner as Window;
+
+        let Some(client) = wm.client_rect(window) else {
+            return false;
+        };
+
+        title_bar_rect(
+            &client,
+            wm.net_frame_extents(window),
+            wm.gtk_frame_extents(window),
+            height,
+        )
+        .is_some_and(|title_bar| title_bar.contains(point))
+    }
+}
+
+impl HitTester for X11HitTester {
+    fn is_title_bar_hit(&self, handle: &WindowHandle, point: Point) -> bool {
+        self.hit(handle, point, self.title_bar_height)
+    }
+
+    fn is_title_bar_hit_with_height(
+        &self,
+        handle: &WindowHandle,
+        point: Point,
+        height: f64,
+    ) -> bool {
+        self.hit(handle, point, height)
+    }
+
+    fn interactive_tab_regions(&self, _handle: &WindowHandle) -> Vec<Rect> {
+        // X11 没有统一的无障碍树可查询标签页，不做检测
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extents(left: u32, right: u32, top: u32, bottom: u32) -> Option<FrameExtents> {
+        FrameExtents::from_property(&[left, right, top, bottom])
+    }
+
+    #[test]
+    fn test_title_bar_from_net_frame_extents() {
+        let client = Rect::new(102.0, 124.0, 800.0, 600.0);
+        let title_bar = title_bar_rect(&client, extents(2, 2, 24, 2), None, 40.0).unwrap();
+
+        // 使用实际装饰高度，而不是配置的 40px
+        assert_eq!(title_bar, Rect::new(100.0, 100.0, 804.0, 24.0));
+    }
+
+    #[test]
+    fn test_title_bar_from_gtk_frame_extents() {
+        // 客户端装饰窗口：四周 26px 阴影，窗口管理器不画装饰
+        let client = Rect::new(74.0, 74.0, 852.0, 652.0);
+        let title_bar =
+            title_bar_rect(&client, extents(0, 0, 0, 0), extents(26, 26, 26, 26), 40.0).unwrap();
+
+        // 从扣除阴影后的可见区域顶部量取配置的高度，阴影内的点击不算标题栏
+        assert_eq!(title_bar, Rect::new(100.0, 100.0, 800.0, 40.0));
+        assert!(!title_bar.contains(Point { x: 200.0, y: 90.0 }));
+    }
+
+    #[test]
+    fn test_title_bar_undecorated() {
+        let client = Rect::new(0.0, 0.0, 800.0, 600.0);
+        assert_eq!(
+            title_bar_rect(&client, extents(0, 0, 0, 0), None, 40.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_title_bar_fallback_without_properties() {
+        let client = Rect::new(0.0, 0.0, 800.0, 600.0);
+        assert_eq!(
+            title_bar_rect(&client, None, None, 40.0),
+            Some(Rect::new(0.0, 0.0, 800.0, 40.0))
+        );
+    }
+}
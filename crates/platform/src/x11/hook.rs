@@ -0,0 +1,259 @@
+use anyhow::{Context, Result};
+use screenhop_core::trigger::Modifiers;
+use screenhop_core::Point;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xinput::{self, ConnectionExt as _};
+use x11rb::protocol::xproto::{
+    ClientMessageEvent, ConnectionExt as _, CreateWindowAux, EventMask, KeyButMask, Window,
+    WindowClass,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+use crate::{MouseEvent, MouseHook};
+
+/// X11 中键的按钮编号
+const MIDDLE_BUTTON: u32 = 2;
+/// X11 侧键（后退 / 前进）的按钮编号
+const BACK_BUTTON: u32 = 8;
+const FORWARD_BUTTON: u32 = 9;
+/// X11 把滚轮上下滚动报告为按钮 4 / 5
+const WHEEL_UP_BUTTON: u32 = 4;
+const WHEEL_DOWN_BUTTON: u32 = 5;
+
+/// X11 按钮编号转换为 `MouseEvent::button`，不关心的按钮返回 `None`
+pub(crate) fn button_number(detail: u32) -> Option<u32> {
+    match detail {
+        MIDDLE_BUTTON => Some(2),
+        BACK_BUTTON => Some(3),
+        FORWARD_BUTTON => Some(4),
+        _ => None,
+    }
+}
+
+/// X11 滚轮按钮对应的 `MouseEvent::wheel_delta`，其他按钮返回 0
+pub(crate) fn wheel_delta(detail: u32) -> i32 {
+    match detail {
+        WHEEL_UP_BUTTON => 1,
+        WHEEL_DOWN_BUTTON => -1,
+        _ => 0,
+    }
+}
+
+/// 从 QueryPointer 的按键掩码读取修饰键（Mod1 = Alt，Mod4 = Super）
+pub(crate) fn modifiers_from_mask(mask: KeyButMask) -> Modifiers {
+    Modifiers {
+        ctrl: mask.contains(KeyButMask::CONTROL),
+        alt: mask.contains(KeyButMask::MOD1),
+        shift: mask.contains(KeyButMask::SHIFT),
+        cmd: mask.contains(KeyButMask::MOD4),
+    }
+}
+
+/// 正在运行的监听线程
+struct HookThread {
+    conn: Arc<RustConnection>,
+    /// 用于唤醒监听线程的隐藏窗口
+    wakeup_window: Window,
+    handle: JoinHandle<()>,
+}
+
+/// Linux X11 鼠标钩子（基于 XInput2 原始按键事件）
+///
+/// 原始事件只是通知，X server 仍会把点击投递给窗口，因此无法消费事件，
+/// 回调的返回值会被忽略。
+pub struct X11MouseHook {
+    active: Arc<AtomicBool>,
+    thread: Option<HookThread>,
+}
+
+impl Default for X11MouseHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl X11MouseHook {
+    pub fn new() -> Self {
+        Self {
+            active: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+}
+
+impl MouseHook for X11MouseHook {
+    fn start<F>(&mut self, callback: F) -> Result<()>
+    where
+        F: Fn(MouseEvent) -> bool + Send + 'static,
+    {
+        if self.thread.is_some() {
+            self.stop()?;
+        }
+
+        // 监听线程独占一条连接，避免阻塞其他请求
+        let (conn, screen_num) = x11rb::connect(None).context("无法连接 X server")?;
+        let root = conn.setup().roots[screen_num].root;
+
+        conn.extension_information(xinput::X11_EXTENSION_NAME)?
+            .context("X server 不支持 XInput 扩展")?;
+        // 声明支持 XI 2.2：2.1 起原始事件在其他客户端持有抓取时也会送达
+        // （菜单打开、拖拽过程中的点击），2.0 下会丢失
+        let version = conn.xinput_xi_query_version(2, 2)?.reply()?;
+        if version.major_version < 2 {
+            anyhow::bail!(
+                "X server 的 XInput 版本过低: {}.{}",
+                version.major_version,
+                version.minor_version
+            );
+        }
+
+        conn.xinput_xi_select_events(
+            root,
+            &[xinput::EventMask {
+                deviceid: xinput::Device::ALL_MASTER.into(),
+                mask: vec![xinput::XIEventMask::RAW_BUTTON_PRESS],
+            }],
+        )?;
+
+        let wakeup_window = conn.generate_id()?;
+        conn.create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            wakeup_window,
+            root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            x11rb::COPY_FROM_PARENT,
+            &CreateWindowAux::new(),
+        )?;
+        conn.flush()?;
+
+        let conn = Arc::new(conn);
+        let thread_conn = conn.clone();
+        let active = self.active.clone();
+        active.store(true, Ordering::SeqCst);
+
+        let handle = std::thread::Builder::new()
+            .name("x11-mouse-hook".to_string())
+            .spawn(move || {
+                loop {
+                    match thread_conn.wait_for_event() {
+                        Ok(Event::XinputRawButtonPress(event)) => {
+                            let wheel_delta = wheel_delta(event.detail);
+                            let button = match button_number(event.detail) {
+                                Some(button) => button,
+                                None if wheel_delta != 0 => 0,
+                                None => continue,
+                            };
+
+                            // 原始事件不带坐标和修饰键，查询当前指针状态
+                            let pointer = match thread_conn
+                                .query_pointer(root)
+                                .map_err(anyhow::Error::from)
+                                .and_then(|c| c.reply().map_err(anyhow::Error::from))
+                            {
+                                Ok(pointer) => pointer,
+                                Err(e) => {
+                                    log::warn!("查询指针位置失败: {}", e);
+                                    continue;
+                                }
+                            };
+
+                            callback(MouseEvent {
+                                point: Point {
+                                    x: pointer.root_x as f64,
+                                    y: pointer.root_y as f64,
+                                },
+                                button,
+                                wheel_delta,
+                                modifiers: modifiers_from_mask(pointer.mask),
+                            });
+                        }
+                        Ok(Event::ClientMessage(event)) if event.window == wakeup_window => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::error!("X11 事件连接断开: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                active.store(false, Ordering::SeqCst);
+            })
+            .context("无法创建鼠标钩子线程")?;
+
+        self.thread = Some(HookThread {
+            conn,
+            wakeup_window,
+            handle,
+        });
+
+        log::info!("XInput2 鼠标钩子已启动");
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let Some(thread) = self.thread.take() else {
+            return Ok(());
+        };
+
+        // 事件掩码为空时，事件会发送给创建该窗口的客户端，即监听线程自己
+        let event = ClientMessageEvent::new(32, thread.wakeup_window, 0u32, [0u32; 5]);
+        thread
+            .conn
+            .send_event(false, thread.wakeup_window, EventMask::NO_EVENT, event)?;
+        thread.conn.flush()?;
+
+        if thread.handle.join().is_err() {
+            log::error!("鼠标钩子线程异常退出");
+        }
+        let _ = thread.conn.destroy_window(thread.wakeup_window);
+        let _ = thread.conn.flush();
+
+        self.active.store(false, Ordering::SeqCst);
+        log::info!("XInput2 鼠标钩子已停止");
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_button_number() {
+        assert_eq!(button_number(2), Some(2));
+        assert_eq!(button_number(8), Some(3));
+        assert_eq!(button_number(9), Some(4));
+        // 左键和滚轮
+        assert_eq!(button_number(1), None);
+        assert_eq!(button_number(4), None);
+    }
+
+    #[test]
+    fn test_wheel_delta() {
+        assert_eq!(wheel_delta(4), 1);
+        assert_eq!(wheel_delta(5), -1);
+        // 水平滚动和普通按键
+        assert_eq!(wheel_delta(6), 0);
+        assert_eq!(wheel_delta(2), 0);
+    }
+
+    #[test]
+    fn test_modifiers_from_mask() {
+        let modifiers = modifiers_from_mask(KeyButMask::CONTROL | KeyButMask::MOD4);
+        assert!(modifiers.ctrl && modifiers.cmd);
+        assert!(!modifiers.alt && !modifiers.shift);
+    }
+}
@@ -0,0 +1,272 @@
+use anyhow::{Context, Result};
+use screenhop_core::hotkey::{Key, KeyCombo};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    ClientMessageEvent, ConnectionExt as _, CreateWindowAux, EventMask, Grab, GrabMode, Keycode,
+    ModMask, Window, WindowClass,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+use crate::KeyboardHook;
+
+// 核心协议的修饰键掩码
+const MASK_SHIFT: u16 = 0x0001;
+const MASK_LOCK: u16 = 0x0002;
+const MASK_CONTROL: u16 = 0x0004;
+const MASK_MOD1: u16 = 0x0008;
+const MASK_MOD2: u16 = 0x0010;
+const MASK_MOD4: u16 = 0x0040;
+
+/// 参与匹配的修饰键；NumLock（Mod2）和 CapsLock（Lock）不影响快捷键
+const RELEVANT_MODIFIERS: u16 = MASK_SHIFT | MASK_CONTROL | MASK_MOD1 | MASK_MOD4;
+
+/// 需要额外抓取的锁定键组合（无、CapsLock、NumLock、两者）
+const LOCK_VARIANTS: [u16; 4] = [0, MASK_LOCK, MASK_MOD2, MASK_LOCK | MASK_MOD2];
+
+/// 主键对应的 keysym
+pub(crate) fn keysym(key: Key) -> u32 {
+    match key {
+        // 小写字母的 keysym 与 ASCII 相同
+        Key::Letter(c) => c.to_ascii_lowercase() as u32,
+        Key::Digit(d) => b'0' as u32 + d as u32,
+        Key::F(n) => 0xFFBE + (n as u32 - 1),
+        Key::Left => 0xFF51,
+        Key::Up => 0xFF52,
+        Key::Right => 0xFF53,
+        Key::Down => 0xFF54,
+        Key::Home => 0xFF50,
+        Key::End => 0xFF57,
+        Key::PageUp => 0xFF55,
+        Key::PageDown => 0xFF56,
+        Key::Space => 0x0020,
+        Key::Tab => 0xFF09,
+        Key::Enter => 0xFF0D,
+        Key::Escape => 0xFF1B,
+    }
+}
+
+/// 组合键的修饰键掩码（Mod1 = Alt，Mod4 = Super）
+pub(crate) fn modifier_mask(combo: &KeyCombo) -> u16 {
+    let mut mask = 0;
+    if combo.modifiers.shift {
+        mask |= MASK_SHIFT;
+    }
+    if combo.modifiers.ctrl {
+        mask |= MASK_CONTROL;
+    }
+    if combo.modifiers.alt {
+        mask |= MASK_MOD1;
+    }
+    if combo.modifiers.cmd {
+        mask |= MASK_MOD4;
+    }
+    mask
+}
+
+/// 在键盘映射中查找产生 `keysym` 的键码
+fn find_keycode(conn: &RustConnection, keysym: u32) -> Result<Option<Keycode>> {
+    let setup = conn.setup();
+    let min = setup.min_keycode;
+    let count = setup.max_keycode - min + 1;
+    let mapping = conn.get_keyboard_mapping(min, count)?.reply()?;
+    let per_keycode = mapping.keysyms_per_keycode.max(1) as usize;
+
+    Ok(mapping
+        .keysyms
+        .chunks(per_keycode)
+        .position(|syms| syms.contains(&keysym))
+        .map(|index| min + index as u8))
+}
+
+/// 正在运行的监听线程
+struct HookThread {
+    conn: Arc<RustConnection>,
+    root: Window,
+    /// 用于唤醒监听线程的隐藏窗口
+    wakeup_window: Window,
+    handle: JoinHandle<()>,
+}
+
+/// Linux X11 全局快捷键（基于 XGrabKey）
+///
+/// 在根窗口上抓取组合键，被抓取的按键不会再送给其他窗口。
+/// 每个组合键额外抓取 CapsLock / NumLock 的组合，避免锁定键导致快捷键失效。
+pub struct X11KeyboardHook {
+    active: Arc<AtomicBool>,
+    thread: Option<HookThread>,
+}
+
+impl Default for X11KeyboardHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl X11KeyboardHook {
+    pub fn new() -> Self {
+        Self {
+            active: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+}
+
+impl KeyboardHook for X11KeyboardHook {
+    fn start<F>(&mut self, hotkeys: &[KeyCombo], callback: F) -> Result<()>
+    where
+        F: Fn(usize) + Send + 'static,
+    {
+        if self.thread.is_some() {
+            self.stop()?;
+        }
+
+        // 监听线程独占一条连接，抓取随连接关闭自动释放
+        let (conn, screen_num) = x11rb::connect(None).context("无法连接 X server")?;
+        let root = conn.setup().roots[screen_num].root;
+
+        let mut bindings: HashMap<(Keycode, u16), usize> = HashMap::new();
+        for (index, combo) in hotkeys.iter().enumerate() {
+            let Some(keycode) = find_keycode(&conn, keysym(combo.key))? else {
+                log::warn!("键盘布局中没有按键 {}，跳过快捷键 {}", combo.key, combo);
+                continue;
+            };
+
+            let mask = modifier_mask(combo);
+            let mut grabbed = true;
+            for lock in LOCK_VARIANTS {
+                let result = conn
+                    .grab_key(
+                        false,
+                        root,
+                        ModMask::from(mask | lock),
+                        keycode,
+                        GrabMode::ASYNC,
+                        GrabMode::ASYNC,
+                    )?
+                    .check();
+                if let Err(e) = result {
+                    log::warn!("快捷键 {} 已被其他程序占用: {:?}", combo, e);
+                    grabbed = false;
+                    break;
+                }
+            }
+
+            if grabbed {
+                bindings.insert((keycode, mask), index);
+                log::info!("已注册快捷键 {}", combo);
+            }
+        }
+
+        let wakeup_window = conn.generate_id()?;
+        conn.create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            wakeup_window,
+            root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            x11rb::COPY_FROM_PARENT,
+            &CreateWindowAux::new(),
+        )?;
+        conn.flush()?;
+
+        let conn = Arc::new(conn);
+        let thread_conn = conn.clone();
+        let active = self.active.clone();
+        active.store(true, Ordering::SeqCst);
+
+        let handle = std::thread::Builder::new()
+            .name("x11-hotkeys".to_string())
+            .spawn(move || {
+                loop {
+                    match thread_conn.wait_for_event() {
+                        Ok(Event::KeyPress(event)) => {
+                            let mask = u16::from(event.state) & RELEVANT_MODIFIERS;
+                            if let Some(&index) = bindings.get(&(event.detail, mask)) {
+                                callback(index);
+                            }
+                        }
+                        Ok(Event::ClientMessage(event)) if event.window == wakeup_window => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::error!("X11 快捷键连接断开: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                active.store(false, Ordering::SeqCst);
+            })
+            .context("无法创建快捷键线程")?;
+
+        self.thread = Some(HookThread {
+            conn,
+            root,
+            wakeup_window,
+            handle,
+        });
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let Some(thread) = self.thread.take() else {
+            return Ok(());
+        };
+
+        let _ = thread.conn.ungrab_key(Grab::ANY, thread.root, ModMask::ANY);
+        let event = ClientMessageEvent::new(32, thread.wakeup_window, 0u32, [0u32; 5]);
+        thread
+            .conn
+            .send_event(false, thread.wakeup_window, EventMask::NO_EVENT, event)?;
+        thread.conn.flush()?;
+
+        if thread.handle.join().is_err() {
+            log::error!("快捷键线程异常退出");
+        }
+        let _ = thread.conn.destroy_window(thread.wakeup_window);
+        let _ = thread.conn.flush();
+
+        self.active.store(false, Ordering::SeqCst);
+        log::info!("X11 快捷键已注销");
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for X11KeyboardHook {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keysym() {
+        assert_eq!(keysym(Key::Letter('H')), 0x68);
+        assert_eq!(keysym(Key::Digit(1)), 0x31);
+        assert_eq!(keysym(Key::F(1)), 0xFFBE);
+        assert_eq!(keysym(Key::F(12)), 0xFFC9);
+        assert_eq!(keysym(Key::Right), 0xFF53);
+    }
+
+    #[test]
+    fn test_modifier_mask() {
+        let combo = KeyCombo::parse("Ctrl+Super+Left").unwrap();
+        assert_eq!(modifier_mask(&combo), MASK_CONTROL | MASK_MOD4);
+        assert_eq!(modifier_mask(&combo) & !RELEVANT_MODIFIERS, 0);
+    }
+}
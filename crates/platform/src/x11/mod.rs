@@ -0,0 +1,244 @@
+pub mod hittest;
+pub mod hook;
+pub mod hotkey;
+pub mod monitor;
+pub mod window;
+
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{Atom, ClientMessageEvent, ConnectionExt as _, EventMask, Window};
+use x11rb::rust_connection::RustConnection;
+
+x11rb::atom_manager! {
+    /// 用到的 EWMH / GTK / RandR 原子
+    pub(crate) Atoms: AtomsCookie {
+        _NET_ACTIVE_WINDOW,
+        _NET_MOVERESIZE_WINDOW,
+        _NET_WORKAREA,
+        _NET_CURRENT_DESKTOP,
+        _NET_CLIENT_LIST_STACKING,
+        _NET_FRAME_EXTENTS,
+        _GTK_FRAME_EXTENTS,
+        _NET_WM_DESKTOP,
+        _NET_WM_NAME,
+        _NET_WM_PID,
+        _NET_WM_STATE,
+        _NET_WM_STATE_HIDDEN,
+        _NET_WM_STATE_MAXIMIZED_VERT,
+        _NET_WM_STATE_MAXIMIZED_HORZ,
+        _NET_WM_WINDOW_TYPE,
+        _NET_WM_WINDOW_TYPE_DOCK,
+        _NET_WM_WINDOW_TYPE_DESKTOP,
+        UTF8_STRING,
+        EDID,
+    }
+}
+
+/// EWMH 消息来源标识：2 = 分页器 / 桌面工具（窗口管理器会无条件执行）
+pub(crate) const SOURCE_INDICATION_PAGER: u32 = 2;
+
+/// 共享的 X server 连接
+pub struct X11Connection {
+    pub(crate) conn: RustConnection,
+    pub(crate) root: Window,
+    pub(crate) atoms: Atoms,
+}
+
+impl X11Connection {
+    /// 连接 `$DISPLAY` 指定的 X server
+    pub fn connect() -> Result<Arc<Self>> {
+        let (conn, screen_num) = x11rb::connect(None).context("无法连接 X server")?;
+        let root = conn.setup().roots[screen_num].root;
+        let atoms = Atoms::new(&conn)
+            .context("无法获取 X11 原子")?
+            .reply()
+            .context("无法获取 X11 原子")?;
+
+        Ok(Arc::new(Self { conn, root, atoms }))
+    }
+
+    /// 读取 32 位格式的属性（CARDINAL / WINDOW / ATOM），属性不存在时返回 `None`
+    pub(crate) fn get_u32_property(
+        &self,
+        window: Window,
+        property: Atom,
+        type_: impl Into<Atom>,
+    ) -> Option<Vec<u32>> {
+        let reply = self
+            .conn
+            .get_property(false, window, property, type_, 0, u32::MAX / 4)
+            .ok()?
+            .reply()
+            .ok()?;
+        let values: Vec<u32> = reply.value32()?.collect();
+
+        (!values.is_empty()).then_some(values)
+    }
+
+    /// 读取 8 位格式的属性（STRING / UTF8_STRING），属性不存在时返回 `None`
+    pub(crate) fn get_bytes_property(
+        &self,
+        window: Window,
+        property: impl Into<Atom>,
+        type_: impl Into<Atom>,
+    ) -> Option<Vec<u8>> {
+        let reply = self
+            .conn
+            .get_property(false, window, property, type_, 0, u32::MAX / 4)
+            .ok()?
+            .reply()
+            .ok()?;
+
+        (reply.format == 8 && !reply.value.is_empty()).then_some(reply.value)
+    }
+
+    /// 向根窗口发送 EWMH 客户端消息
+    pub(crate) fn send_client_message(
+        &self,
+        window: Window,
+        message_type: Atom,
+        data: [u32; 5],
+    ) -> Result<()> {
+        let event = ClientMessageEvent::new(32, window, message_type, data);
+        self.conn.send_event(
+            false,
+            self.root,
+            EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+            event,
+        )?;
+        self.conn.flush()?;
+        Ok(())
+    }
+}
+
+/// Linux X11 平台实现集合
+pub struct X11Platform {
+    pub hook: hook::X11MouseHook,
+    pub keyboard: hotkey::X11KeyboardHook,
+    pub window_manager: window::X11WindowManager,
+    pub hit_tester: hittest::X11HitTester,
+    pub monitor_manager: monitor::X11MonitorManager,
+}
+
+impl X11Platform {
+    pub fn new() -> Result<Self> {
+        let conn = X11Connection::connect()?;
+
+        Ok(Self {
+            hook: hook::X11MouseHook::new(),
+            keyboard: hotkey::X11KeyboardHook::new(),
+            window_manager: window::X11WindowManager::new(conn.clone()),
+            hit_tester: hittest::X11HitTester::new(conn.clone()),
+            monitor_manager: monitor::X11MonitorManager::new(conn),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KeyboardHook, MonitorManager, MouseHook, WindowManager};
+    use screenhop_core::Point;
+    use x11rb::protocol::xproto::{AtomEnum, CreateWindowAux, PropMode, WindowClass};
+    use x11rb::wrapper::ConnectionExt as _;
+
+    // 以下测试需要 Xvfb 和一个支持 EWMH 的窗口管理器，例如：
+    // Xvfb :99 -screen 0 1920x1080x24 & DISPLAY=:99 openbox &
+    // DISPLAY=:99 cargo test -p screenhop-platform -- --ignored
+
+    #[test]
+    #[ignore = "需要 X server（如 Xvfb）"]
+    fn test_monitors_from_randr() {
+        let platform = X11Platform::new().unwrap();
+        let monitors = platform.monitor_manager.get_monitors();
+
+        assert!(!monitors.is_empty());
+        for monitor in &monitors {
+            assert!(monitor.bounds.contains(Point {
+                x: monitor.work_area.x,
+                y: monitor.work_area.y,
+            }));
+            assert_eq!(monitor.scale_factor, 1.0);
+        }
+    }
+
+    #[test]
+    #[ignore = "需要 X server（如 Xvfb）"]
+    fn test_subscribe_monitor_changes() {
+        let platform = X11Platform::new().unwrap();
+        assert!(platform.monitor_manager.subscribe_changes(Box::new(|| {})));
+    }
+
+    #[test]
+    #[ignore = "需要 X server 与 EWMH 窗口管理器"]
+    fn test_move_window() {
+        let conn = X11Connection::connect().unwrap();
+        let wm = window::X11WindowManager::new(conn.clone());
+
+        let window = conn.conn.generate_id().unwrap();
+        conn.conn
+            .create_window(
+                x11rb::COPY_DEPTH_FROM_PARENT,
+                window,
+                conn.root,
+                100,
+                100,
+                400,
+                300,
+                0,
+                WindowClass::INPUT_OUTPUT,
+                x11rb::COPY_FROM_PARENT,
+                &CreateWindowAux::new(),
+            )
+            .unwrap();
+        conn.conn
+            .change_property8(
+                PropMode::REPLACE,
+                window,
+                AtomEnum::WM_NAME,
+                AtomEnum::STRING,
+                b"screenhop-test",
+            )
+            .unwrap();
+        conn.conn.map_window(window).unwrap();
+        conn.conn.sync().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        let handle = crate::WindowHandle {
+            inner: window as u64,
+        };
+        wm.set_window_position(&handle, Point { x: 200.0, y: 150.0 })
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        let frame = wm.get_window_frame(&handle).unwrap();
+        assert_eq!((frame.x, frame.y), (200.0, 150.0));
+
+        conn.conn.destroy_window(window).unwrap();
+        conn.conn.flush().unwrap();
+    }
+
+    #[test]
+    #[ignore = "需要 X server（如 Xvfb）"]
+    fn test_hook_start_stop() {
+        let mut hook = hook::X11MouseHook::new();
+        hook.start(|_| false).unwrap();
+        assert!(hook.is_active());
+
+        hook.stop().unwrap();
+        assert!(!hook.is_active());
+    }
+
+    #[test]
+    #[ignore = "需要 X server（如 Xvfb）"]
+    fn test_keyboard_hook_start_stop() {
+        let combo = screenhop_core::hotkey::KeyCombo::parse("Ctrl+Alt+F12").unwrap();
+        let mut keyboard = hotkey::X11KeyboardHook::new();
+        keyboard.start(&[combo], |_| {}).unwrap();
+        assert!(keyboard.is_active());
+
+        keyboard.stop().unwrap();
+        assert!(!keyboard.is_active());
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use screenhop_core::edid::Edid;
+use screenhop_core::{monitor, MonitorInfo, Rect};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use x11rb::connection::Connection;
+use x11rb::protocol::randr::{ConnectionExt as _, NotifyMask, Output};
+use x11rb::protocol::xproto::{AtomEnum, ChangeWindowAttributesAux, ConnectionExt as _, EventMask};
+use x11rb::protocol::Event;
+
+use super::window::X11WindowManager;
+use super::X11Connection;
+use crate::{ChangeListeners, MonitorChangeCallback, MonitorManager, WindowHandle, WindowManager};
+
+/// Linux X11 显示器管理器（基于 RandR 1.5 monitors 与 `_NET_WORKAREA`）
+///
+/// 订阅变化后在独立连接上监听 RandR 事件和根窗口的 `_NET_WORKAREA` 属性变化。
+pub struct X11MonitorManager {
+    conn: Arc<X11Connection>,
+    listeners: ChangeListeners,
+    watching: Arc<AtomicBool>,
+}
+
+/// 由 `_NET_WORKAREA` 推算单个显示器的工作区
+///
+/// EWMH 只提供覆盖整个屏幕的一块工作区（每个桌面一块），这里取它与显示器范围的交集；
+/// 不相交时（如面板只在另一块屏上）退化为显示器完整区域。
+pub(crate) fn monitor_work_area(bounds: &Rect, workarea: Option<&Rect>) -> Rect {
+    workarea
+        .and_then(|area| bounds.intersection(area))
+        .unwrap_or(*bounds)
+}
+
+impl X11MonitorManager {
+    pub fn new(conn: Arc<X11Connection>) -> Self {
+        Self {
+            conn,
+            listeners: ChangeListeners::default(),
+            watching: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 在后台线程中监听 RandR 显示器变化
+    fn watch_changes(&self) -> Result<()> {
+        // 监听线程独占一条连接，避免与共享连接上的请求互相阻塞
+        let (conn, screen_num) = x11rb::connect(None).context("无法连接 X server")?;
+        let root = conn.setup().roots[screen_num].root;
+        let workarea = conn.intern_atom(false, b"_NET_WORKAREA")?.reply()?.atom;
+
+        // 声明 1.2 以上的版本，X server 才会发送 RRNotify 事件
+        conn.randr_query_version(1, 5)?.reply()?;
+        conn.randr_select_input(
+            root,
+            NotifyMask::SCREEN_CHANGE | NotifyMask::CRTC_CHANGE | NotifyMask::OUTPUT_CHANGE,
+        )?;
+        conn.change_window_attributes(
+            root,
+            &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+        )?;
+        conn.flush()?;
+
+        let listeners = self.listeners.clone();
+        let watching = self.watching.clone();
+        watching.store(true, Ordering::SeqCst);
+
+        std::thread::Builder::new()
+            .name("x11-randr-events".to_string())
+            .spawn(move || {
+                loop {
+                    match conn.wait_for_event() {
+                        Ok(Event::RandrScreenChangeNotify(_)) | Ok(Event::RandrNotify(_)) => {
+                            log::info!("显示器布局变化（RandR）");
+                            listeners.notify();
+                        }
+                        Ok(Event::PropertyNotify(event)) if event.atom == workarea => {
+                            listeners.notify();
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::warn!("RandR 事件连接断开: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                // 之后不会再有通知，最后清空一次订阅者的缓存
+                watching.store(false, Ordering::SeqCst);
+                listeners.notify();
+            })
+            .context("无法创建 RandR 事件线程")?;
+
+        Ok(())
+    }
+
+    /// 当前桌面的 `_NET_WORKAREA`
+    fn current_workarea(&self) -> Option<Rect> {
+        let atoms = &self.conn.atoms;
+        let values =
+            self.conn
+                .get_u32_property(self.conn.root, atoms._NET_WORKAREA, AtomEnum::CARDINAL)?;
+        let desktop = self
+            .conn
+            .get_u32_property(
+                self.conn.root,
+                atoms._NET_CURRENT_DESKTOP,
+                AtomEnum::CARDINAL,
+            )
+            .and_then(|v| v.first().copied())
+            .unwrap_or(0) as usize;
+
+        let area = values
+            .chunks_exact(4)
+            .nth(desktop)
+            .or_else(|| values.chunks_exact(4).next())?;
+
+        Some(Rect::new(
+            area[0] as i32 as f64,
+            area[1] as i32 as f64,
+            area[2] as f64,
+            area[3] as f64,
+        ))
+    }
+
+    /// 输出口名称（如 "DP-1"）
+    fn output_name(&self, output: Output) -> Option<String> {
+        let info = self
+            .conn
+            .conn
+            .randr_get_output_info(output, x11rb::CURRENT_TIME)
+            .ok()?
+            .reply()
+            .ok()?;
+
+        String::from_utf8(info.name).ok()
+    }
+
+    /// 读取输出口的 EDID
+    fn output_edid(&self, output: Output) -> Option<Edid> {
+        let reply = self
+            .conn
+            .conn
+            .randr_get_output_property(
+                output,
+                self.conn.atoms.EDID,
+                AtomEnum::ANY,
+                0,
+                128,
+                false,
+                false,
+            )
+            .ok()?
+            .reply()
+            .ok()?;
+
+        Edid::parse(&reply.data).ok()
+    }
+}
+
+impl MonitorManager for X11MonitorManager {
+    fn get_monitors(&self) -> Vec<MonitorInfo> {
+        let reply = match self
+            .conn
+            .conn
+            .randr_get_monitors(self.conn.root, true)
+            .map_err(anyhow::Error::from)
+            .and_then(|cookie| cookie.reply().map_err(anyhow::Error::from))
+        {
+            Ok(reply) => reply,
+            Err(e) => {
+                log::error!("RandR 获取显示器失败: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let workarea = self.current_workarea();
+        let mut monitors: Vec<MonitorInfo> = reply
+            .monitors
+            .iter()
+            .map(|m| {
+                let bounds = Rect::new(m.x as f64, m.y as f64, m.width as f64, m.height as f64);
+                let output = m.outputs.first().copied();
+                let connector = output.and_then(|o| self.output_name(o));
+                let edid = output.and_then(|o| self.output_edid(o));
+
+                let name = edid
+                    .as_ref()
+                    .and_then(|e| e.monitor_name.clone())
+                    .or_else(|| connector.clone())
+                    .unwrap_or_default();
+
+                MonitorInfo {
+                    id: output.unwrap_or(m.name) as u64,
+                    bounds,
+                    work_area: monitor_work_area(&bounds, workarea.as_ref()),
+                    name,
+                    manufacturer: edid.as_ref().map(|e| e.manufacturer.clone()),
+                    product_code: edid.as_ref().map(|e| e.product_code),
+                    serial: edid.as_ref().and_then(|e| e.serial()),
+                    connector,
+                    is_primary: m.primary,
+                    // X11 坐标即物理像素，且没有按显示器缩放的概念
+                    scale_factor: 1.0,
+                }
+            })
+            .collect();
+
+        monitor::sort_monitors(&mut monitors);
+        monitors
+    }
+
+    fn get_monitor_for_window(&self, handle: &WindowHandle) -> Option<MonitorInfo> {
+        let wm = X11WindowManager::new(self.conn.clone());
+        let frame = wm.get_window_frame(handle)?;
+        let monitors = self.get_monitors();
+
+        monitor::find_monitor_for_rect(&frame, &monitors).map(|idx| monitors[idx].clone())
+    }
+
+    fn subscribe_changes(&self, callback: MonitorChangeCallback) -> bool {
+        if self.listeners.add(callback) {
+            if let Err(e) = self.watch_changes() {
+                log::error!("无法监听 RandR 显示器变化: {}", e);
+            }
+        }
+        self.watching.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monitor_work_area() {
+        let left = Rect::new(0.0, 0.0, 1920.0, 1080.0);
+        let right = Rect::new(1920.0, 0.0, 2560.0, 1440.0);
+        // 顶部面板 28px，横跨两块屏
+        let workarea = Rect::new(0.0, 28.0, 4480.0, 1412.0);
+
+        assert_eq!(
+            monitor_work_area(&left, Some(&workarea)),
+            Rect::new(0.0, 28.0, 1920.0, 1052.0)
+        );
+        assert_eq!(
+            monitor_work_area(&right, Some(&workarea)),
+            Rect::new(1920.0, 28.0, 2560.0, 1412.0)
+        );
+        assert_eq!(monitor_work_area(&left, None), left);
+    }
+}
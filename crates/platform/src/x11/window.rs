@@ -0,0 +1,400 @@
+use anyhow::Result;
+use screenhop_core::rules::WindowInfo;
+use screenhop_core::{Point, Rect};
+use std::sync::Arc;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, Window};
+use x11rb::CURRENT_TIME;
+
+use super::{X11Connection, SOURCE_INDICATION_PAGER};
+use crate::{WindowHandle, WindowManager};
+
+/// `_NET_WM_DESKTOP` 取此值表示窗口出现在所有桌面上
+const ALL_DESKTOPS: u32 = 0xFFFF_FFFF;
+
+/// `_NET_MOVERESIZE_WINDOW` 标志位
+const GRAVITY_NORTH_WEST: u32 = 1;
+const MOVERESIZE_X: u32 = 1 << 8;
+const MOVERESIZE_Y: u32 = 1 << 9;
+const MOVERESIZE_WIDTH: u32 = 1 << 10;
+const MOVERESIZE_HEIGHT: u32 = 1 << 11;
+
+/// `_NET_WM_STATE` 操作
+const NET_WM_STATE_REMOVE: u32 = 0;
+const NET_WM_STATE_ADD: u32 = 1;
+
+/// 窗口管理器装饰的边框宽度（`_NET_FRAME_EXTENTS`）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct FrameExtents {
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+    pub bottom: f64,
+}
+
+impl FrameExtents {
+    /// 从属性值解析（顺序为 left, right, top, bottom）
+    pub(crate) fn from_property(values: &[u32]) -> Option<Self> {
+        match values {
+            [left, right, top, bottom, ..] => Some(Self {
+                left: *left as f64,
+                right: *right as f64,
+                top: *top as f64,
+                bottom: *bottom as f64,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 由客户区矩形得到包含装饰的外框
+    pub(crate) fn outer(&self, client: &Rect) -> Rect {
+        Rect::new(
+            client.x - self.left,
+            client.y - self.top,
+            client.width + self.left + self.right,
+            client.height + self.top + self.bottom,
+        )
+    }
+
+    /// 去掉边距后的内部区域（用于从客户区中扣除 GTK 阴影）
+    pub(crate) fn inner(&self, rect: &Rect) -> Rect {
+        Rect::new(
+            rect.x + self.left,
+            rect.y + self.top,
+            (rect.width - self.left - self.right).max(0.0),
+            (rect.height - self.top - self.bottom).max(0.0),
+        )
+    }
+
+    /// 外框尺寸对应的客户区尺寸
+    pub(crate) fn client_size(&self, width: f64, height: f64) -> (f64, f64) {
+        (
+            (width - self.left - self.right).max(1.0),
+            (height - self.top - self.bottom).max(1.0),
+        )
+    }
+}
+
+/// 构造 `_NET_MOVERESIZE_WINDOW` 的消息数据（坐标为外框左上角，尺寸为客户区尺寸）
+pub(crate) fn moveresize_data(position: Option<Point>, size: Option<(f64, f64)>) -> [u32; 5] {
+    let mut flags = GRAVITY_NORTH_WEST | (SOURCE_INDICATION_PAGER << 12);
+    let mut data = [0u32; 5];
+
+    if let Some(pos) = position {
+        flags |= MOVERESIZE_X | MOVERESIZE_Y;
+        data[1] = pos.x.round() as i32 as u32;
+        data[2] = pos.y.round() as i32 as u32;
+    }
+    if let Some((width, height)) = size {
+        flags |= MOVERESIZE_WIDTH | MOVERESIZE_HEIGHT;
+        data[3] = width.round() as u32;
+        data[4] = height.round() as u32;
+    }
+
+    data[0] = flags;
+    data
+}
+
+/// 从 `WM_CLASS`（`instance\0class\0`）中取出类名，没有类名时退回实例名
+pub(crate) fn parse_wm_class(value: &[u8]) -> Option<String> {
+    let mut parts = value
+        .split(|&b| b == 0)
+        .filter(|part| !part.is_empty())
+        .map(|part| String::from_utf8_lossy(part).into_owned());
+    let instance = parts.next()?;
+    Some(parts.next().unwrap_or(instance))
+}
+
+/// Linux X11 窗口管理器（基于 EWMH）
+pub struct X11WindowManager {
+    conn: Arc<X11Connection>,
+}
+
+impl X11WindowManager {
+    pub fn new(conn: Arc<X11Connection>) -> Self {
+        Self { conn }
+    }
+
+    /// 按从上到下的 Z 序列出顶层客户窗口
+    fn clients_top_down(&self) -> Vec<Window> {
+        let atoms = &self.conn.atoms;
+        let mut clients = self
+            .conn
+            .get_u32_property(
+                self.conn.root,
+                atoms._NET_CLIENT_LIST_STACKING,
+                AtomEnum::WINDOW,
+            )
+            .unwrap_or_default();
+        clients.reverse();
+        clients
+    }
+
+    /// 窗口是否可以被移动：排除最小化、Dock / 桌面类型以及不在当前桌面上的窗口
+    fn is_movable(&self, window: Window) -> bool {
+        let atoms = &self.conn.atoms;
+
+        if self.has_state(window, atoms._NET_WM_STATE_HIDDEN) {
+            return false;
+        }
+
+        let types = self
+            .conn
+            .get_u32_property(window, atoms._NET_WM_WINDOW_TYPE, AtomEnum::ATOM)
+            .unwrap_or_default();
+        if types.contains(&atoms._NET_WM_WINDOW_TYPE_DOCK)
+            || types.contains(&atoms._NET_WM_WINDOW_TYPE_DESKTOP)
+        {
+            return false;
+        }
+
+        let current = self
+            .conn
+            .get_u32_property(
+                self.conn.root,
+                atoms._NET_CURRENT_DESKTOP,
+                AtomEnum::CARDINAL,
+            )
+            .and_then(|v| v.first().copied());
+        let desktop = self
+            .conn
+            .get_u32_property(window, atoms._NET_WM_DESKTOP, AtomEnum::CARDINAL)
+            .and_then(|v| v.first().copied());
+
+        match (current, desktop) {
+            (Some(current), Some(desktop)) => desktop == current || desktop == ALL_DESKTOPS,
+            _ => true,
+        }
+    }
+
+    fn has_state(&self, window: Window, state: u32) -> bool {
+        self.conn
+            .get_u32_property(window, self.conn.atoms._NET_WM_STATE, AtomEnum::ATOM)
+            .is_some_and(|states| states.contains(&state))
+    }
+
+    fn frame_extents(&self, window: Window) -> FrameExtents {
+        self.net_frame_extents(window).unwrap_or_default()
+    }
+
+    /// 窗口管理器装饰（`_NET_FRAME_EXTENTS`），属性不存在时返回 `None`
+    pub(crate) fn net_frame_extents(&self, window: Window) -> Option<FrameExtents> {
+        self.conn
+            .get_u32_property(
+                window,
+                self.conn.atoms._NET_FRAME_EXTENTS,
+                AtomEnum::CARDINAL,
+            )
+            .and_then(|v| FrameExtents::from_property(&v))
+    }
+
+    /// 客户端装饰窗口的阴影边距（`_GTK_FRAME_EXTENTS`），属性不存在时返回 `None`
+    pub(crate) fn gtk_frame_extents(&self, window: Window) -> Option<FrameExtents> {
+        self.conn
+            .get_u32_property(
+                window,
+                self.conn.atoms._GTK_FRAME_EXTENTS,
+                AtomEnum::CARDINAL,
+            )
+            .and_then(|v| FrameExtents::from_property(&v))
+    }
+
+    /// 客户区在根窗口坐标系中的矩形
+    pub(crate) fn client_rect(&self, window: Window) -> Option<Rect> {
+        let geometry = self.conn.conn.get_geometry(window).ok()?.reply().ok()?;
+        let origin = self
+            .conn
+            .conn
+            .translate_coordinates(window, self.conn.root, 0, 0)
+            .ok()?
+            .reply()
+            .ok()?;
+
+        Some(Rect::new(
+            origin.dst_x as f64,
+            origin.dst_y as f64,
+            geometry.width as f64,
+            geometry.height as f64,
+        ))
+    }
+
+    /// 包含窗口管理器装饰的外框
+    fn outer_frame(&self, window: Window) -> Option<Rect> {
+        let client = self.client_rect(window)?;
+        Some(self.frame_extents(window).outer(&client))
+    }
+
+    /// 窗口标题，优先使用 UTF-8 的 `_NET_WM_NAME`
+    fn title(&self, window: Window) -> Option<String> {
+        let atoms = &self.conn.atoms;
+        self.conn
+            .get_bytes_property(window, atoms._NET_WM_NAME, atoms.UTF8_STRING)
+            .or_else(|| {
+                self.conn
+                    .get_bytes_property(window, AtomEnum::WM_NAME, AtomEnum::STRING)
+            })
+            .map(|title| String::from_utf8_lossy(&title).into_owned())
+    }
+
+    fn set_maximized(&self, window: Window, maximized: bool) -> Result<()> {
+        let atoms = &self.conn.atoms;
+        let action = if maximized {
+            NET_WM_STATE_ADD
+        } else {
+            NET_WM_STATE_REMOVE
+        };
+
+        self.conn.send_client_message(
+            window,
+            atoms._NET_WM_STATE,
+            [
+                action,
+                atoms._NET_WM_STATE_MAXIMIZED_VERT,
+                atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+                SOURCE_INDICATION_PAGER,
+                0,
+            ],
+        )
+    }
+}
+
+impl WindowManager for X11WindowManager {
+    fn get_window_at(&self, point: Point) -> Option<WindowHandle> {
+        let window = self.clients_top_down().into_iter().find(|&window| {
+            self.is_movable(window)
+                && self
+                    .outer_frame(window)
+                    .is_some_and(|frame| frame.contains(point))
+        })?;
+
+        Some(WindowHandle {
+            inner: window as u64,
+        })
+    }
+
+    fn get_focused_window(&self) -> Option<WindowHandle> {
+        // 0 表示没有活动窗口
+        let window = self
+            .conn
+            .get_u32_property(
+                self.conn.root,
+                self.conn.atoms._NET_ACTIVE_WINDOW,
+                AtomEnum::WINDOW,
+            )
+            .and_then(|v| v.first().copied())
+            .filter(|&window| window != 0)?;
+
+        self.is_movable(window).then_some(WindowHandle {
+            inner: window as u64,
+        })
+    }
+
+    fn get_window_frame(&self, handle: &WindowHandle) -> Option<Rect> {
+        self.outer_frame(handle.inner as Window)
+    }
+
+    fn get_window_info(&self, handle: &WindowHandle) -> WindowInfo {
+        let window = handle.inner as Window;
+        let pid = self
+            .conn
+            .get_u32_property(window, self.conn.atoms._NET_WM_PID, AtomEnum::CARDINAL)
+            .and_then(|v| v.first().copied());
+
+        WindowInfo {
+            process_name: pid.and_then(crate::linux::process_name),
+            bundle_id: None,
+            class: self
+                .conn
+                .get_bytes_property(window, AtomEnum::WM_CLASS, AtomEnum::STRING)
+                .and_then(|value| parse_wm_class(&value)),
+            title: self.title(window),
+        }
+    }
+
+    fn set_window_position(&self, handle: &WindowHandle, pos: Point) -> Result<()> {
+        self.conn.send_client_message(
+            handle.inner as Window,
+            self.conn.atoms._NET_MOVERESIZE_WINDOW,
+            moveresize_data(Some(pos), None),
+        )
+    }
+
+    fn set_window_size(&self, handle: &WindowHandle, width: f64, height: f64) -> Result<()> {
+        let window = handle.inner as Window;
+        let size = self.frame_extents(window).client_size(width, height);
+
+        self.conn.send_client_message(
+            window,
+            self.conn.atoms._NET_MOVERESIZE_WINDOW,
+            moveresize_data(None, Some(size)),
+        )
+    }
+
+    fn activate_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.conn.send_client_message(
+            handle.inner as Window,
+            self.conn.atoms._NET_ACTIVE_WINDOW,
+            [SOURCE_INDICATION_PAGER, CURRENT_TIME, 0, 0, 0],
+        )
+    }
+
+    fn is_maximized(&self, handle: &WindowHandle) -> bool {
+        let window = handle.inner as Window;
+        let atoms = &self.conn.atoms;
+
+        self.has_state(window, atoms._NET_WM_STATE_MAXIMIZED_VERT)
+            && self.has_state(window, atoms._NET_WM_STATE_MAXIMIZED_HORZ)
+    }
+
+    fn restore_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.set_maximized(handle.inner as Window, false)
+    }
+
+    fn maximize_window(&self, handle: &WindowHandle) -> Result<()> {
+        self.set_maximized(handle.inner as Window, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_extents_outer_and_client_size() {
+        let extents = FrameExtents::from_property(&[2, 2, 28, 2]).unwrap();
+        let client = Rect::new(102.0, 128.0, 800.0, 600.0);
+
+        let outer = extents.outer(&client);
+        assert_eq!(outer, Rect::new(100.0, 100.0, 804.0, 630.0));
+        assert_eq!(
+            extents.client_size(outer.width, outer.height),
+            (800.0, 600.0)
+        );
+    }
+
+    #[test]
+    fn test_frame_extents_invalid_property() {
+        assert_eq!(FrameExtents::from_property(&[1, 2]), None);
+    }
+
+    #[test]
+    fn test_parse_wm_class() {
+        assert_eq!(
+            parse_wm_class(b"navigator\0firefox\0").as_deref(),
+            Some("firefox")
+        );
+        assert_eq!(parse_wm_class(b"xterm\0").as_deref(), Some("xterm"));
+        assert_eq!(parse_wm_class(b""), None);
+    }
+
+    #[test]
+    fn test_moveresize_data() {
+        let data = moveresize_data(Some(Point { x: -10.0, y: 20.4 }), None);
+        assert_eq!(data[0], 1 | (1 << 8) | (1 << 9) | (2 << 12));
+        assert_eq!(data[1] as i32, -10);
+        assert_eq!(data[2], 20);
+
+        let data = moveresize_data(None, Some((800.0, 600.0)));
+        assert_eq!(data[0], 1 | (1 << 10) | (1 << 11) | (2 << 12));
+        assert_eq!(&data[3..], &[800, 600]);
+    }
+}